@@ -3,7 +3,7 @@ use core::mem;
 use cosmwasm_std::{ContractResult, Empty, Response};
 use cosmwasm_vm::executor::{
 	AllocateCall, AsFunctionName, DeallocateCall, ExecuteCall, InstantiateCall, MigrateCall,
-	QueryCall, ReplyCall,
+	QueryCall, ReplyCall, SudoCall,
 };
 use serde::Serialize;
 use wasm_instrument::parity_wasm::{
@@ -41,6 +41,7 @@ pub struct ModuleDefinition {
 	migrate_call: MigrateFn,
 	query_call: QueryFn,
 	reply_call: ReplyFn,
+	sudo_call: SudoFn,
 	table: Option<Table>,
 	additional_functions: Vec<Function>,
 	additional_binary_size: usize,
@@ -84,6 +85,7 @@ impl ModuleDefinition {
 			migrate_call: MigrateFn::new().map_err(|_| Error::Internal)?,
 			query_call: QueryFn::new().map_err(|_| Error::Internal)?,
 			reply_call: ReplyFn::new().map_err(|_| Error::Internal)?,
+			sudo_call: SudoFn::new().map_err(|_| Error::Internal)?,
 			table,
 			additional_functions,
 			additional_binary_size,
@@ -118,6 +120,21 @@ impl ModuleDefinition {
 			migrate_call: MigrateFn::new().map_err(|_| Error::Internal)?,
 			query_call: QueryFn::new().map_err(|_| Error::Internal)?,
 			reply_call: ReplyFn::new().map_err(|_| Error::Internal)?,
+			sudo_call: SudoFn::new().map_err(|_| Error::Internal)?,
+			table: None,
+			additional_functions: Vec::new(),
+			additional_binary_size: 0,
+		})
+	}
+
+	pub fn with_sudo_response<S: Serialize>(response: S) -> Result<Self, Error> {
+		Ok(Self {
+			instantiate_call: InstantiateFn::new().map_err(|_| Error::Internal)?,
+			execute_call: ExecuteFn::new().map_err(|_| Error::Internal)?,
+			migrate_call: MigrateFn::new().map_err(|_| Error::Internal)?,
+			query_call: QueryFn::new().map_err(|_| Error::Internal)?,
+			reply_call: ReplyFn::new().map_err(|_| Error::Internal)?,
+			sudo_call: SudoFn(SudoFn::plain(response).map_err(|_| Error::Internal)?),
 			table: None,
 			additional_functions: Vec::new(),
 			additional_binary_size: 0,
@@ -319,6 +336,20 @@ impl ReplyFn {
 	}
 }
 
+#[derive(Debug)]
+struct SudoFn(FuncBody);
+
+impl EntrypointCall for SudoFn {
+	const MSG_PTR_INDEX: u32 = 2;
+}
+
+impl SudoFn {
+	pub fn new() -> Result<Self, serde_json::Error> {
+		let response = Response::<Empty>::default();
+		Ok(SudoFn(Self::plain(ContractResult::Ok(response))?))
+	}
+}
+
 impl From<ModuleDefinition> for WasmModule {
 	#[allow(clippy::too_many_lines, clippy::cast_possible_truncation)]
 	fn from(def: ModuleDefinition) -> Self {
@@ -473,6 +504,15 @@ impl From<ModuleDefinition> for WasmModule {
 			function_definitions.push(function);
 		}
 
+		// fn sudo(env_ptr: u32, msg_ptr: u32) -> u32;
+		// Defined last so that the indices of the user defined functions are kept.
+		function_definitions.push(Function {
+			name: <SudoCall>::NAME.into(),
+			params: vec![ValueType::I32, ValueType::I32],
+			result: Some(ValueType::I32),
+			definition: def.sudo_call.0,
+		});
+
 		// we target wasm32 so this will not truncate
 		#[allow(clippy::cast_possible_wrap)]
 		for (i, func) in function_definitions.into_iter().enumerate() {
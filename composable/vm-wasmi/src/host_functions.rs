@@ -138,6 +138,44 @@ where
 	}
 }
 
+/// Reads the key of the next item in an iterator with the given iterator id.
+///
+/// Returns `0` once the iterator is exhausted.
+#[cfg(feature = "iterator")]
+pub fn env_db_next_key<V, S>(mut vm: WasmiVM<V, S>, iterator_id: i32) -> Result<i32, VmErrorOf<V>>
+where
+	V: WasmiBaseVM,
+	S: AsContextMut<UserState = V>,
+{
+	log::debug!("db_next_key");
+	match vm.db_next_key(iterator_id as u32)? {
+		Some(key) => {
+			let Tagged(key_pointer, _) = passthrough_in::<WasmiVM<V, S>, ()>(&mut vm, &key)?;
+			Ok(key_pointer as i32)
+		},
+		None => Ok(0),
+	}
+}
+
+/// Reads the value of the next item in an iterator with the given iterator id.
+///
+/// Returns `0` once the iterator is exhausted.
+#[cfg(feature = "iterator")]
+pub fn env_db_next_value<V, S>(mut vm: WasmiVM<V, S>, iterator_id: i32) -> Result<i32, VmErrorOf<V>>
+where
+	V: WasmiBaseVM,
+	S: AsContextMut<UserState = V>,
+{
+	log::debug!("db_next_value");
+	match vm.db_next_value(iterator_id as u32)? {
+		Some(value) => {
+			let Tagged(value_pointer, _) = passthrough_in::<WasmiVM<V, S>, ()>(&mut vm, &value)?;
+			Ok(value_pointer as i32)
+		},
+		None => Ok(0),
+	}
+}
+
 /// Validates the address.
 ///
 /// Returns `0` if the address is valid, otherwise pointer to the error string.
@@ -332,6 +370,237 @@ where
 	}
 }
 
+/// Verifies `secp256r1` signature with the given public key and message hash.
+///
+/// Returns `0` if success, otherwise `1`.
+pub fn env_secp256r1_verify<V, S>(
+	mut vm: WasmiVM<V, S>,
+	message_hash_ptr: i32,
+	signature_ptr: i32,
+	public_key_ptr: i32,
+) -> Result<i32, VmErrorOf<V>>
+where
+	V: WasmiBaseVM,
+	S: AsContextMut<UserState = V>,
+{
+	let message_hash = passthrough_out::<
+		WasmiVM<V, S>,
+		ConstantReadLimit<{ constants::MAX_LENGTH_MESSAGE_HASH }>,
+	>(&vm, message_hash_ptr as u32)?;
+	let signature = passthrough_out::<
+		WasmiVM<V, S>,
+		ConstantReadLimit<{ constants::EDCSA_SIGNATURE_LENGTH }>,
+	>(&vm, signature_ptr as u32)?;
+	let public_key = passthrough_out::<
+		WasmiVM<V, S>,
+		ConstantReadLimit<{ constants::MAX_LENGTH_EDCSA_PUBKEY_LENGTH }>,
+	>(&vm, public_key_ptr as u32)?;
+
+	let result = vm.secp256r1_verify(&message_hash, &signature, &public_key)?;
+
+	Ok(i32::from(!result))
+}
+
+/// Recovers `secp256r1` public key from message hash, signature and recovery parameter.
+///
+/// Returns 64 bit integer.
+/// Returns the pointer to the recovered public key in the lower 32 bits, upper 32 bits MUST
+/// be `0`.
+/// Returns the error code in the upper 32 bits. Lower 32 bits MUST be `0`.
+pub fn env_secp256r1_recover_pubkey<V, S>(
+	mut vm: WasmiVM<V, S>,
+	message_hash_ptr: i32,
+	signature_ptr: i32,
+	recovery_param: i32,
+) -> Result<i64, VmErrorOf<V>>
+where
+	V: WasmiBaseVM,
+	S: AsContextMut<UserState = V>,
+{
+	log::debug!("secp256r1_recover_pubkey");
+	let message_hash = passthrough_out::<
+		WasmiVM<V, S>,
+		ConstantReadLimit<{ constants::MAX_LENGTH_MESSAGE_HASH }>,
+	>(&vm, message_hash_ptr as u32)?;
+	let signature = passthrough_out::<
+		WasmiVM<V, S>,
+		ConstantReadLimit<{ constants::EDCSA_SIGNATURE_LENGTH }>,
+	>(&vm, signature_ptr as u32)?;
+
+	if let Ok(pubkey) =
+		vm.secp256r1_recover_pubkey(&message_hash, &signature, recovery_param as u8)?
+	{
+		// Same encoding as `secp256k1_recover_pubkey`.
+		let Tagged(value_pointer, _) = passthrough_in::<WasmiVM<V, S>, ()>(&mut vm, &pubkey)?;
+		Ok(i64::from(value_pointer))
+	} else {
+		const GENERIC_ERROR_CODE: i64 = 10;
+		Ok(GENERIC_ERROR_CODE << 32)
+	}
+}
+
+/// Aggregates BLS12-381 G1 points.
+///
+/// Writes the aggregated point to `out_ptr`.
+/// Returns `0` if success, otherwise the crypto error code.
+pub fn env_bls12_381_aggregate_g1<V, S>(
+	mut vm: WasmiVM<V, S>,
+	g1s_ptr: i32,
+	out_ptr: i32,
+) -> Result<i32, VmErrorOf<V>>
+where
+	V: WasmiBaseVM,
+	S: AsContextMut<UserState = V>,
+{
+	log::debug!("bls12_381_aggregate_g1");
+	let g1s = passthrough_out::<
+		WasmiVM<V, S>,
+		ConstantReadLimit<{ constants::MAX_LENGTH_BLS12_381_POINTS }>,
+	>(&vm, g1s_ptr as u32)?;
+
+	match vm.bls12_381_aggregate_g1(&g1s)? {
+		Ok(point) => {
+			passthrough_in_to::<WasmiVM<V, S>>(&mut vm, out_ptr as u32, &point)?;
+			Ok(0)
+		},
+		Err(code) => Ok(code as i32),
+	}
+}
+
+/// Aggregates BLS12-381 G2 points.
+///
+/// Writes the aggregated point to `out_ptr`.
+/// Returns `0` if success, otherwise the crypto error code.
+pub fn env_bls12_381_aggregate_g2<V, S>(
+	mut vm: WasmiVM<V, S>,
+	g2s_ptr: i32,
+	out_ptr: i32,
+) -> Result<i32, VmErrorOf<V>>
+where
+	V: WasmiBaseVM,
+	S: AsContextMut<UserState = V>,
+{
+	log::debug!("bls12_381_aggregate_g2");
+	let g2s = passthrough_out::<
+		WasmiVM<V, S>,
+		ConstantReadLimit<{ constants::MAX_LENGTH_BLS12_381_POINTS }>,
+	>(&vm, g2s_ptr as u32)?;
+
+	match vm.bls12_381_aggregate_g2(&g2s)? {
+		Ok(point) => {
+			passthrough_in_to::<WasmiVM<V, S>>(&mut vm, out_ptr as u32, &point)?;
+			Ok(0)
+		},
+		Err(code) => Ok(code as i32),
+	}
+}
+
+/// Checks a BLS12-381 pairing equality.
+///
+/// Returns `0` if the pairings are equal, `1` if they are not, otherwise the crypto error code.
+pub fn env_bls12_381_pairing_equality<V, S>(
+	mut vm: WasmiVM<V, S>,
+	ps_ptr: i32,
+	qs_ptr: i32,
+	r_ptr: i32,
+	s_ptr: i32,
+) -> Result<i32, VmErrorOf<V>>
+where
+	V: WasmiBaseVM,
+	S: AsContextMut<UserState = V>,
+{
+	log::debug!("bls12_381_pairing_equality");
+	let ps = passthrough_out::<
+		WasmiVM<V, S>,
+		ConstantReadLimit<{ constants::MAX_LENGTH_BLS12_381_POINTS }>,
+	>(&vm, ps_ptr as u32)?;
+	let qs = passthrough_out::<
+		WasmiVM<V, S>,
+		ConstantReadLimit<{ constants::MAX_LENGTH_BLS12_381_POINTS }>,
+	>(&vm, qs_ptr as u32)?;
+	let r = passthrough_out::<
+		WasmiVM<V, S>,
+		ConstantReadLimit<{ constants::BLS12_381_G1_POINT_LENGTH }>,
+	>(&vm, r_ptr as u32)?;
+	let s = passthrough_out::<
+		WasmiVM<V, S>,
+		ConstantReadLimit<{ constants::BLS12_381_G2_POINT_LENGTH }>,
+	>(&vm, s_ptr as u32)?;
+
+	match vm.bls12_381_pairing_equality(&ps, &qs, &r, &s)? {
+		Ok(equal) => Ok(i32::from(!equal)),
+		Err(code) => Ok(code as i32),
+	}
+}
+
+/// Hashes a message to a BLS12-381 G1 point.
+///
+/// Writes the point to `out_ptr`.
+/// Returns `0` if success, otherwise the crypto error code.
+pub fn env_bls12_381_hash_to_g1<V, S>(
+	mut vm: WasmiVM<V, S>,
+	hash_function: i32,
+	msg_ptr: i32,
+	dst_ptr: i32,
+	out_ptr: i32,
+) -> Result<i32, VmErrorOf<V>>
+where
+	V: WasmiBaseVM,
+	S: AsContextMut<UserState = V>,
+{
+	log::debug!("bls12_381_hash_to_g1");
+	let msg = passthrough_out::<
+		WasmiVM<V, S>,
+		ConstantReadLimit<{ constants::MAX_LENGTH_BLS12_381_MESSAGE }>,
+	>(&vm, msg_ptr as u32)?;
+	let dst = passthrough_out::<
+		WasmiVM<V, S>,
+		ConstantReadLimit<{ constants::MAX_LENGTH_BLS12_381_DST }>,
+	>(&vm, dst_ptr as u32)?;
+
+	match vm.bls12_381_hash_to_g1(hash_function as u32, &msg, &dst)? {
+		Ok(point) => {
+			passthrough_in_to::<WasmiVM<V, S>>(&mut vm, out_ptr as u32, &point)?;
+			Ok(0)
+		},
+		Err(code) => Ok(code as i32),
+	}
+}
+
+/// Hashes a message to a BLS12-381 G2 point.
+///
+/// Writes the point to `out_ptr`.
+/// Returns `0` if success, otherwise the crypto error code.
+pub fn env_bls12_381_hash_to_g2<V, S>(
+	mut vm: WasmiVM<V, S>,
+	hash_function: i32,
+	msg_ptr: i32,
+	dst_ptr: i32,
+	out_ptr: i32,
+) -> Result<i32, VmErrorOf<V>>
+where
+	V: WasmiBaseVM,
+	S: AsContextMut<UserState = V>,
+{
+	log::debug!("bls12_381_hash_to_g2");
+	let msg = passthrough_out::<
+		WasmiVM<V, S>,
+		ConstantReadLimit<{ constants::MAX_LENGTH_BLS12_381_MESSAGE }>,
+	>(&vm, msg_ptr as u32)?;
+	let dst = passthrough_out::<
+		WasmiVM<V, S>,
+		ConstantReadLimit<{ constants::MAX_LENGTH_BLS12_381_DST }>,
+	>(&vm, dst_ptr as u32)?;
+
+	match vm.bls12_381_hash_to_g2(hash_function as u32, &msg, &dst)? {
+		Ok(point) => {
+			passthrough_in_to::<WasmiVM<V, S>>(&mut vm, out_ptr as u32, &point)?;
+			Ok(0)
+		},
+		Err(code) => Ok(code as i32),
+	}
+}
+
 /// Verifies `ed25519` signature with the given public key and message.
 ///
 /// Returns `0` if success, otherwise `1`.
@@ -581,6 +850,32 @@ pub(crate) fn define<V: WasmiBaseVM>(
 			),
 		)
 		.map_err(Into::<wasmi::Error>::into)?;
+	#[cfg(feature = "iterator")]
+	linker
+		.define(
+			"env",
+			"db_next_key",
+			Func::wrap(
+				ctx.as_context_mut(),
+				|caller: Caller<'_, V>, iterator_id: i32| -> Result<i32, Trap> {
+					env_db_next_key(WasmiVM(caller), iterator_id).map_err(Into::into)
+				},
+			),
+		)
+		.map_err(Into::<wasmi::Error>::into)?;
+	#[cfg(feature = "iterator")]
+	linker
+		.define(
+			"env",
+			"db_next_value",
+			Func::wrap(
+				ctx.as_context_mut(),
+				|caller: Caller<'_, V>, iterator_id: i32| -> Result<i32, Trap> {
+					env_db_next_value(WasmiVM(caller), iterator_id).map_err(Into::into)
+				},
+			),
+		)
+		.map_err(Into::<wasmi::Error>::into)?;
 	linker
 		.define(
 			"env",
@@ -669,6 +964,142 @@ pub(crate) fn define<V: WasmiBaseVM>(
 			),
 		)
 		.map_err(Into::<wasmi::Error>::into)?;
+	linker
+		.define(
+			"env",
+			"secp256r1_verify",
+			Func::wrap(
+				ctx.as_context_mut(),
+				|caller: Caller<'_, V>,
+				 message_hash_ptr: i32,
+				 signature_ptr: i32,
+				 public_key_ptr: i32|
+				 -> Result<i32, Trap> {
+					env_secp256r1_verify(
+						WasmiVM(caller),
+						message_hash_ptr,
+						signature_ptr,
+						public_key_ptr,
+					)
+					.map_err(Into::into)
+				},
+			),
+		)
+		.map_err(Into::<wasmi::Error>::into)?;
+	linker
+		.define(
+			"env",
+			"secp256r1_recover_pubkey",
+			Func::wrap(
+				ctx.as_context_mut(),
+				|caller: Caller<'_, V>,
+				 message_hash_ptr: i32,
+				 signature_ptr: i32,
+				 recovery_param: i32|
+				 -> Result<i64, Trap> {
+					env_secp256r1_recover_pubkey(
+						WasmiVM(caller),
+						message_hash_ptr,
+						signature_ptr,
+						recovery_param,
+					)
+					.map_err(Into::into)
+				},
+			),
+		)
+		.map_err(Into::<wasmi::Error>::into)?;
+	linker
+		.define(
+			"env",
+			"bls12_381_aggregate_g1",
+			Func::wrap(
+				ctx.as_context_mut(),
+				|caller: Caller<'_, V>, g1s_ptr: i32, out_ptr: i32| -> Result<i32, Trap> {
+					env_bls12_381_aggregate_g1(WasmiVM(caller), g1s_ptr, out_ptr)
+						.map_err(Into::into)
+				},
+			),
+		)
+		.map_err(Into::<wasmi::Error>::into)?;
+	linker
+		.define(
+			"env",
+			"bls12_381_aggregate_g2",
+			Func::wrap(
+				ctx.as_context_mut(),
+				|caller: Caller<'_, V>, g2s_ptr: i32, out_ptr: i32| -> Result<i32, Trap> {
+					env_bls12_381_aggregate_g2(WasmiVM(caller), g2s_ptr, out_ptr)
+						.map_err(Into::into)
+				},
+			),
+		)
+		.map_err(Into::<wasmi::Error>::into)?;
+	linker
+		.define(
+			"env",
+			"bls12_381_pairing_equality",
+			Func::wrap(
+				ctx.as_context_mut(),
+				|caller: Caller<'_, V>,
+				 ps_ptr: i32,
+				 qs_ptr: i32,
+				 r_ptr: i32,
+				 s_ptr: i32|
+				 -> Result<i32, Trap> {
+					env_bls12_381_pairing_equality(WasmiVM(caller), ps_ptr, qs_ptr, r_ptr, s_ptr)
+						.map_err(Into::into)
+				},
+			),
+		)
+		.map_err(Into::<wasmi::Error>::into)?;
+	linker
+		.define(
+			"env",
+			"bls12_381_hash_to_g1",
+			Func::wrap(
+				ctx.as_context_mut(),
+				|caller: Caller<'_, V>,
+				 hash_function: i32,
+				 msg_ptr: i32,
+				 dst_ptr: i32,
+				 out_ptr: i32|
+				 -> Result<i32, Trap> {
+					env_bls12_381_hash_to_g1(
+						WasmiVM(caller),
+						hash_function,
+						msg_ptr,
+						dst_ptr,
+						out_ptr,
+					)
+					.map_err(Into::into)
+				},
+			),
+		)
+		.map_err(Into::<wasmi::Error>::into)?;
+	linker
+		.define(
+			"env",
+			"bls12_381_hash_to_g2",
+			Func::wrap(
+				ctx.as_context_mut(),
+				|caller: Caller<'_, V>,
+				 hash_function: i32,
+				 msg_ptr: i32,
+				 dst_ptr: i32,
+				 out_ptr: i32|
+				 -> Result<i32, Trap> {
+					env_bls12_381_hash_to_g2(
+						WasmiVM(caller),
+						hash_function,
+						msg_ptr,
+						dst_ptr,
+						out_ptr,
+					)
+					.map_err(Into::into)
+				},
+			),
+		)
+		.map_err(Into::<wasmi::Error>::into)?;
 	linker
 		.define(
 			"env",
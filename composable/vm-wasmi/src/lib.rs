@@ -234,20 +234,47 @@ where
 	}
 }
 
+/// A wasm module parsed and compiled ahead of its instantiation, along with the engine it was
+/// compiled by.
+///
+/// Preparing a module is the most expensive part of loading a contract, so that implementers
+/// may keep prepared modules around and instantiate them with [`new_wasmi_vm_from_module`].
+pub struct PreparedModule {
+	engine: Engine,
+	module: Module,
+}
+
+impl PreparedModule {
+	/// Parse and compile `code`.
+	///
+	/// Note that validation is not done here, see [`new_wasmi_vm`].
+	pub fn new(code: &[u8]) -> Result<Self, wasmi::Error> {
+		let engine = Engine::default();
+		let module = Module::new(&engine, code).map_err(Into::<wasmi::Error>::into)?;
+		Ok(Self { engine, module })
+	}
+}
+
 /// Note that validation is not done here since the implementers probably wouldn't want
 /// to do an expensive validation on each time they load the same code. So DO NOT forget
 /// to use `CodeValidation` to properly validate the wasm module.
 pub fn new_wasmi_vm<V: WasmiBaseVM>(code: &[u8], data: V) -> Result<OwnedWasmiVM<V>, VmErrorOf<V>> {
-	let engine = Engine::default();
-	let module = Module::new(&engine, code).map_err(Into::<wasmi::Error>::into)?;
+	let module = PreparedModule::new(code)?;
+	new_wasmi_vm_from_module(&module, data)
+}
 
-	let mut store = Store::new(&engine, data);
-	let mut linker = <Linker<V>>::new(&engine);
+/// Instantiate a VM out of an already [`PreparedModule`].
+pub fn new_wasmi_vm_from_module<V: WasmiBaseVM>(
+	PreparedModule { engine, module }: &PreparedModule,
+	data: V,
+) -> Result<OwnedWasmiVM<V>, VmErrorOf<V>> {
+	let mut store = Store::new(engine, data);
+	let mut linker = <Linker<V>>::new(engine);
 
 	host_functions::define(store.as_context_mut(), &mut linker)?;
 
 	let instance = linker
-		.instantiate(&mut store, &module)
+		.instantiate(&mut store, module)
 		.map_err(Into::<wasmi::Error>::into)?
 		.start(&mut store)
 		.map_err(Into::<wasmi::Error>::into)?;
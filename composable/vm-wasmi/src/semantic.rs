@@ -25,8 +25,8 @@ use cosmwasm_std::IbcTimeout;
 use cosmwasm_std::Order;
 use cosmwasm_std::{
 	Addr, Attribute, Binary, BlockInfo, CanonicalAddr, CodeInfoResponse, Coin, ContractInfo,
-	ContractInfoResponse, ContractResult, Empty, Env, Event, MessageInfo, Reply, SystemResult,
-	Timestamp,
+	ContractInfoResponse, ContractResult, Empty, Env, Event, MessageInfo, Reply, SubMsgResult,
+	SystemResult, Timestamp,
 };
 use cosmwasm_vm::{
 	executor::{
@@ -71,6 +71,7 @@ enum SimpleVMError {
 	NoCustomMessage,
 	Unsupported,
 	OutOfGas,
+	InsufficientFunds,
 	#[cfg(feature = "iterator")]
 	IteratorDoesNotExist,
 	CannotDeserialize,
@@ -152,12 +153,16 @@ impl Gas {
 		let parent = self.current_mut();
 		*parent += child;
 	}
+	fn remaining(&self) -> u64 {
+		self.checkpoints.iter().sum()
+	}
 	fn charge(&mut self, value: u64) -> Result<(), SimpleVMError> {
 		let current = self.current_mut();
 		if *current >= value {
 			*current -= value;
 			Ok(())
 		} else {
+			*current = 0;
 			Err(SimpleVMError::OutOfGas)
 		}
 	}
@@ -198,11 +203,17 @@ struct SimpleWasmiVMExtension {
 	storage: BTreeMap<BankAccount, SimpleWasmiVMStorage>,
 	codes: BTreeMap<CosmwasmCodeId, Vec<u8>>,
 	contracts: BTreeMap<BankAccount, CosmwasmContractMeta<BankAccount>>,
+	balances: BTreeMap<BankAccount, BTreeMap<String, u128>>,
 	next_account_id: BankAccount,
 	transaction_depth: u32,
+	/// Storage and balances at the beginning of each pending transaction.
+	snapshots: Vec<Snapshot>,
 	gas: Gas,
 }
 
+type Snapshot =
+	(BTreeMap<BankAccount, SimpleWasmiVMStorage>, BTreeMap<BankAccount, BTreeMap<String, u128>>);
+
 struct SimpleWasmiVM<'a> {
 	executing_module: Option<WasmiModule>,
 	env: Env,
@@ -289,6 +300,7 @@ impl<'a> VMBase for SimpleWasmiVM<'a> {
 	type QueryCustom = Empty;
 	type MessageCustom = Empty;
 	type ContractMeta = CosmwasmContractMeta<BankAccount>;
+	type CodeInfo = CodeInfoResponse;
 	type Address = BankAccount;
 	type CanonicalAddress = CanonicalAddress;
 	type StorageKey = Vec<u8>;
@@ -432,6 +444,15 @@ impl<'a> VMBase for SimpleWasmiVM<'a> {
 		Err(SimpleVMError::NoCustomQuery)
 	}
 
+	fn query_grpc(
+		&mut self,
+		path: String,
+		_: Binary,
+	) -> Result<SystemResult<CosmwasmQueryResult>, Self::Error> {
+		log::debug!("Grpc query: {}", path);
+		Ok(SystemResult::Err(cosmwasm_std::SystemError::UnsupportedRequest { kind: path }))
+	}
+
 	fn message_custom(
 		&mut self,
 		_: Self::MessageCustom,
@@ -440,6 +461,16 @@ impl<'a> VMBase for SimpleWasmiVM<'a> {
 		Err(SimpleVMError::NoCustomMessage)
 	}
 
+	fn message_any(
+		&mut self,
+		type_url: String,
+		_: Binary,
+		_: &mut dyn FnMut(Event),
+	) -> Result<Option<Binary>, Self::Error> {
+		log::debug!("Any message: {}", type_url);
+		Err(SimpleVMError::Unsupported)
+	}
+
 	fn query_raw(
 		&mut self,
 		address: Self::Address,
@@ -472,6 +503,14 @@ impl<'a> VMBase for SimpleWasmiVM<'a> {
 
 	fn burn(&mut self, funds: &[Coin]) -> Result<(), Self::Error> {
 		log::debug!("Burn: {:?}\n{:?}", self.env.contract.address, funds);
+		let account = BankAccount::try_from(self.env.contract.address.clone())?;
+		let mut balances = self.extension.balances.get(&account).cloned().unwrap_or_default();
+		for Coin { denom, amount } in funds {
+			let balance = balances.entry(denom.clone()).or_default();
+			*balance =
+				balance.checked_sub(amount.u128()).ok_or(SimpleVMError::InsufficientFunds)?;
+		}
+		self.extension.balances.insert(account, balances);
 		Ok(())
 	}
 
@@ -497,7 +536,7 @@ impl<'a> VMBase for SimpleWasmiVM<'a> {
 		Err(SimpleVMError::Unsupported)
 	}
 
-	fn query_code_info(&mut self, _: CosmwasmCodeId) -> Result<CodeInfoResponse, Self::Error> {
+	fn query_code_info(&mut self, _: CosmwasmCodeId) -> Result<Self::CodeInfo, Self::Error> {
 		Err(SimpleVMError::Unsupported)
 	}
 
@@ -509,15 +548,26 @@ impl<'a> VMBase for SimpleWasmiVM<'a> {
 	#[cfg(feature = "iterator")]
 	fn db_scan(
 		&mut self,
-		_start: Option<Self::StorageKey>,
-		_end: Option<Self::StorageKey>,
-		_order: Order,
+		start: Option<Self::StorageKey>,
+		end: Option<Self::StorageKey>,
+		order: Order,
 	) -> Result<u32, Self::Error> {
 		let contract_addr = self.env.contract.address.clone().try_into()?;
 		let mut empty = SimpleWasmiVMStorage::default();
 		let storage = self.extension.storage.get_mut(&contract_addr).unwrap_or(&mut empty);
 
-		let data = storage.data.clone().into_iter().collect::<Vec<_>>();
+		let mut data = storage
+			.data
+			.iter()
+			.filter(|(key, _)| {
+				start.as_ref().map_or(true, |start| *key >= start) &&
+					end.as_ref().map_or(true, |end| *key < end)
+			})
+			.map(|(key, value)| (key.clone(), value.clone()))
+			.collect::<Vec<_>>();
+		if order == Order::Descending {
+			data.reverse();
+		}
 		// Exceeding u32 size is fatal
 		let last_id: u32 = storage
 			.iterators
@@ -559,6 +609,19 @@ impl<'a> VMBase for SimpleWasmiVM<'a> {
 		}
 	}
 
+	#[cfg(feature = "iterator")]
+	fn db_next_key(&mut self, iterator_id: u32) -> Result<Option<Self::StorageKey>, Self::Error> {
+		self.db_next(iterator_id).map(|(key, _)| (!key.is_empty()).then_some(key))
+	}
+
+	#[cfg(feature = "iterator")]
+	fn db_next_value(
+		&mut self,
+		iterator_id: u32,
+	) -> Result<Option<Self::StorageValue>, Self::Error> {
+		self.db_next(iterator_id).map(|(key, value)| (!key.is_empty()).then_some(value))
+	}
+
 	fn secp256k1_verify(
 		&mut self,
 		message_hash: &[u8],
@@ -579,6 +642,70 @@ impl<'a> VMBase for SimpleWasmiVM<'a> {
 			.map_err(|_| ()))
 	}
 
+	fn secp256r1_verify(
+		&mut self,
+		message_hash: &[u8],
+		signature: &[u8],
+		public_key: &[u8],
+	) -> Result<bool, Self::Error> {
+		cosmwasm_crypto::secp256r1_verify(message_hash, signature, public_key)
+			.map_err(|_| SimpleVMError::Crypto)
+	}
+
+	fn secp256r1_recover_pubkey(
+		&mut self,
+		message_hash: &[u8],
+		signature: &[u8],
+		recovery_param: u8,
+	) -> Result<Result<Vec<u8>, ()>, Self::Error> {
+		Ok(cosmwasm_crypto::secp256r1_recover_pubkey(message_hash, signature, recovery_param)
+			.map_err(|_| ()))
+	}
+
+	fn bls12_381_aggregate_g1(&mut self, g1s: &[u8]) -> Result<Result<Vec<u8>, u32>, Self::Error> {
+		Ok(cosmwasm_crypto::bls12_381_aggregate_g1(g1s)
+			.map(Vec::from)
+			.map_err(|e| e.code()))
+	}
+
+	fn bls12_381_aggregate_g2(&mut self, g2s: &[u8]) -> Result<Result<Vec<u8>, u32>, Self::Error> {
+		Ok(cosmwasm_crypto::bls12_381_aggregate_g2(g2s)
+			.map(Vec::from)
+			.map_err(|e| e.code()))
+	}
+
+	fn bls12_381_pairing_equality(
+		&mut self,
+		ps: &[u8],
+		qs: &[u8],
+		r: &[u8],
+		s: &[u8],
+	) -> Result<Result<bool, u32>, Self::Error> {
+		Ok(cosmwasm_crypto::bls12_381_pairing_equality(ps, qs, r, s).map_err(|e| e.code()))
+	}
+
+	fn bls12_381_hash_to_g1(
+		&mut self,
+		hash_function: u32,
+		msg: &[u8],
+		dst: &[u8],
+	) -> Result<Result<Vec<u8>, u32>, Self::Error> {
+		Ok(cosmwasm_crypto::HashFunction::from_u32(hash_function)
+			.map(|hash| cosmwasm_crypto::bls12_381_hash_to_g1(hash, msg, dst).to_vec())
+			.map_err(|e| e.code()))
+	}
+
+	fn bls12_381_hash_to_g2(
+		&mut self,
+		hash_function: u32,
+		msg: &[u8],
+		dst: &[u8],
+	) -> Result<Result<Vec<u8>, u32>, Self::Error> {
+		Ok(cosmwasm_crypto::HashFunction::from_u32(hash_function)
+			.map(|hash| cosmwasm_crypto::bls12_381_hash_to_g2(hash, msg, dst).to_vec())
+			.map_err(|e| e.code()))
+	}
+
 	fn ed25519_verify(
 		&mut self,
 		message: &[u8],
@@ -742,6 +869,10 @@ impl<'a> VMBase for SimpleWasmiVM<'a> {
 		}
 	}
 
+	fn gas_remaining(&mut self) -> Result<u64, Self::Error> {
+		Ok(self.extension.gas.remaining())
+	}
+
 	#[cfg(feature = "stargate")]
 	fn ibc_transfer(
 		&mut self,
@@ -810,16 +941,24 @@ impl<'a> Transactional for SimpleWasmiVM<'a> {
 	type Error = SimpleVMError;
 	fn transaction_begin(&mut self) -> Result<(), Self::Error> {
 		self.extension.transaction_depth += 1;
+		self.extension
+			.snapshots
+			.push((self.extension.storage.clone(), self.extension.balances.clone()));
 		log::debug!("> Transaction begin: {}", self.extension.transaction_depth);
 		Ok(())
 	}
 	fn transaction_commit(&mut self) -> Result<(), Self::Error> {
 		self.extension.transaction_depth -= 1;
+		self.extension.snapshots.pop();
 		log::debug!("< Transaction end: {}", self.extension.transaction_depth);
 		Ok(())
 	}
 	fn transaction_rollback(&mut self) -> Result<(), Self::Error> {
 		self.extension.transaction_depth -= 1;
+		if let Some((storage, balances)) = self.extension.snapshots.pop() {
+			self.extension.storage = storage;
+			self.extension.balances = balances;
+		}
 		log::debug!("< Transaction abort: {}", self.extension.transaction_depth);
 		Ok(())
 	}
@@ -983,8 +1122,71 @@ fn test_recursion() {
 	);
 }
 
+/// Tests that `db_next_key` and `db_next_value` return a null region once the iterator is
+/// exhausted.
+///
+/// `cosmwasm-std` only stops `keys`, `values` and `skip`/`nth` when the host returns `0`, any other
+/// region being taken as one more entry.
+#[cfg(feature = "iterator")]
+#[test]
+fn test_db_next_key_value_exhaustion() {
+	use super::{
+		host_functions::{env_db_next_key, env_db_next_value},
+		WasmiVM,
+	};
+	use cosmwasm_vm::executor::{passthrough_out, ConstantReadLimit};
+
+	let code = instrument_contract(include_bytes!("../fixtures/cw20_base.wasm"));
+	let sender = BankAccount(100);
+	let address = BankAccount(10_000);
+	let funds = vec![];
+	let data = BTreeMap::from(
+		[(b"ant", b"1"), (b"foo", b"2"), (b"zoo", b"3")]
+			.map(|(key, value)| (key.to_vec(), value.to_vec())),
+	);
+	let mut extension = SimpleWasmiVMExtension {
+		storage: BTreeMap::from([(address, SimpleWasmiVMStorage { data, ..Default::default() })]),
+		codes: BTreeMap::from([(0x1337, code)]),
+		contracts: BTreeMap::from([(
+			address,
+			CosmwasmContractMeta { code_id: 0x1337, admin: None, label: String::new() },
+		)]),
+		next_account_id: BankAccount(10_001),
+		gas: Gas::new(100_000_000),
+		..Default::default()
+	};
+	let mut vm = create_simple_vm(sender, address, funds, &mut extension).unwrap();
+	let read = |vm: &OwnedWasmiVM<SimpleWasmiVM>, pointer: i32| {
+		passthrough_out::<_, ConstantReadLimit<64>>(vm, pointer as u32).unwrap()
+	};
+
+	// Keys are iterated to the end, then the iterator keeps returning `0`.
+	let iterator_id = vm.db_scan(None, None, Order::Ascending).unwrap() as i32;
+	let mut keys = vec![];
+	for _ in 0..4 {
+		match env_db_next_key(WasmiVM(&mut vm.0), iterator_id).unwrap() {
+			0 => break,
+			pointer => keys.push(read(&vm, pointer)),
+		}
+	}
+	assert_eq!(keys, vec![b"ant".to_vec(), b"foo".to_vec(), b"zoo".to_vec()]);
+	assert_eq!(env_db_next_key(WasmiVM(&mut vm.0), iterator_id).unwrap(), 0);
+	assert_eq!(env_db_next_value(WasmiVM(&mut vm.0), iterator_id).unwrap(), 0);
+
+	// `skip`/`nth` advance with `db_next_key` before reading the remaining values.
+	let iterator_id = vm.db_scan(None, None, Order::Descending).unwrap() as i32;
+	for _ in 0..2 {
+		assert_ne!(env_db_next_key(WasmiVM(&mut vm.0), iterator_id).unwrap(), 0);
+	}
+	let pointer = env_db_next_value(WasmiVM(&mut vm.0), iterator_id).unwrap();
+	assert_eq!(read(&vm, pointer), b"1".to_vec());
+	assert_eq!(env_db_next_value(WasmiVM(&mut vm.0), iterator_id).unwrap(), 0);
+	assert_eq!(env_db_next_key(WasmiVM(&mut vm.0), iterator_id).unwrap(), 0);
+}
+
 mod test_code_gen {
-	use cosmwasm_std::{ContractResult, Response};
+	use cosmwasm_std::{BankMsg, ContractResult, Response};
+	use cosmwasm_vm::executor::{SudoCall, SudoResult};
 
 	use super::*;
 	#[test]
@@ -1054,6 +1256,116 @@ mod test_code_gen {
 		assert_eq!(result, InstantiateResult(response));
 		assert_ne!(result, InstantiateResult(response_2));
 	}
+
+	#[test]
+	fn sudo_response() {
+		let response =
+			ContractResult::Ok(Response::<Empty>::new().add_attribute("Hello", "governance!"));
+		let module: code_gen::WasmModule =
+			code_gen::ModuleDefinition::with_sudo_response(response.clone()).unwrap().into();
+
+		let sender = BankAccount(100);
+		let address = BankAccount(10_000);
+		let funds = vec![];
+		let mut extension = SimpleWasmiVMExtension {
+			storage: BTreeMap::default(),
+			codes: BTreeMap::from([(0x1337, module.code)]),
+			contracts: BTreeMap::from([(
+				address,
+				CosmwasmContractMeta { code_id: 0x1337, admin: None, label: String::new() },
+			)]),
+			next_account_id: BankAccount(10_001),
+			transaction_depth: 0,
+			gas: Gas::new(100_000_000),
+			..Default::default()
+		};
+		let mut vm = create_simple_vm(sender, address, funds, &mut extension).unwrap();
+		assert_eq!(
+			cosmwasm_call::<SudoCall, OwnedWasmiVM<SimpleWasmiVM>>(&mut vm, r#"{}"#.as_bytes())
+				.unwrap(),
+			SudoResult(response)
+		);
+		let (_, events) = cosmwasm_system_entrypoint::<SudoCall, OwnedWasmiVM<SimpleWasmiVM>>(
+			&mut vm,
+			r#"{}"#.as_bytes(),
+		)
+		.unwrap();
+		assert!(events.iter().any(|e| e.ty == "sudo"));
+		assert!(events.iter().any(|e| e
+			.attributes
+			.contains(&Attribute { key: "Hello".into(), value: "governance!".into() })));
+	}
+
+	fn burn_extension(code: Vec<u8>, address: BankAccount) -> SimpleWasmiVMExtension {
+		SimpleWasmiVMExtension {
+			storage: BTreeMap::default(),
+			codes: BTreeMap::from([(0x1337, code)]),
+			contracts: BTreeMap::from([(
+				address,
+				CosmwasmContractMeta { code_id: 0x1337, admin: None, label: String::new() },
+			)]),
+			balances: BTreeMap::from([(
+				address,
+				BTreeMap::from([("uatom".into(), 1_000), ("ujuno".into(), 50)]),
+			)]),
+			next_account_id: BankAccount(10_001),
+			transaction_depth: 0,
+			gas: Gas::new(100_000_000),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn burn() {
+		let response = ContractResult::Ok(Response::<Empty>::new().add_message(BankMsg::Burn {
+			amount: vec![Coin::new(400u128, "uatom"), Coin::new(50u128, "ujuno")],
+		}));
+		let module: code_gen::WasmModule =
+			code_gen::ModuleDefinition::with_instantiate_response(response).unwrap().into();
+
+		let sender = BankAccount(100);
+		let address = BankAccount(10_000);
+		let mut extension = burn_extension(module.code, address);
+		{
+			let mut vm = create_simple_vm(sender, address, vec![], &mut extension).unwrap();
+			cosmwasm_system_entrypoint::<InstantiateCall, OwnedWasmiVM<SimpleWasmiVM>>(
+				&mut vm,
+				r#"{}"#.as_bytes(),
+			)
+			.unwrap();
+		}
+		assert_eq!(
+			extension.balances[&address],
+			BTreeMap::from([("uatom".into(), 600), ("ujuno".into(), 0)])
+		);
+	}
+
+	#[test]
+	fn burn_insufficient_funds() {
+		let response = ContractResult::Ok(Response::<Empty>::new().add_message(BankMsg::Burn {
+			amount: vec![Coin::new(400u128, "uatom"), Coin::new(51u128, "ujuno")],
+		}));
+		let module: code_gen::WasmModule =
+			code_gen::ModuleDefinition::with_instantiate_response(response).unwrap().into();
+
+		let sender = BankAccount(100);
+		let address = BankAccount(10_000);
+		let mut extension = burn_extension(module.code, address);
+		{
+			let mut vm = create_simple_vm(sender, address, vec![], &mut extension).unwrap();
+			assert_matches!(
+				cosmwasm_system_entrypoint::<InstantiateCall, OwnedWasmiVM<SimpleWasmiVM>>(
+					&mut vm,
+					r#"{}"#.as_bytes(),
+				),
+				Err(SimpleVMError::InsufficientFunds)
+			);
+		}
+		assert_eq!(
+			extension.balances[&address],
+			BTreeMap::from([("uatom".into(), 1_000), ("ujuno".into(), 50)])
+		);
+	}
 }
 
 pub fn digit_sum(input: &[u8]) -> usize {
@@ -1327,6 +1639,131 @@ fn test_reply() {
 	}
 }
 
+/// Reflect contract (10000) owned by 100, along with a hackatom contract (10001).
+fn reflect_hackatom_extension() -> SimpleWasmiVMExtension {
+	let code = instrument_contract(include_bytes!("../fixtures/reflect.wasm"));
+	let code_hackatom = instrument_contract(include_bytes!("../fixtures/hackatom.wasm"));
+	let sender = BankAccount(100);
+	let address = BankAccount(10_000);
+	let hackatom_address = BankAccount(10_001);
+	let mut extension = SimpleWasmiVMExtension {
+		storage: BTreeMap::default(),
+		codes: BTreeMap::from([(0x1337, code), (0x1338, code_hackatom)]),
+		contracts: BTreeMap::from([
+			(address, CosmwasmContractMeta { code_id: 0x1337, admin: None, label: String::new() }),
+			(
+				hackatom_address,
+				CosmwasmContractMeta { code_id: 0x1338, admin: None, label: String::new() },
+			),
+		]),
+		next_account_id: BankAccount(10_002),
+		transaction_depth: 0,
+		gas: Gas::new(100_000_000),
+		..Default::default()
+	};
+	{
+		let mut vm = create_simple_vm(address, hackatom_address, vec![], &mut extension).unwrap();
+		cosmwasm_system_entrypoint::<InstantiateCall, _>(
+			&mut vm,
+			r#"{"verifier": "10000", "beneficiary": "10000"}"#.as_bytes(),
+		)
+		.unwrap();
+	}
+	{
+		let mut vm = create_simple_vm(sender, address, vec![], &mut extension).unwrap();
+		cosmwasm_system_entrypoint::<InstantiateCall, OwnedWasmiVM<SimpleWasmiVM>>(
+			&mut vm,
+			r#"{}"#.as_bytes(),
+		)
+		.unwrap();
+	}
+	extension
+}
+
+/// Make the reflect contract send a submessage looping over the storage of the
+/// hackatom contract until it runs out of gas.
+fn reflect_storage_loop(
+	extension: &mut SimpleWasmiVMExtension,
+	gas_limit: Option<u64>,
+	reply_on: &str,
+) -> Result<(Option<Binary>, Vec<Event>), SimpleVMError> {
+	let mut vm = create_simple_vm(BankAccount(100), BankAccount(10_000), vec![], extension)?;
+	cosmwasm_system_entrypoint::<ExecuteCall, OwnedWasmiVM<SimpleWasmiVM>>(
+		&mut vm,
+		format!(
+			r#"{{
+              "reflect_sub_msg": {{
+                "msgs": [{{
+                  "id": 10,
+                  "msg": {{
+                    "wasm": {{
+                      "execute": {{
+                        "contract_addr": "10001",
+                        "msg": "eyJzdG9yYWdlX2xvb3AiOnt9fQ==",
+                        "funds": []
+                      }}
+                    }}
+                  }},
+                  "gas_limit": {},
+                  "reply_on": "{reply_on}"
+                }}]
+              }}
+            }}"#,
+			gas_limit.map_or("null".into(), |gas_limit| gas_limit.to_string()),
+		)
+		.as_bytes(),
+	)
+}
+
+#[test]
+fn test_reply_gas_limited_submessage_out_of_gas() {
+	let mut extension = reflect_hackatom_extension();
+	let hackatom_address = BankAccount(10_001);
+	let hackatom_storage = extension.storage.get(&hackatom_address).unwrap().data.clone();
+
+	reflect_storage_loop(&mut extension, Some(1_000_000), "error").unwrap();
+
+	// Only the submessage state changes are reverted, the caller keeps the
+	// rest of its gas and gets a failure reply.
+	assert_eq!(extension.storage.get(&hackatom_address).unwrap().data, hackatom_storage);
+	assert!(extension.gas.remaining() > 0);
+
+	let mut vm =
+		create_simple_vm(BankAccount(100), BankAccount(10_000), vec![], &mut extension).unwrap();
+	let QueryResult(CosmwasmQueryResult::Ok(reply)) =
+		cosmwasm_call::<QueryCall, OwnedWasmiVM<SimpleWasmiVM>>(
+			&mut vm,
+			r#"{ "sub_msg_result": { "id": 10 } }"#.as_bytes(),
+		)
+		.unwrap()
+	else {
+		panic!("the reply should have been stored");
+	};
+	let reply = serde_json::from_slice::<Reply>(reply.as_slice()).unwrap();
+	assert_matches!(reply.result, SubMsgResult::Err(_));
+	assert!(reply.gas_used >= 1_000_000);
+}
+
+#[test]
+fn test_gas_limited_submessage_out_of_gas_without_reply() {
+	let mut extension = reflect_hackatom_extension();
+	assert!(reflect_storage_loop(&mut extension, Some(1_000_000), "never").is_err());
+	assert!(extension.gas.remaining() > 0);
+}
+
+#[test]
+fn test_unlimited_submessage_out_of_gas() {
+	let mut extension = reflect_hackatom_extension();
+	let hackatom_address = BankAccount(10_001);
+	let hackatom_storage = extension.storage.get(&hackatom_address).unwrap().data.clone();
+
+	// Without a gas limit, the whole budget is consumed and the reply on error
+	// does not save the caller.
+	assert!(reflect_storage_loop(&mut extension, None, "error").is_err());
+	assert_eq!(extension.gas.remaining(), 0);
+	assert_eq!(extension.storage.get(&hackatom_address).unwrap().data, hackatom_storage);
+}
+
 #[cfg(feature = "stargate")]
 mod cw20_ics20 {
 	use super::{
@@ -6,7 +6,7 @@ use cosmwasm_vm::executor::ibc::{
 
 use cosmwasm_vm::executor::{
 	AllocateCall, AsFunctionName, DeallocateCall, ExecuteCall, InstantiateCall, MigrateCall,
-	QueryCall, ReplyCall,
+	QueryCall, ReplyCall, SudoCall,
 };
 use wasm_instrument::parity_wasm::elements::ValueType;
 
@@ -77,6 +77,12 @@ impl Version for Version1x {
 			ReplyCall::<()>::NAME,
 			&[ValueType::I32, ValueType::I32],
 		),
+		(
+			ExportRequirement::Optional,
+			// extern "C" fn sudo(env_ptr: u32, msg_ptr: u32) -> u32;
+			SudoCall::<()>::NAME,
+			&[ValueType::I32, ValueType::I32],
+		),
 	];
 
 	// IBC callbacks that a contract must export to be considered IBC capable:
@@ -10,18 +10,19 @@ use core::{
 #[cfg(feature = "iterator")]
 use cosmwasm_std::Order;
 use cosmwasm_std::{
-	Addr, Binary, CanonicalAddr, CodeInfoResponse, Coin, ContractInfoResponse, Env, Event,
-	MessageInfo, Reply, SystemResult,
+	Addr, Binary, CanonicalAddr, Coin, ContractInfoResponse, Env, Event, MessageInfo, Reply,
+	SystemResult,
 };
 use cosmwasm_vm::{
-	executor::{CosmwasmQueryResult, ExecutorError, QueryResult},
+	executor::{constants, CosmwasmQueryResult, ExecutorError, QueryResult},
 	has::Has,
 	memory::{MemoryReadError, MemoryWriteError, Pointable, ReadableMemory, WritableMemory},
 	system::{CosmwasmCodeId, CosmwasmContractMeta, SystemError},
 	transaction::{Transactional, TransactionalErrorOf},
 	vm::{
-		VMBase, VmAddressOf, VmCanonicalAddressOf, VmContractMetaOf, VmErrorOf, VmGas,
-		VmGasCheckpoint, VmMessageCustomOf, VmQueryCustomOf, VmStorageKeyOf, VmStorageValueOf, VM,
+		VMBase, VmAddressOf, VmCanonicalAddressOf, VmCodeInfoOf, VmContractMetaOf, VmErrorOf,
+		VmGas, VmGasCheckpoint, VmMessageCustomOf, VmQueryCustomOf, VmStorageKeyOf,
+		VmStorageValueOf, VM,
 	},
 };
 use wasmi::{AsContextMut, Extern, Store};
@@ -88,6 +89,7 @@ where
 	type QueryCustom = VmQueryCustomOf<V>;
 	type MessageCustom = VmMessageCustomOf<V>;
 	type ContractMeta = VmContractMetaOf<V>;
+	type CodeInfo = VmCodeInfoOf<V>;
 	type Address = VmAddressOf<V>;
 	type CanonicalAddress = VmCanonicalAddressOf<V>;
 	type StorageKey = VmStorageKeyOf<V>;
@@ -119,6 +121,21 @@ where
 		self.0.as_context_mut().data_mut().db_next(iterator_id)
 	}
 
+	#[cfg(feature = "iterator")]
+	fn db_next_key(&mut self, iterator_id: u32) -> Result<Option<Self::StorageKey>, Self::Error> {
+		self.charge(VmGas::DbNextKey)?;
+		self.0.as_context_mut().data_mut().db_next_key(iterator_id)
+	}
+
+	#[cfg(feature = "iterator")]
+	fn db_next_value(
+		&mut self,
+		iterator_id: u32,
+	) -> Result<Option<Self::StorageValue>, Self::Error> {
+		self.charge(VmGas::DbNextValue)?;
+		self.0.as_context_mut().data_mut().db_next_value(iterator_id)
+	}
+
 	fn set_contract_meta(
 		&mut self,
 		address: Self::Address,
@@ -226,6 +243,15 @@ where
 		self.0.as_context_mut().data_mut().query_custom(query)
 	}
 
+	fn query_grpc(
+		&mut self,
+		path: String,
+		data: Binary,
+	) -> Result<SystemResult<CosmwasmQueryResult>, Self::Error> {
+		self.charge(VmGas::QueryGrpc)?;
+		self.0.as_context_mut().data_mut().query_grpc(path, data)
+	}
+
 	fn message_custom(
 		&mut self,
 		message: Self::MessageCustom,
@@ -235,6 +261,16 @@ where
 		self.0.as_context_mut().data_mut().message_custom(message, event_handler)
 	}
 
+	fn message_any(
+		&mut self,
+		type_url: String,
+		value: Binary,
+		event_handler: &mut dyn FnMut(Event),
+	) -> Result<Option<Binary>, Self::Error> {
+		self.charge(VmGas::MessageAny)?;
+		self.0.as_context_mut().data_mut().message_any(type_url, value, event_handler)
+	}
+
 	fn query_raw(
 		&mut self,
 		address: Self::Address,
@@ -291,7 +327,7 @@ where
 		self.0.as_context_mut().data_mut().query_contract_info(address)
 	}
 
-	fn query_code_info(&mut self, id: CosmwasmCodeId) -> Result<CodeInfoResponse, Self::Error> {
+	fn query_code_info(&mut self, id: CosmwasmCodeId) -> Result<Self::CodeInfo, Self::Error> {
 		self.charge(VmGas::QueryCodeInfo)?;
 		self.0.as_context_mut().data_mut().query_code_info(id)
 	}
@@ -364,6 +400,10 @@ where
 		self.0.as_context_mut().data_mut().gas_ensure_available()
 	}
 
+	fn gas_remaining(&mut self) -> Result<u64, Self::Error> {
+		self.0.as_context_mut().data_mut().gas_remaining()
+	}
+
 	fn secp256k1_verify(
 		&mut self,
 		message_hash: &[u8],
@@ -391,6 +431,77 @@ where
 		)
 	}
 
+	fn secp256r1_verify(
+		&mut self,
+		message_hash: &[u8],
+		signature: &[u8],
+		public_key: &[u8],
+	) -> Result<bool, Self::Error> {
+		self.charge(VmGas::Secp256r1Verify)?;
+		self.0
+			.as_context_mut()
+			.data_mut()
+			.secp256r1_verify(message_hash, signature, public_key)
+	}
+
+	fn secp256r1_recover_pubkey(
+		&mut self,
+		message_hash: &[u8],
+		signature: &[u8],
+		recovery_param: u8,
+	) -> Result<Result<Vec<u8>, ()>, Self::Error> {
+		self.charge(VmGas::Secp256r1RecoverPubkey)?;
+		self.0.as_context_mut().data_mut().secp256r1_recover_pubkey(
+			message_hash,
+			signature,
+			recovery_param,
+		)
+	}
+
+	fn bls12_381_aggregate_g1(&mut self, g1s: &[u8]) -> Result<Result<Vec<u8>, u32>, Self::Error> {
+		let nb_of_points = (g1s.len() / constants::BLS12_381_G1_POINT_LENGTH) as u32;
+		self.charge(VmGas::Bls12381AggregateG1 { nb_of_points })?;
+		self.0.as_context_mut().data_mut().bls12_381_aggregate_g1(g1s)
+	}
+
+	fn bls12_381_aggregate_g2(&mut self, g2s: &[u8]) -> Result<Result<Vec<u8>, u32>, Self::Error> {
+		let nb_of_points = (g2s.len() / constants::BLS12_381_G2_POINT_LENGTH) as u32;
+		self.charge(VmGas::Bls12381AggregateG2 { nb_of_points })?;
+		self.0.as_context_mut().data_mut().bls12_381_aggregate_g2(g2s)
+	}
+
+	fn bls12_381_pairing_equality(
+		&mut self,
+		ps: &[u8],
+		qs: &[u8],
+		r: &[u8],
+		s: &[u8],
+	) -> Result<Result<bool, u32>, Self::Error> {
+		let nb_of_pairs = (ps.len() / constants::BLS12_381_G1_POINT_LENGTH) as u32;
+		self.charge(VmGas::Bls12381PairingEquality { nb_of_pairs })?;
+		self.0.as_context_mut().data_mut().bls12_381_pairing_equality(ps, qs, r, s)
+	}
+
+	fn bls12_381_hash_to_g1(
+		&mut self,
+		hash_function: u32,
+		msg: &[u8],
+		dst: &[u8],
+	) -> Result<Result<Vec<u8>, u32>, Self::Error> {
+		self.charge(VmGas::Bls12381HashToG1)?;
+		self.0.as_context_mut().data_mut().bls12_381_hash_to_g1(hash_function, msg, dst)
+	}
+
+	fn bls12_381_hash_to_g2(
+		&mut self,
+		hash_function: u32,
+		msg: &[u8],
+		dst: &[u8],
+	) -> Result<Result<Vec<u8>, u32>, Self::Error> {
+		self.charge(VmGas::Bls12381HashToG2)?;
+		self.0.as_context_mut().data_mut().bls12_381_hash_to_g2(hash_function, msg, dst)
+	}
+
 	fn ed25519_verify(
 		&mut self,
 		message: &[u8],
@@ -96,6 +96,7 @@ pub type CosmwasmExecutionResult<T = Empty> = ContractResult<Response<T>>;
 pub type CosmwasmQueryResult = ContractResult<QueryResponse>;
 pub type CosmwasmReplyResult<T = Empty> = ContractResult<Response<T>>;
 pub type CosmwasmMigrateResult<T = Empty> = ContractResult<Response<T>>;
+pub type CosmwasmSudoResult<T = Empty> = ContractResult<Response<T>>;
 
 pub type QueryResponse = Binary;
 
@@ -234,6 +235,29 @@ impl<T> From<ContractResult<Response<T>>> for MigrateResult<T> {
 	}
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SudoResult<T = Empty>(pub CosmwasmExecutionResult<T>);
+impl<T> DeserializeLimit for SudoResult<T> {
+	fn deserialize_limit() -> usize {
+		deserialization_limits::RESULT_SUDO
+	}
+}
+impl<T> ReadLimit for SudoResult<T> {
+	fn read_limit() -> usize {
+		read_limits::RESULT_SUDO
+	}
+}
+impl<T> From<SudoResult<T>> for ContractResult<Response<T>> {
+	fn from(SudoResult(result): SudoResult<T>) -> Self {
+		result
+	}
+}
+impl<T> From<ContractResult<Response<T>>> for SudoResult<T> {
+	fn from(value: ContractResult<Response<T>>) -> Self {
+		SudoResult(value)
+	}
+}
+
 pub mod ibc {
 	#![cfg(feature = "stargate")]
 
@@ -639,6 +663,18 @@ impl<T> HasInfo for MigrateCall<T> {
 	const HAS_INFO: bool = false;
 }
 
+/// The type representing a call to a contract `sudo` export.
+pub struct SudoCall<T = Empty>(PhantomData<T>);
+impl<T> Input for SudoCall<T> {
+	type Output = SudoResult<T>;
+}
+impl<T> AsFunctionName for SudoCall<T> {
+	const NAME: &'static str = "sudo";
+}
+impl<T> HasInfo for SudoCall<T> {
+	const HAS_INFO: bool = false;
+}
+
 pub trait AsFunctionName {
 	const NAME: &'static str;
 }
@@ -727,6 +763,16 @@ pub mod constants {
 	pub const MAX_LENGTH_EDCSA_PUBKEY_LENGTH: usize = 65;
 	/// Length of an eddsa public key
 	pub const EDDSA_PUBKEY_LENGTH: usize = 32;
+	/// Length of a compressed BLS12-381 G1 point
+	pub const BLS12_381_G1_POINT_LENGTH: usize = 48;
+	/// Length of a compressed BLS12-381 G2 point
+	pub const BLS12_381_G2_POINT_LENGTH: usize = 96;
+	/// Max length of the points given to the BLS12-381 aggregation and pairing functions
+	pub const MAX_LENGTH_BLS12_381_POINTS: usize = 2 * MI;
+	/// Max length of a message hashed to a BLS12-381 curve
+	pub const MAX_LENGTH_BLS12_381_MESSAGE: usize = 5 * MI;
+	/// Max length of a domain separation tag used to hash to a BLS12-381 curve
+	pub const MAX_LENGTH_BLS12_381_DST: usize = 5 * KI;
 }
 
 /// Allow for untyped marshalling to specify a limit while extracting the bytes from a contract
@@ -11,7 +11,7 @@ use crate::{
 	executor::{
 		cosmwasm_call, AllocateCall, CosmwasmCallInput, CosmwasmCallWithoutInfoInput,
 		CosmwasmQueryResult, DeallocateCall, DeserializeLimit, ExecuteCall, ExecutorError, HasInfo,
-		InstantiateCall, MigrateCall, QueryResult, ReadLimit, ReplyCall, Unit,
+		InstantiateCall, MigrateCall, QueryResult, ReadLimit, ReplyCall, SudoCall, Unit,
 	},
 	has::Has,
 	input::{Input, OutputOf},
@@ -30,10 +30,19 @@ use alloc::{
 	vec::Vec,
 };
 use core::fmt::Debug;
+use cosmos_sdk_proto::{
+	cosmos::bank::v1beta1::MsgSendResponse,
+	cosmwasm::wasm::v1::{
+		MsgClearAdminResponse, MsgExecuteContractResponse, MsgInstantiateContract2Response,
+		MsgInstantiateContractResponse, MsgMigrateContractResponse, MsgUpdateAdminResponse,
+	},
+	traits::Message,
+};
 use cosmwasm_std::{
-	Addr, AllBalanceResponse, Attribute, BalanceResponse, BankMsg, BankQuery, Binary,
-	ContractResult, CosmosMsg, Env, Event, MessageInfo, QueryRequest, Reply, ReplyOn, Response,
-	SubMsgResponse, SubMsgResult, SupplyResponse, SystemResult, WasmMsg, WasmQuery,
+	Addr, AllBalanceResponse, AnyMsg, Attribute, BalanceResponse, BankMsg, BankQuery, Binary,
+	ContractResult, CosmosMsg, Env, Event, GrpcQuery, MessageInfo, MsgResponse, QueryRequest,
+	Reply, ReplyOn, Response, SubMsgResponse, SubMsgResult, SupplyResponse, SystemResult, WasmMsg,
+	WasmQuery,
 };
 #[cfg(feature = "stargate")]
 use cosmwasm_std::{Empty, IbcMsg};
@@ -161,6 +170,10 @@ impl<T> EventHasCodeId for ReplyCall<T> {
 	const HAS_CODE_ID: bool = false;
 }
 
+impl<T> EventHasCodeId for SudoCall<T> {
+	const HAS_CODE_ID: bool = false;
+}
+
 #[cfg(feature = "stargate")]
 impl<T> EventHasCodeId for IbcChannelConnectCall<T> {
 	const HAS_CODE_ID: bool = false;
@@ -206,6 +219,10 @@ impl<T> EventIsTyped for ReplyCall<T> {
 	const TYPE: SystemEventType = SystemEventType::Reply;
 }
 
+impl<T> EventIsTyped for SudoCall<T> {
+	const TYPE: SystemEventType = SystemEventType::Sudo;
+}
+
 #[cfg(feature = "stargate")]
 impl<T> EventIsTyped for IbcChannelConnectCall<T> {
 	const TYPE: SystemEventType = SystemEventType::IbcChannelConnect;
@@ -523,7 +540,7 @@ fn dispatch_submessage<V, I>(
 	msg: CosmosMsg<VmMessageCustomOf<V>>,
 	gas_limit: Option<u64>,
 	event_handler: &mut dyn FnMut(Event),
-) -> Result<(Option<Binary>, Vec<Event>), VmErrorOf<V>>
+) -> Result<(Option<Binary>, Vec<Event>, Vec<MsgResponse>), VmErrorOf<V>>
 where
 	V: CosmwasmCallVM<I> + StargateCosmwasmCallVM,
 {
@@ -542,16 +559,23 @@ where
 		sub_events.push(event);
 	};
 
+	// Along with the data, each message yields the protobuf encoded `Msg*Response` of the
+	// equivalent Cosmos SDK message, if any.
 	let sub_result = (|| match msg {
-		CosmosMsg::Custom(message) =>
-			vm.message_custom(message, &mut sub_event_handler).map_err(Into::into),
+		CosmosMsg::Custom(message) => vm
+			.message_custom(message, &mut sub_event_handler)
+			.map(|data| (data, None))
+			.map_err(Into::into),
 		CosmosMsg::Wasm(wasm_message) => match wasm_message {
 			WasmMsg::Execute { contract_addr, msg, funds } => {
 				let vm_contract_addr = contract_addr.try_into()?;
-				vm.continue_execute(vm_contract_addr, funds, &msg, &mut sub_event_handler)
+				let data =
+					vm.continue_execute(vm_contract_addr, funds, &msg, &mut sub_event_handler)?;
+				let response = MsgExecuteContractResponse { data: to_vec(&data) };
+				Ok((data, msg_response("/cosmwasm.wasm.v1.MsgExecuteContractResponse", &response)))
 			},
-			WasmMsg::Instantiate { admin, code_id, msg, funds, label } => vm
-				.continue_instantiate(
+			WasmMsg::Instantiate { admin, code_id, msg, funds, label } => {
+				let (address, data) = vm.continue_instantiate(
 					CosmwasmContractMeta {
 						code_id,
 						admin: match admin {
@@ -563,10 +587,18 @@ where
 					funds,
 					&msg,
 					&mut sub_event_handler,
-				)
-				.map(|(_, data)| data),
-			WasmMsg::Instantiate2 { admin, code_id, label, msg, funds, salt } => vm
-				.continue_instantiate2(
+				)?;
+				let response = MsgInstantiateContractResponse {
+					address: Into::<Addr>::into(address).into_string(),
+					data: to_vec(&data),
+				};
+				Ok((
+					data,
+					msg_response("/cosmwasm.wasm.v1.MsgInstantiateContractResponse", &response),
+				))
+			},
+			WasmMsg::Instantiate2 { admin, code_id, label, msg, funds, salt } => {
+				let (address, data) = vm.continue_instantiate2(
 					CosmwasmContractMeta {
 						code_id,
 						admin: match admin {
@@ -579,24 +611,46 @@ where
 					&salt,
 					&msg,
 					&mut sub_event_handler,
-				)
-				.map(|(_, data)| data),
+				)?;
+				let response = MsgInstantiateContract2Response {
+					address: Into::<Addr>::into(address).into_string(),
+					data: to_vec(&data),
+				};
+				Ok((
+					data,
+					msg_response("/cosmwasm.wasm.v1.MsgInstantiateContract2Response", &response),
+				))
+			},
 			WasmMsg::Migrate { contract_addr, new_code_id, msg } => {
 				let contract_addr = VmAddressOf::<V>::try_from(contract_addr)?;
 				let sender = VmAddressOf::<V>::try_from(caller_contract.clone().into_string())?;
 				migrate::<V>(vm, sender, contract_addr.clone(), new_code_id)?;
-				vm.continue_migrate(contract_addr, &msg, &mut sub_event_handler)
+				let data = vm.continue_migrate(contract_addr, &msg, &mut sub_event_handler)?;
+				let response = MsgMigrateContractResponse { data: to_vec(&data) };
+				Ok((data, msg_response("/cosmwasm.wasm.v1.MsgMigrateContractResponse", &response)))
 			},
 			WasmMsg::UpdateAdmin { contract_addr, admin: new_admin } => {
 				let new_admin = new_admin.try_into()?;
 				let vm_contract_addr = VmAddressOf::<V>::try_from(contract_addr)?;
 				update_admin::<V>(vm, caller_contract, vm_contract_addr, Some(new_admin))?;
-				Ok(None)
+				Ok((
+					None,
+					msg_response(
+						"/cosmwasm.wasm.v1.MsgUpdateAdminResponse",
+						&MsgUpdateAdminResponse {},
+					),
+				))
 			},
 			WasmMsg::ClearAdmin { contract_addr } => {
 				let vm_contract_addr = VmAddressOf::<V>::try_from(contract_addr)?;
 				update_admin::<V>(vm, caller_contract, vm_contract_addr, None)?;
-				Ok(None)
+				Ok((
+					None,
+					msg_response(
+						"/cosmwasm.wasm.v1.MsgClearAdminResponse",
+						&MsgClearAdminResponse {},
+					),
+				))
 			},
 			_ => Err(SystemError::UnsupportedMessage.into()),
 		},
@@ -604,11 +658,14 @@ where
 			BankMsg::Send { to_address, amount } => {
 				let vm_contract_addr = to_address.try_into()?;
 				vm.transfer(&vm_contract_addr, &amount)?;
-				Ok(None)
+				Ok((
+					None,
+					msg_response("/cosmos.bank.v1beta1.MsgSendResponse", &MsgSendResponse {}),
+				))
 			},
 			BankMsg::Burn { amount } => {
 				vm.burn(&amount)?;
-				Ok(None)
+				Ok((None, None))
 			},
 			_ => Err(SystemError::UnsupportedMessage.into()),
 		},
@@ -616,21 +673,28 @@ where
 		CosmosMsg::Ibc(ibc_message) => match ibc_message {
 			IbcMsg::Transfer { channel_id, to_address, amount, timeout, memo: _ } => {
 				vm.ibc_transfer(channel_id, to_address, amount, timeout)?;
-				Ok(None)
+				Ok((None, None))
 			},
 			IbcMsg::SendPacket { channel_id, data, timeout } => {
 				vm.ibc_send_packet(channel_id, data, timeout)?;
-				Ok(None)
+				Ok((None, None))
 			},
 			IbcMsg::CloseChannel { channel_id } => {
 				vm.ibc_close_channel(channel_id)?;
-				Ok(None)
+				Ok((None, None))
 			},
 			_ => Err(SystemError::UnsupportedMessage.into()),
 		},
-		// TODO(hussein-aitlahcen): determine whether we handle.
 		#[cfg(feature = "stargate")]
-		CosmosMsg::Stargate { .. } => Err(SystemError::UnsupportedMessage.into()),
+		#[allow(deprecated)]
+		CosmosMsg::Stargate { type_url, value } => {
+			let data = vm.message_any(type_url.clone(), value, &mut sub_event_handler)?;
+			Ok((data.clone(), any_msg_response(type_url, data)))
+		},
+		CosmosMsg::Any(AnyMsg { type_url, value }) => {
+			let data = vm.message_any(type_url.clone(), value, &mut sub_event_handler)?;
+			Ok((data.clone(), any_msg_response(type_url, data)))
+		},
 		// TODO(hussein-aitlahcen): determine whether we handle.
 		#[cfg(feature = "stargate")]
 		CosmosMsg::Gov(_) => Err(SystemError::UnsupportedMessage.into()),
@@ -640,7 +704,20 @@ where
 	// Make sure we remove the checkpoint.
 	vm.gas_checkpoint_pop()?;
 
-	sub_result.map(|data| (data, sub_events))
+	sub_result.map(|(data, msg_response)| (data, sub_events, msg_response.into_iter().collect()))
+}
+
+fn to_vec(data: &Option<Binary>) -> Vec<u8> {
+	data.as_ref().map(Binary::to_vec).unwrap_or_default()
+}
+
+fn msg_response<M: Message>(type_url: &str, response: &M) -> Option<MsgResponse> {
+	Some(MsgResponse { type_url: type_url.into(), value: response.encode_to_vec().into() })
+}
+
+/// By convention, the response of a `Msg` service method `Foo` is `FooResponse`.
+fn any_msg_response(type_url: String, data: Option<Binary>) -> Option<MsgResponse> {
+	Some(MsgResponse { type_url: type_url + "Response", value: data.unwrap_or_default() })
 }
 
 #[allow(clippy::too_many_lines)]
@@ -741,6 +818,7 @@ where
 		// like the questionmark operator, as we want to catch both the success
 		// and failure branches here.  Both branches may be used depending on
 		// the reply attached to the message. See reply_on.
+		let gas_remaining = vm.gas_remaining()?;
 		let sub_res = dispatch_submessage(
 			vm,
 			&env.contract.address,
@@ -748,6 +826,7 @@ where
 			submsg.gas_limit,
 			event_handler,
 		);
+		let gas_used = gas_remaining.saturating_sub(vm.gas_remaining()?);
 
 		log::debug!("Submessage result: {:?}", sub_res);
 
@@ -755,7 +834,7 @@ where
 			// If the submessage suceeded and no reply was asked or only on
 			// error, the call is considered successful and state change is
 			// comitted.
-			(Ok((data, _)), ReplyOn::Never | ReplyOn::Error) => {
+			(Ok((data, _, _)), ReplyOn::Never | ReplyOn::Error) => {
 				log::debug!("Commit & Continue");
 				vm.transaction_commit()?;
 				SubCallContinuation::Continue(data)
@@ -763,16 +842,23 @@ where
 			// Similarly to previous case, if the submessage suceeded and we ask
 			// for a reply, the call is considered successful and we redispatch
 			// a reply to the parent contract.
-			(Ok((data, events)), ReplyOn::Always | ReplyOn::Success) => {
+			(Ok((data, events, msg_responses)), ReplyOn::Always | ReplyOn::Success) => {
 				log::debug!("Commit & Reply");
 				vm.transaction_commit()?;
-				// TODO: Handle msg_response
 				SubCallContinuation::Reply(SubMsgResult::Ok(SubMsgResponse {
 					events,
 					data,
-					msg_responses: Vec::new(),
+					msg_responses,
 				}))
 			},
+			// Running out of gas is only recoverable if the submessage was run
+			// with its own gas limit. Otherwise, the whole budget is gone and
+			// the transaction is aborted, whether a reply was asked or not.
+			(Err(e), _) if submsg.gas_limit.is_none() && vm.gas_ensure_available().is_err() => {
+				log::debug!("Out of gas, Rollback & Abort");
+				vm.transaction_rollback()?;
+				SubCallContinuation::Abort(e)
+			},
 			// If the submessage failed and a reply is required, rollback the
 			// state change and dispatch a reply to the parent contract. The
 			// transaction is not aborted unless the reply also fails
@@ -805,13 +891,12 @@ where
 			// the reply and optionally overwrite the current data with with the
 			// one yield by the reply.
 			SubCallContinuation::Reply(response) => {
-				// TODO: Handle payload and gas_used
 				let new_data = vm.continue_reply(
 					Reply {
 						id: submsg.id,
 						result: response.clone(),
-						payload: Binary::default(),
-						gas_used: 0,
+						payload: submsg.payload,
+						gas_used,
 					},
 					event_handler,
 				)?;
@@ -889,6 +974,10 @@ where
 			},
 			_ => Err(SystemError::UnsupportedMessage.into()),
 		},
+		#[cfg(feature = "stargate")]
+		#[allow(deprecated)]
+		QueryRequest::Stargate { path, data } => Ok(vm.query_grpc(path, data)?),
+		QueryRequest::Grpc(GrpcQuery { path, data }) => Ok(vm.query_grpc(path, data)?),
 		_ => Err(SystemError::UnsupportedMessage.into()),
 	}
 }
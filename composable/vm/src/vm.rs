@@ -9,11 +9,9 @@ use core::fmt::Debug;
 use cosmwasm_std::IbcTimeout;
 #[cfg(feature = "iterator")]
 use cosmwasm_std::Order;
-use cosmwasm_std::{
-	Binary, CodeInfoResponse, Coin, ContractInfoResponse, Event, Reply, SystemResult,
-};
+use cosmwasm_std::{Binary, Coin, ContractInfoResponse, Event, Reply, SystemResult};
 
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Serialize};
 
 /// Gas checkpoint, used to meter sub-call gas usage.
 #[allow(clippy::module_name_repetitions)]
@@ -49,8 +47,12 @@ pub enum VmGas {
 	ContinueReply,
 	/// Cost of `query_custom`.
 	QueryCustom,
+	/// Cost of `query_grpc`.
+	QueryGrpc,
 	/// Cost of `message_custom`.
 	MessageCustom,
+	/// Cost of `message_any`.
+	MessageAny,
 	/// Cost of `query_raw`.
 	QueryRaw,
 	/// Cost of `transfer`.
@@ -79,12 +81,32 @@ pub enum VmGas {
 	#[cfg(feature = "iterator")]
 	/// Cost of `db_next`.
 	DbNext,
+	#[cfg(feature = "iterator")]
+	/// Cost of `db_next_key`.
+	DbNextKey,
+	#[cfg(feature = "iterator")]
+	/// Cost of `db_next_value`.
+	DbNextValue,
 	/// Cost of `debug`
 	Debug,
 	/// Cost of `secp256k1_verify`
 	Secp256k1Verify,
 	/// Cost of `secp256k1_recover_pubkey`
 	Secp256k1RecoverPubkey,
+	/// Cost of `secp256r1_verify`
+	Secp256r1Verify,
+	/// Cost of `secp256r1_recover_pubkey`
+	Secp256r1RecoverPubkey,
+	/// Cost of `bls12_381_aggregate_g1`
+	Bls12381AggregateG1 { nb_of_points: u32 },
+	/// Cost of `bls12_381_aggregate_g2`
+	Bls12381AggregateG2 { nb_of_points: u32 },
+	/// Cost of `bls12_381_pairing_equality`
+	Bls12381PairingEquality { nb_of_pairs: u32 },
+	/// Cost of `bls12_381_hash_to_g1`
+	Bls12381HashToG1,
+	/// Cost of `bls12_381_hash_to_g2`
+	Bls12381HashToG2,
 	/// Cost of `ed25519_verify`
 	Ed25519Verify,
 	/// Cost of `ed25519_batch_verify`
@@ -126,6 +148,8 @@ pub type VmStorageKeyOf<T> = <T as VMBase>::StorageKey;
 pub type VmStorageValueOf<T> = <T as VMBase>::StorageValue;
 #[allow(clippy::module_name_repetitions)]
 pub type VmContractMetaOf<T> = <T as VMBase>::ContractMeta;
+#[allow(clippy::module_name_repetitions)]
+pub type VmCodeInfoOf<T> = <T as VMBase>::CodeInfo;
 
 /// A way of calling a VM. From the abstract `call` to `raw_call`.
 pub trait VM: VMBase {
@@ -157,6 +181,8 @@ pub trait VMBase {
 	type MessageCustom: DeserializeOwned + Debug;
 	/// Metadata of a contract.
 	type ContractMeta;
+	/// Metadata of a code, answered to `WasmQuery::CodeInfo`.
+	type CodeInfo: Serialize;
 	/// Unique identifier for contract instances and users under the system.
 	type Address;
 	/// Binary representation of `Address`.
@@ -188,6 +214,19 @@ pub trait VMBase {
 		iterator_id: u32,
 	) -> Result<(Self::StorageKey, Self::StorageValue), Self::Error>;
 
+	#[cfg(feature = "iterator")]
+	/// Returns the key of the next element of the iterator with the given ID, skipping the value.
+	/// Returns `None` once the iterator is exhausted.
+	fn db_next_key(&mut self, iterator_id: u32) -> Result<Option<Self::StorageKey>, Self::Error>;
+
+	#[cfg(feature = "iterator")]
+	/// Returns the value of the next element of the iterator with the given ID, skipping the key.
+	/// Returns `None` once the iterator is exhausted.
+	fn db_next_value(
+		&mut self,
+		iterator_id: u32,
+	) -> Result<Option<Self::StorageValue>, Self::Error>;
+
 	/// Change the contract meta of a contract, actually migrating it.
 	fn set_contract_meta(
 		&mut self,
@@ -255,6 +294,14 @@ pub trait VMBase {
 		query: Self::QueryCustom,
 	) -> Result<SystemResult<CosmwasmQueryResult>, Self::Error>;
 
+	/// Protobuf encoded query (`QueryRequest::Grpc`, formerly `QueryRequest::Stargate`) at the
+	/// fully qualified service `path`.
+	fn query_grpc(
+		&mut self,
+		path: String,
+		data: Binary,
+	) -> Result<SystemResult<CosmwasmQueryResult>, Self::Error>;
+
 	/// Custom `CosmWasm` message. Usually a host extension.
 	fn message_custom(
 		&mut self,
@@ -262,6 +309,15 @@ pub trait VMBase {
 		event_handler: &mut dyn FnMut(Event),
 	) -> Result<Option<Binary>, Self::Error>;
 
+	/// Protobuf encoded message (`CosmosMsg::Any`, formerly `CosmosMsg::Stargate`), dispatched
+	/// by the host on behalf of the current contract.
+	fn message_any(
+		&mut self,
+		type_url: String,
+		value: Binary,
+		event_handler: &mut dyn FnMut(Event),
+	) -> Result<Option<Binary>, Self::Error>;
+
 	/// Query raw value in a contract db.
 	fn query_raw(
 		&mut self,
@@ -299,7 +355,7 @@ pub trait VMBase {
 	) -> Result<ContractInfoResponse, Self::Error>;
 
 	/// Query the code info.
-	fn query_code_info(&mut self, id: CosmwasmCodeId) -> Result<CodeInfoResponse, Self::Error>;
+	fn query_code_info(&mut self, id: CosmwasmCodeId) -> Result<Self::CodeInfo, Self::Error>;
 
 	/// Log the message
 	fn debug(&mut self, message: Vec<u8>) -> Result<(), Self::Error>;
@@ -353,6 +409,9 @@ pub trait VMBase {
 	/// Ensure that some gas is available.
 	fn gas_ensure_available(&mut self) -> Result<(), Self::Error>;
 
+	/// Gas remaining, summed over all the checkpoints.
+	fn gas_remaining(&mut self) -> Result<u64, Self::Error>;
+
 	/// Verifies `message_hash` against a `signature` with a `public_key`, using the
 	/// secp256k1 ECDSA parametrization.
 	fn secp256k1_verify(
@@ -374,6 +433,72 @@ pub trait VMBase {
 		recovery_param: u8,
 	) -> Result<Result<Vec<u8>, ()>, Self::Error>;
 
+	/// Verifies `message_hash` against a `signature` with a `public_key`, using the
+	/// secp256r1 ECDSA parametrization.
+	fn secp256r1_verify(
+		&mut self,
+		message_hash: &[u8],
+		signature: &[u8],
+		public_key: &[u8],
+	) -> Result<bool, Self::Error>;
+
+	/// Recovers a secp256r1 public key from a message hash and a signature.
+	///
+	/// Returns the recovered pubkey in uncompressed form, which can be used
+	/// in `secp256r1_verify` directly. Any errors related to recovering the
+	/// public key should result in `Ok(Err(()))`
+	fn secp256r1_recover_pubkey(
+		&mut self,
+		message_hash: &[u8],
+		signature: &[u8],
+		recovery_param: u8,
+	) -> Result<Result<Vec<u8>, ()>, Self::Error>;
+
+	/// Aggregates the concatenated compressed BLS12-381 G1 points `g1s` into a single point.
+	///
+	/// Any errors related to the points should result in `Ok(Err(code))`, `code` being the
+	/// CosmWasm crypto error code.
+	fn bls12_381_aggregate_g1(&mut self, g1s: &[u8]) -> Result<Result<Vec<u8>, u32>, Self::Error>;
+
+	/// Aggregates the concatenated compressed BLS12-381 G2 points `g2s` into a single point.
+	///
+	/// Any errors related to the points should result in `Ok(Err(code))`, `code` being the
+	/// CosmWasm crypto error code.
+	fn bls12_381_aggregate_g2(&mut self, g2s: &[u8]) -> Result<Result<Vec<u8>, u32>, Self::Error>;
+
+	/// Checks whether the product of the pairings of `ps` and `qs` equals the pairing of `r` and
+	/// `s`.
+	///
+	/// Any errors related to the points should result in `Ok(Err(code))`, `code` being the
+	/// CosmWasm crypto error code.
+	fn bls12_381_pairing_equality(
+		&mut self,
+		ps: &[u8],
+		qs: &[u8],
+		r: &[u8],
+		s: &[u8],
+	) -> Result<Result<bool, u32>, Self::Error>;
+
+	/// Hashes `msg` to a compressed BLS12-381 G1 point, using the domain separation tag `dst`.
+	///
+	/// An unknown `hash_function` should result in `Ok(Err(code))`.
+	fn bls12_381_hash_to_g1(
+		&mut self,
+		hash_function: u32,
+		msg: &[u8],
+		dst: &[u8],
+	) -> Result<Result<Vec<u8>, u32>, Self::Error>;
+
+	/// Hashes `msg` to a compressed BLS12-381 G2 point, using the domain separation tag `dst`.
+	///
+	/// An unknown `hash_function` should result in `Ok(Err(code))`.
+	fn bls12_381_hash_to_g2(
+		&mut self,
+		hash_function: u32,
+		msg: &[u8],
+		dst: &[u8],
+	) -> Result<Result<Vec<u8>, u32>, Self::Error>;
+
 	/// Verify `message` against a `signature`, with the `public_key` of the signer, using
 	/// the ed25519 elliptic curve digital signature parametrization / algorithm.
 	fn ed25519_verify(
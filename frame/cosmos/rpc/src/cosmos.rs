@@ -24,7 +24,7 @@ use jsonrpsee::{
 	proc_macros::rpc,
 };
 use sc_transaction_pool_api::TransactionPool;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::{sha2_256, Bytes, H256};
 use sp_runtime::{traits::Block as BlockT, transaction_validity::TransactionSource};
@@ -38,6 +38,9 @@ pub trait CosmosApi {
 
 	#[method(name = "cosmos_simulate")]
 	async fn simulate(&self, tx_bytes: Bytes) -> RpcResult<SimulateResponse>;
+
+	#[method(name = "cosmos_allBalances")]
+	async fn all_balances(&self, request: Bytes) -> RpcResult<Bytes>;
 }
 
 pub struct Cosmos<C, P> {
@@ -88,4 +91,21 @@ where
 				SimulateError::InternalError(e) => internal_error(String::from_utf8_lossy(&e)),
 			})
 	}
+
+	async fn all_balances(&self, request: Bytes) -> RpcResult<Bytes> {
+		let best_hash = self.client.info().best_hash;
+		let api = self.client.runtime_api();
+
+		let version = api
+			.api_version::<dyn CosmosRuntimeApi<Block>>(best_hash)
+			.map_err(internal_error)?;
+		if !version.is_some_and(|version| version >= 2) {
+			return Err(request_error("AllBalances is not supported by the runtime"));
+		}
+
+		api.all_balances(best_hash, request.to_vec())
+			.map_err(internal_error)?
+			.map(Bytes::from)
+			.map_err(|e| request_error(String::from_utf8_lossy(&e)))
+	}
 }
@@ -48,8 +48,13 @@ pub enum SimulateError {
 pub type SimulateResult = Result<SimulateResponse, SimulateError>;
 
 decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait CosmosRuntimeApi {
 		fn convert_tx(tx_bytes: Vec<u8>) -> <Block as BlockT>::Extrinsic;
 		fn simulate(tx_bytes: Vec<u8>) -> SimulateResult;
+
+		/// `cosmos.bank.v1beta1.Query/AllBalances`, with protobuf encoded request and response.
+		#[api_version(2)]
+		fn all_balances(request: Vec<u8>) -> Result<Vec<u8>, Vec<u8>>;
 	}
 }
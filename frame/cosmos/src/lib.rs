@@ -29,13 +29,17 @@ pub mod weights;
 
 pub use self::pallet::*;
 use crate::weights::WeightInfo;
-use alloc::{string::String, vec::Vec};
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
 use core::marker::PhantomData;
 use cosmos_sdk_proto::{
 	cosmos::{bank::v1beta1::MsgSend, tx::v1beta1::Tx},
 	cosmwasm::wasm::v1::{
-		MsgExecuteContract, MsgInstantiateContract2, MsgMigrateContract, MsgStoreCode,
-		MsgUpdateAdmin,
+		MsgClearAdmin, MsgExecuteContract, MsgInstantiateContract, MsgInstantiateContract2,
+		MsgMigrateContract, MsgStoreAndInstantiateContract, MsgStoreCode, MsgSudoContract,
+		MsgUpdateAdmin, MsgUpdateInstantiateConfig,
 	},
 	prost::Message,
 	Any,
@@ -44,7 +48,10 @@ use frame_support::{
 	dispatch::{DispatchInfo, PostDispatchInfo, WithPostDispatchInfo},
 	pallet_prelude::{DispatchResultWithPostInfo, InvalidTransaction, Pays},
 	traits::{
-		tokens::{fungibles, AssetId, Balance},
+		tokens::{
+			fungibles::{self, Inspect as _},
+			AssetId, Balance,
+		},
 		Currency, Get,
 	},
 	weights::Weight,
@@ -52,6 +59,7 @@ use frame_support::{
 use frame_system::{pallet_prelude::OriginFor, CheckWeight};
 use pallet_cosmos_types::{
 	address::acc_address_from_bech32,
+	coin::Coin,
 	context,
 	context::traits::Context,
 	errors::{CosmosError, RootError},
@@ -192,10 +200,15 @@ pub mod pallet {
 					msg, {
 						MsgSend => true,
 						MsgStoreCode => true,
+						MsgInstantiateContract => true,
 						MsgInstantiateContract2 => true,
 						MsgExecuteContract => true,
 						MsgMigrateContract => true,
 						MsgUpdateAdmin => true,
+						MsgClearAdmin => true,
+						MsgStoreAndInstantiateContract => true,
+						MsgUpdateInstantiateConfig => true,
+						MsgSudoContract => true,
 					},
 					false
 				)
@@ -473,4 +486,50 @@ impl<T: Config> Pallet<T> {
 			pays_fee: Pays::Yes,
 		})
 	}
+
+	/// The non-zero balances of `who` across every registered denom, sorted by denom.
+	pub fn all_balances(who: &T::AccountId) -> Vec<Coin> {
+		Self::balances(who, u32::MAX)
+			.unwrap_or_default()
+			.into_iter()
+			.filter(|(_, amount)| *amount != 0)
+			.map(|(denom, amount)| Coin { amount: amount.to_string(), denom })
+			.collect()
+	}
+
+	/// The balances of `who`, zero ones included, as `(denom, amount)` pairs sorted by denom.
+	///
+	/// At most `limit` registered denoms are visited. Returns `None` if more are registered,
+	/// without reading any balance.
+	pub fn balances(who: &T::AccountId, limit: u32) -> Option<Vec<(String, u128)>> {
+		let denoms = AssetDenomRouter::<T>::iter()
+			.take((limit as usize).saturating_add(1))
+			.collect::<Vec<_>>();
+		if denoms.len() > limit as usize {
+			return None;
+		}
+
+		let mut balances = denoms
+			.into_iter()
+			.filter_map(|(asset_id, denom)| {
+				let denom = String::from_utf8(denom.into_inner()).ok()?;
+				let amount: u128 = if asset_id == T::NativeAssetId::get() {
+					T::NativeAsset::free_balance(who).saturated_into()
+				} else {
+					T::Assets::balance(asset_id, who).into()
+				};
+				Some((denom, amount))
+			})
+			.collect::<Vec<_>>();
+		balances.sort_by(|(a, _), (b, _)| a.cmp(b));
+		Some(balances)
+	}
+}
+
+/// The balances of an account across at most `limit` registered denoms, see [`Pallet::balances`].
+pub struct AllBalances<T>(PhantomData<T>);
+impl<T: Config> Convert<(T::AccountId, u32), Option<Vec<(String, u128)>>> for AllBalances<T> {
+	fn convert((who, limit): (T::AccountId, u32)) -> Option<Vec<(String, u128)>> {
+		Pallet::<T>::balances(&who, limit)
+	}
 }
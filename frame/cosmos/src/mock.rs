@@ -24,11 +24,21 @@ use core::marker::PhantomData;
 use cosmos_sdk_proto::{
 	cosmos::bank::v1beta1::MsgSend,
 	cosmwasm::wasm::v1::{
-		MsgExecuteContract, MsgInstantiateContract2, MsgMigrateContract, MsgStoreCode,
-		MsgUpdateAdmin,
+		MsgClearAdmin, MsgExecuteContract, MsgInstantiateContract, MsgInstantiateContract2,
+		MsgMigrateContract, MsgPinCodes, MsgStoreAndInstantiateContract, MsgStoreCode,
+		MsgSudoContract, MsgUnpinCodes, MsgUpdateAdmin, MsgUpdateInstantiateConfig,
 	},
 	Any,
 };
+use cosmwasm_std::{Binary, ContractResult, IbcTimeout, Order, QueryResponse, Response};
+use cosmwasm_vm::{
+	system::CosmwasmCodeId,
+	vm::{VMBase, VmErrorOf},
+};
+use cosmwasm_vm_wasmi::{
+	code_gen::{ModuleDefinition, WasmModule},
+	OwnedWasmiVM,
+};
 use frame_support::{derive_impl, parameter_types, traits::AsEnsureOriginWithArg, PalletId};
 use hp_account::CosmosSigner;
 use hp_crypto::EcdsaExt;
@@ -39,10 +49,24 @@ use pallet_cosmos_x_auth_signing::{
 };
 use pallet_cosmos_x_bank::msgs::MsgSendHandler;
 use pallet_cosmos_x_wasm::msgs::{
-	MsgExecuteContractHandler, MsgInstantiateContract2Handler, MsgMigrateContractHandler,
-	MsgStoreCodeHandler, MsgUpdateAdminHandler,
+	MsgClearAdminHandler, MsgExecuteContractHandler, MsgInstantiateContract2Handler,
+	MsgInstantiateContractHandler, MsgMigrateContractHandler, MsgPinCodesHandler,
+	MsgRemoveCodeHandler, MsgStoreAndInstantiateContractHandler, MsgStoreCodeHandler,
+	MsgSudoContractHandler, MsgUnpinCodesHandler, MsgUpdateAdminHandler,
+	MsgUpdateInstantiateConfigHandler,
+};
+use pallet_cosmos_x_wasm_types::proto::MsgRemoveCode;
+use pallet_cosmwasm::{
+	ibc::IbcHandler,
+	instrument::{CostRules, INSTRUMENTATION_VERSION},
+	pallet_hook::PalletHook,
+	runtimes::vm::{CosmwasmVM, CosmwasmVMError},
+	types::{
+		AccessConfig, CodeInfo, ContractInfo, ContractLabelOf, ContractTrieIdOf, EntryPoint,
+		PalletContractCodeInfo,
+	},
 };
-use pallet_cosmwasm::instrument::CostRules;
+use parity_scale_codec::{Decode, Encode};
 use sp_core::{
 	crypto::UncheckedFrom, ecdsa, ConstU128, ConstU32, ConstU64, Hasher, Pair, H160, H256,
 };
@@ -114,7 +138,7 @@ pub struct MsgServiceRouter<T>(PhantomData<T>);
 impl<T, Context> pallet_cosmos_types::msgservice::MsgServiceRouter<Context> for MsgServiceRouter<T>
 where
 	T: frame_system::Config + pallet_cosmos::Config + pallet_cosmwasm::Config,
-	T::AccountId: EcdsaExt,
+	T::AccountId: EcdsaExt + UncheckedFrom<H256>,
 	Context: context::traits::Context,
 {
 	fn route(msg: &Any) -> Option<Box<dyn MsgHandler<Context>>> {
@@ -122,10 +146,18 @@ where
 			msg, {
 				MsgSend => Some(Box::<MsgSendHandler<T>>::default()),
 				MsgStoreCode => Some(Box::<MsgStoreCodeHandler<T>>::default()),
+				MsgInstantiateContract => Some(Box::<MsgInstantiateContractHandler<T>>::default()),
 				MsgInstantiateContract2 => Some(Box::<MsgInstantiateContract2Handler<T>>::default()),
 				MsgExecuteContract => Some(Box::<MsgExecuteContractHandler<T>>::default()),
 				MsgMigrateContract => Some(Box::<MsgMigrateContractHandler<T>>::default()),
 				MsgUpdateAdmin => Some(Box::<MsgUpdateAdminHandler<T>>::default()),
+				MsgClearAdmin => Some(Box::<MsgClearAdminHandler<T>>::default()),
+				MsgStoreAndInstantiateContract => Some(Box::<MsgStoreAndInstantiateContractHandler<T, WasmAuthority>>::default()),
+				MsgUpdateInstantiateConfig => Some(Box::<MsgUpdateInstantiateConfigHandler<T, WasmAuthority>>::default()),
+				MsgSudoContract => Some(Box::<MsgSudoContractHandler<T, WasmAuthority>>::default()),
+				MsgPinCodes => Some(Box::<MsgPinCodesHandler<T, WasmAuthority>>::default()),
+				MsgUnpinCodes => Some(Box::<MsgUnpinCodesHandler<T, WasmAuthority>>::default()),
+				MsgRemoveCode => Some(Box::<MsgRemoveCodeHandler<T, WasmAuthority>>::default()),
 			},
 			None
 		)
@@ -179,13 +211,13 @@ parameter_types! {
 	pub const MaxContractTrieIdSize: u32 = Hash::len_bytes() as u32;
 	pub const MaxInstantiateSaltSize: u32 = 128;
 	pub const MaxFundsAssets: u32 = 32;
+	pub const MaxCodeHistoryEntries: u32 = 16;
+	pub const ModuleCacheSize: u32 = 32;
 	pub const CodeTableSizeLimit: u32 = 4096;
 	pub const CodeGlobalVariableLimit: u32 = 256;
 	pub const CodeParameterLimit: u32 = 128;
 	pub const CodeBranchTableSizeLimit: u32 = 256;
 	pub const CodeStorageByteDeposit: u32 = 1_000_000;
-	pub const ContractStorageByteReadPrice: u32 = 1;
-	pub const ContractStorageByteWritePrice: u32 = 1;
 	pub WasmCostRules: CostRules<Test> = Default::default();
 }
 
@@ -243,6 +275,127 @@ where
 	}
 }
 
+parameter_types! {
+	/// Packets sent by contracts through [`MockIbcHandler`], waiting to be relayed.
+	pub static SentPackets: Vec<(String, String, Binary, IbcTimeout)> = Vec::new();
+	/// Gas charged by the last operation executed by [`StorageHook`].
+	pub static StorageGas: u64 = 0;
+	/// Alice, allowed to submit `MsgSudoContract`.
+	pub WasmAuthority: String = "cosmos1qd69nuwj95gta4akjgyxtj9ujmz4w8edmqysqw".into();
+}
+
+/// In-process channel layer, recording the packets sent by contracts instead of committing them.
+pub struct MockIbcHandler;
+impl IbcHandler<Test> for MockIbcHandler {
+	fn send_packet<'a>(
+		_vm: &mut CosmwasmVM<'a, Test>,
+		port_id: String,
+		channel_id: String,
+		data: Binary,
+		timeout: IbcTimeout,
+	) -> Result<(), CosmwasmVMError<Test>> {
+		SentPackets::mutate(|packets| packets.push((port_id, channel_id, data, timeout)));
+		Ok(())
+	}
+
+	fn close_channel<'a>(
+		_vm: &mut CosmwasmVM<'a, Test>,
+		_port_id: String,
+		_channel_id: String,
+	) -> Result<(), CosmwasmVMError<Test>> {
+		Ok(())
+	}
+}
+
+/// Storage operation executed by [`StorageHook`], passed as the SCALE-encoded message.
+#[derive(Encode, Decode)]
+pub enum StorageOp {
+	Read(Vec<u8>),
+	Write(Vec<u8>, Vec<u8>),
+	Remove(Vec<u8>),
+	/// Step once through an iterator over the whole storage.
+	Next {
+		descending: bool,
+	},
+}
+
+/// Precompiled contract executing a single [`StorageOp`] through the VM, recording the gas it
+/// charged in [`StorageGas`].
+pub struct StorageHook;
+impl StorageHook {
+	pub fn address() -> AccountId {
+		AccountId::unchecked_from(H256::repeat_byte(0xff))
+	}
+}
+impl PalletHook<Test> for StorageHook {
+	fn info(
+		contract_address: &AccountId,
+	) -> Option<PalletContractCodeInfo<AccountId, ContractLabelOf<Test>, ContractTrieIdOf<Test>>> {
+		(*contract_address == Self::address()).then(|| PalletContractCodeInfo {
+			code: CodeInfo {
+				creator: Self::address(),
+				pristine_code_hash: [0; 32],
+				instrumentation_version: INSTRUMENTATION_VERSION,
+				refcount: 0,
+				ibc_capable: false,
+				instantiate_permission: AccessConfig::Nobody,
+			},
+			contract: ContractInfo {
+				code_id: 0,
+				trie_id: b"storage".to_vec().try_into().unwrap(),
+				instantiator: Self::address(),
+				admin: None,
+				label: b"storage".to_vec().try_into().unwrap(),
+			},
+		})
+	}
+
+	fn execute<'a>(
+		vm: &mut OwnedWasmiVM<CosmwasmVM<'a, Test>>,
+		_entrypoint: EntryPoint,
+		message: &[u8],
+	) -> Result<
+		ContractResult<Response<<OwnedWasmiVM<CosmwasmVM<'a, Test>> as VMBase>::MessageCustom>>,
+		VmErrorOf<OwnedWasmiVM<CosmwasmVM<'a, Test>>>,
+	> {
+		let op =
+			StorageOp::decode(&mut &*message).map_err(|_| CosmwasmVMError::ExecuteDeserialize)?;
+		let iterator_id = match op {
+			StorageOp::Next { descending: false } => vm.db_scan(None, None, Order::Ascending)?,
+			StorageOp::Next { descending: true } => vm.db_scan(None, None, Order::Descending)?,
+			_ => 0,
+		};
+		let remaining = vm.0.data().shared.gas.remaining();
+		match op {
+			StorageOp::Read(key) => {
+				vm.db_read(key)?;
+			},
+			StorageOp::Write(key, value) => vm.db_write(key, value)?,
+			StorageOp::Remove(key) => vm.db_remove(key)?,
+			StorageOp::Next { .. } => {
+				vm.db_next(iterator_id)?;
+			},
+		}
+		StorageGas::set(remaining - vm.0.data().shared.gas.remaining());
+		Ok(ContractResult::Ok(Response::new()))
+	}
+
+	fn run<'a>(
+		_vm: &mut OwnedWasmiVM<CosmwasmVM<'a, Test>>,
+		_entrypoint: EntryPoint,
+		_message: &[u8],
+	) -> Result<Vec<u8>, VmErrorOf<OwnedWasmiVM<CosmwasmVM<'a, Test>>>> {
+		Err(CosmwasmVMError::Unsupported)
+	}
+
+	fn query<'a>(
+		_vm: &mut OwnedWasmiVM<CosmwasmVM<'a, Test>>,
+		_message: &[u8],
+	) -> Result<ContractResult<QueryResponse>, VmErrorOf<OwnedWasmiVM<CosmwasmVM<'a, Test>>>> {
+		Err(CosmwasmVMError::Unsupported)
+	}
+}
+
 impl pallet_cosmwasm::Config for Test {
 	const MAX_FRAMES: u8 = 64;
 	type RuntimeEvent = RuntimeEvent;
@@ -262,6 +415,8 @@ impl pallet_cosmwasm::Config for Test {
 	type MaxContractTrieIdSize = MaxContractTrieIdSize;
 	type MaxInstantiateSaltSize = MaxInstantiateSaltSize;
 	type MaxFundsAssets = MaxFundsAssets;
+	type MaxCodeHistoryEntries = MaxCodeHistoryEntries;
+	type ModuleCacheSize = ModuleCacheSize;
 
 	type CodeTableSizeLimit = CodeTableSizeLimit;
 	type CodeGlobalVariableLimit = CodeGlobalVariableLimit;
@@ -270,23 +425,28 @@ impl pallet_cosmwasm::Config for Test {
 	type CodeParameterLimit = CodeParameterLimit;
 	type CodeBranchTableSizeLimit = CodeBranchTableSizeLimit;
 	type CodeStorageByteDeposit = CodeStorageByteDeposit;
-	type ContractStorageByteReadPrice = ContractStorageByteReadPrice;
-	type ContractStorageByteWritePrice = ContractStorageByteWritePrice;
 
 	type WasmCostRules = WasmCostRules;
 	type UnixTime = Timestamp;
 	type WeightInfo = pallet_cosmwasm::weights::SubstrateWeight<Test>;
 
-	// TODO: Add precompile to use execute or query pallet
-	type PalletHook = ();
+	type PalletHook = StorageHook;
+
+	type AnyMsgHandler = pallet_cosmos_x_wasm::any_msg_handler::AnyMsgHandler<Test>;
+
+	type GrpcQueryRouter = ();
 
-	type UploadWasmOrigin = frame_system::EnsureSigned<Self::AccountId>;
+	type CustomHandler = pallet_cosmos_x_wasm::custom_handler::CustomHandler<Test>;
+
+	type IbcHandler = MockIbcHandler;
 
 	type ExecuteWasmOrigin = frame_system::EnsureSigned<Self::AccountId>;
 
 	type NativeAssetId = NativeAssetId;
 
 	type NativeDenom = NativeDenom;
+
+	type AllBalances = pallet_cosmos::AllBalances<Test>;
 }
 
 impl pallet_cosmos_accounts::Config for Test {
@@ -357,11 +517,52 @@ impl fp_self_contained::SelfContainedCall for RuntimeCall {
 	}
 }
 
+/// Gas limit of the contract calls made by the tests.
+pub const GAS: u64 = 100_000_000_000;
+
+pub fn alice() -> AccountId {
+	CosmosSigner(ecdsa::Pair::from_string("//Alice", None).unwrap().public())
+}
+
+pub fn bob() -> AccountId {
+	CosmosSigner(ecdsa::Pair::from_string("//Bob", None).unwrap().public())
+}
+
+/// Upload a generated code whose size grows with `additional_binary_size`, so that distinct sizes
+/// upload distinct codes.
+pub fn upload(creator: &AccountId, additional_binary_size: usize) -> CosmwasmCodeId {
+	let module: WasmModule =
+		ModuleDefinition::new(vec![], additional_binary_size, None).unwrap().into();
+	let (_, code_id) =
+		Cosmwasm::do_upload(creator, module.code.try_into().unwrap(), AccessConfig::Everybody)
+			.unwrap();
+	code_id
+}
+
+/// Instantiate a contract of `code_id` without admin nor funds, with an empty message.
+pub fn instantiate(
+	creator: AccountId,
+	code_id: CosmwasmCodeId,
+	salt: &[u8],
+	label: &[u8],
+) -> AccountId {
+	pallet_cosmwasm::instantiate::<Test>(
+		creator,
+		code_id,
+		salt.to_vec(),
+		None,
+		label.to_vec(),
+		Default::default(),
+		GAS,
+		b"{}".to_vec(),
+	)
+	.unwrap()
+}
+
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
 
-	let alice = CosmosSigner(ecdsa::Pair::from_string("//Alice", None).unwrap().public());
-	let bob = CosmosSigner(ecdsa::Pair::from_string("//Bob", None).unwrap().public());
+	let (alice, bob) = (alice(), bob());
 
 	pallet_balances::GenesisConfig::<Test> {
 		balances: vec![(alice, 1_000_000_000_000_000_000), (bob, 1_000_000_000_000_000_000)],
@@ -0,0 +1,376 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Tests of the storage, the gas and the migrations of pallet_cosmwasm, which do not involve
+//! the Cosmos messages.
+
+use crate::mock::*;
+use frame_support::{assert_ok, traits::Get, weights::Weight};
+use pallet_cosmwasm::types::AccessConfig;
+
+#[test]
+fn pallet_cosmwasm_reinstrumentation_test() {
+	use cosmwasm_vm_wasmi::code_gen::{ModuleDefinition, WasmModule};
+	use frame_support::{assert_noop, traits::Hooks};
+	use pallet_cosmwasm::{
+		estimated_weights::EstimatedWeightInfo, instrument::INSTRUMENTATION_VERSION,
+		weights::SubstrateWeight, Event,
+	};
+	use sp_runtime::DispatchError;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = alice();
+
+		let [(code_id, code_len), (other_code_id, _)] = [0, 1].map(|additional_binary_size| {
+			let module: WasmModule =
+				ModuleDefinition::new(vec![], additional_binary_size, None).unwrap().into();
+			let code_len = module.code.len() as u32;
+			let (_, code_id) = Cosmwasm::do_upload(
+				&alice,
+				module.code.try_into().unwrap(),
+				AccessConfig::Everybody,
+			)
+			.unwrap();
+			(code_id, code_len)
+		});
+
+		// The extrinsic is restricted to the root origin.
+		assert_noop!(
+			Cosmwasm::force_reinstrument(RuntimeOrigin::signed(alice), code_id),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Cosmwasm::force_reinstrument(RuntimeOrigin::root(), 42),
+			pallet_cosmwasm::Error::<Test>::CodeNotFound
+		);
+		assert_ok!(Cosmwasm::force_reinstrument(RuntimeOrigin::root(), code_id));
+		System::assert_last_event(RuntimeEvent::Cosmwasm(Event::CodeReinstrumented { code_id }));
+
+		// The background re-instrumentation resumes where the idle weight ran out.
+		let db_weight = <Test as frame_system::Config>::DbWeight::get();
+		let limit = SubstrateWeight::<Test>::reinstrument(code_len)
+			.saturating_add(db_weight.reads_writes(4, 2));
+		System::reset_events();
+		Cosmwasm::on_idle(System::block_number(), limit);
+		System::assert_last_event(RuntimeEvent::Cosmwasm(Event::ReinstrumentationProgressed {
+			next_code_id: other_code_id,
+			reinstrumented: 0,
+		}));
+
+		System::reset_events();
+		Cosmwasm::on_idle(System::block_number(), Weight::MAX);
+		System::assert_has_event(RuntimeEvent::Cosmwasm(Event::ReinstrumentationProgressed {
+			next_code_id: other_code_id + 1,
+			reinstrumented: 0,
+		}));
+		System::assert_last_event(RuntimeEvent::Cosmwasm(Event::ReinstrumentationCompleted {
+			instrumentation_version: INSTRUMENTATION_VERSION,
+		}));
+
+		// Once completed, only the instrumentation version and the key indexing cursor are
+		// checked.
+		System::reset_events();
+		assert_eq!(Cosmwasm::on_idle(System::block_number(), Weight::MAX), db_weight.reads(2));
+		assert!(System::events().is_empty());
+	});
+}
+
+#[test]
+fn pallet_cosmwasm_storage_gas_test() {
+	use pallet_cosmwasm::{
+		estimated_weights::EstimatedWeightInfo,
+		runtimes::vm::InitialStorageMutability,
+		weights::{SubstrateWeight, WeightInfo as _},
+	};
+	use parity_scale_codec::Encode;
+
+	type CosmwasmWeight = SubstrateWeight<Test>;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = alice();
+		let execute = |op: StorageOp| {
+			let mut shared =
+				Cosmwasm::do_create_vm_shared(GAS, InitialStorageMutability::ReadWrite);
+			assert_ok!(Cosmwasm::do_execute(
+				&mut shared,
+				alice,
+				StorageHook::address(),
+				Default::default(),
+				op.encode().try_into().unwrap(),
+			));
+			StorageGas::get()
+		};
+
+		let (key, value) = (b"key".to_vec(), vec![1u8; 64]);
+		let key_len = key.len() as u64;
+		let value_len = value.len() as u64;
+		let read_bytes = |key_len: u64, value_len: u64| {
+			key_len * CosmwasmWeight::db_read_key_byte().ref_time() +
+				value_len * CosmwasmWeight::db_read_value_byte().ref_time()
+		};
+		let step = <Test as frame_system::Config>::DbWeight::get().reads(1).ref_time();
+
+		let index = (3 * key_len + 1) * CosmwasmWeight::db_write_key_byte().ref_time();
+
+		// Writes are charged by the bytes of the entry written, along with the key indexed.
+		assert_eq!(
+			execute(StorageOp::Write(key.clone(), value)),
+			CosmwasmWeight::db_write().ref_time() +
+				key_len * CosmwasmWeight::db_write_key_byte().ref_time() +
+				value_len * CosmwasmWeight::db_write_value_byte().ref_time() +
+				index
+		);
+
+		// Reads by the bytes of the entry read, if any.
+		assert_eq!(
+			execute(StorageOp::Read(key.clone())),
+			CosmwasmWeight::db_read().ref_time() + read_bytes(key_len, value_len)
+		);
+		assert_eq!(
+			execute(StorageOp::Read(b"missing".to_vec())),
+			CosmwasmWeight::db_read().ref_time() + read_bytes(7, 0)
+		);
+
+		// Iterators by the step along with the entry read, in both orders.
+		for descending in [false, true] {
+			assert_eq!(
+				execute(StorageOp::Next { descending }),
+				CosmwasmWeight::db_next().ref_time() + step + read_bytes(key_len, value_len)
+			);
+		}
+
+		// Removals by the key written and the value removed, along with the key dropped from the
+		// index.
+		assert_eq!(
+			execute(StorageOp::Remove(key.clone())),
+			CosmwasmWeight::db_remove().ref_time() +
+				key_len * CosmwasmWeight::db_write_key_byte().ref_time() +
+				value_len * CosmwasmWeight::db_remove_value_byte().ref_time() +
+				index
+		);
+		assert_eq!(
+			execute(StorageOp::Remove(key)),
+			CosmwasmWeight::db_remove().ref_time() +
+				key_len * CosmwasmWeight::db_write_key_byte().ref_time() +
+				index
+		);
+		for descending in [false, true] {
+			assert_eq!(
+				execute(StorageOp::Next { descending }),
+				CosmwasmWeight::db_next().ref_time() + step
+			);
+		}
+	});
+}
+
+#[test]
+fn pallet_cosmwasm_migrate_to_v1_test() {
+	use cosmwasm_vm_wasmi::code_gen::{ModuleDefinition, WasmModule};
+	use frame_support::{
+		storage::{storage_prefix, unhashed},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		StorageHasher, Twox64Concat,
+	};
+	use pallet_cosmwasm::migrations::v1::MigrateToV1;
+	use parity_scale_codec::Encode;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = alice();
+
+		let module: WasmModule = ModuleDefinition::new(vec![], 0, None).unwrap().into();
+		let (_, code_id) =
+			Cosmwasm::do_upload(&alice, module.code.try_into().unwrap(), AccessConfig::Nobody)
+				.unwrap();
+		let info = Cosmwasm::code_info(code_id).unwrap();
+
+		// Code metadata stored before the instantiate permission existed.
+		let key = [
+			storage_prefix(b"Cosmwasm", b"CodeIdToInfo").as_slice(),
+			&Twox64Concat::hash(&code_id.encode()),
+		]
+		.concat();
+		unhashed::put(
+			&key,
+			&(
+				info.creator,
+				info.pristine_code_hash,
+				info.instrumentation_version,
+				info.refcount,
+				info.ibc_capable,
+			),
+		);
+		assert!(Cosmwasm::code_info(code_id).is_err());
+
+		StorageVersion::new(0).put::<Cosmwasm>();
+		MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(Cosmwasm::on_chain_storage_version(), 1);
+
+		let migrated = Cosmwasm::code_info(code_id).unwrap();
+		assert_eq!(migrated.instantiate_permission, AccessConfig::Everybody);
+		assert_eq!(migrated.creator, info.creator);
+		assert_eq!(migrated.pristine_code_hash, info.pristine_code_hash);
+		assert_eq!(migrated.instrumentation_version, info.instrumentation_version);
+		assert_eq!(migrated.refcount, info.refcount);
+		assert_eq!(migrated.ibc_capable, info.ibc_capable);
+	});
+}
+
+#[test]
+fn pallet_cosmwasm_migrate_to_v2_test() {
+	use frame_support::{
+		storage::{storage_prefix, unhashed},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+	use pallet_cosmwasm::migrations::v2::MigrateToV2;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = alice();
+
+		let code_id = upload(&alice, 0);
+		let contract = instantiate(alice, code_id, b"salt", b"index");
+
+		// Contracts instantiated before they were indexed by code.
+		let _ =
+			unhashed::clear_prefix(&storage_prefix(b"Cosmwasm", b"CodeIdToContracts"), None, None);
+		assert_eq!(Cosmwasm::contracts_by_code(code_id, None).count(), 0);
+
+		StorageVersion::new(1).put::<Cosmwasm>();
+		MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(Cosmwasm::on_chain_storage_version(), 2);
+
+		assert_eq!(Cosmwasm::contracts_by_code(code_id, None).collect::<Vec<_>>(), vec![contract]);
+	});
+}
+
+#[test]
+fn pallet_cosmwasm_migrate_to_v3_test() {
+	use frame_support::{
+		storage::{storage_prefix, unhashed},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+	use pallet_cosmwasm::{
+		migrations::v3::MigrateToV3,
+		types::{ContractCodeHistoryEntry, ContractCodeHistoryOperation},
+	};
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = alice();
+
+		let code_id = upload(&alice, 0);
+		let contract = instantiate(alice, code_id, b"salt", b"history");
+
+		// Contracts instantiated before their code history was recorded.
+		for storage in [b"ContractToHistory".as_slice(), b"ContractToHistoryBounds"] {
+			let _ = unhashed::clear_prefix(&storage_prefix(b"Cosmwasm", storage), None, None);
+		}
+
+		System::set_block_number(5);
+		StorageVersion::new(2).put::<Cosmwasm>();
+		MigrateToV3::<Test>::on_runtime_upgrade();
+		assert_eq!(Cosmwasm::on_chain_storage_version(), 3);
+
+		// The history starts with a single entry, numbered from zero.
+		let history: Vec<_> = Cosmwasm::contract_history(&contract, None).unwrap().collect();
+		assert_eq!(
+			history,
+			vec![(
+				0,
+				ContractCodeHistoryEntry {
+					operation: ContractCodeHistoryOperation::Genesis,
+					code_id,
+					updated: 5,
+					msg: Default::default(),
+				}
+			)]
+		);
+	});
+}
+
+#[test]
+fn pallet_cosmwasm_migrate_to_v4_test() {
+	use frame_support::{
+		storage::child::{self, ChildInfo},
+		traits::{GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
+		Blake2_128Concat, StorageHasher,
+	};
+	use pallet_cosmwasm::{migrations::v4::MigrateToV4, runtimes::iterator::reverse_key, Event};
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = alice();
+
+		let code_id = upload(&alice, 0);
+		let contract = instantiate(alice, code_id, b"salt", b"index");
+
+		// Contract storage written before the key index existed.
+		let info = Cosmwasm::contract_info(&contract).unwrap();
+		let child_info = ChildInfo::new_default(&info.trie_id);
+		for key in [b"foo".as_slice(), b"ant", b"ze"] {
+			child::put_raw(&child_info, &Blake2_128Concat::hash(key), key);
+		}
+		assert_eq!(Cosmwasm::contract_state(&contract, None).unwrap().count(), 0);
+
+		StorageVersion::new(3).put::<Cosmwasm>();
+		MigrateToV4::<Test>::on_runtime_upgrade();
+		assert_eq!(Cosmwasm::on_chain_storage_version(), 4);
+
+		// The contract cannot be iterated until the background key indexing walked it.
+		assert!(matches!(
+			Cosmwasm::contract_state(&contract, None),
+			Err(pallet_cosmwasm::Error::<Test>::ContractNotIndexed)
+		));
+
+		// The key indexing resumes where the idle weight ran out.
+		let db_weight = <Test as frame_system::Config>::DbWeight::get();
+		System::reset_events();
+		Cosmwasm::on_idle(System::block_number(), db_weight.reads_writes(5, 5));
+		assert!(System::events().is_empty());
+		assert!(Cosmwasm::contract_state(&contract, None).is_err());
+
+		Cosmwasm::on_idle(System::block_number(), Weight::MAX);
+		System::assert_last_event(RuntimeEvent::Cosmwasm(Event::KeyIndexingCompleted));
+		assert_eq!(Cosmwasm::on_idle(System::block_number(), Weight::MAX), db_weight.reads(2));
+
+		let reverse_index = ChildInfo::new_default(&[info.trie_id.as_slice(), b":rindex"].concat());
+		for key in [b"foo".as_slice(), b"ant", b"ze"] {
+			assert!(child::exists(&reverse_index, &reverse_key(key)));
+		}
+
+		let state: Vec<_> = Cosmwasm::contract_state(&contract, None).unwrap().collect();
+		assert_eq!(
+			state,
+			[b"ant".as_slice(), b"foo", b"ze"]
+				.map(|key| (key.to_vec(), key.to_vec()))
+				.to_vec()
+		);
+		let state: Vec<_> =
+			Cosmwasm::contract_state(&contract, Some(b"ant".to_vec())).unwrap().collect();
+		assert_eq!(state.len(), 2);
+	});
+}
@@ -0,0 +1,1462 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+mod cosmwasm;
+
+use crate::{mock::*, weights::WeightInfo};
+use base64ct::{Base64, Encoding};
+use cosmos_sdk_proto::{cosmos::tx::v1beta1::Tx, prost::Message};
+use fp_self_contained::{CheckedExtrinsic, SelfContainedCall};
+use frame_support::{
+	assert_ok,
+	dispatch::GetDispatchInfo,
+	traits::{fungible::Inspect, Get},
+	weights::Weight,
+};
+use hp_account::CosmosSigner;
+use pallet_cosmos_types::{
+	coin::Coin,
+	events::{CosmosEvent, EventAttribute},
+};
+use pallet_cosmos_x_bank_types::msgs::msg_send::MsgSend;
+use pallet_cosmwasm::types::AccessConfig;
+use sp_core::H160;
+use sp_runtime::BoundedVec;
+use std::fs;
+
+#[test]
+fn pallet_cosmos_msg_send_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		System::reset_events();
+
+		let alice = alice();
+
+		let tx_raw =  "CpoBCpcBChwvY29zbW9zLmJhbmsudjFiZXRhMS5Nc2dTZW5kEncKLWNvc21vczFxZDY5bnV3ajk1Z3RhNGFramd5eHRqOXVqbXo0dzhlZG1xeXNxdxItY29zbW9zMW41amd4NjR6dzM4c3M3Nm16dXU0dWM3amV5cXcydmZqazYwZmR6GhcKBGFjZHQSDzEwMDAwMDAwMDAwMDAwMBJsCk4KRgofL2Nvc21vcy5jcnlwdG8uc2VjcDI1NmsxLlB1YktleRIjCiECChCRNB/lZkv6F4LV4Ed5aJBoyRawTLNl7DFTdVaE2aESBAoCCH8SGgoSCgRhY2R0EgoxMDQwMDAwMDAwEIDa8esEGkBgXIiPoBpecG7QpKDJPaztFogqvmxjDHF5ORfWBrOoSzf0+AAmch1CXrG4OmiKL0y8v9ITx0QzWYUc7ueXcdIm";
+		let tx_bytes = Base64::decode_vec(&tx_raw).unwrap();
+		let tx = Tx::decode(&mut &*tx_bytes).unwrap();
+
+		let mut expected_balance = 1_000_000_000_000_000_000u128;
+		assert_eq!(Balances::balance(&alice), expected_balance);
+
+		let call = pallet_cosmos::Call::<Test>::transact { tx_bytes };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<CosmosSigner, _, (), H160> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Cosmos(call.clone()),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+
+		assert_ok!(call.pre_dispatch_self_contained(&dispatch_info, 0).unwrap());
+		assert_ok!(extrinsic.function.apply_self_contained(alice).unwrap());
+
+		let msg = tx.body.as_ref().unwrap().messages.first().unwrap();
+		let msg = MsgSend::try_from(msg).unwrap();
+
+		let amount = msg.amount.first().unwrap().amount.parse::<u128>().unwrap();
+		expected_balance -= amount;
+
+		let fee = tx.auth_info.as_ref().unwrap().fee.clone().unwrap();
+		let fee_amount = fee.amount.first().unwrap().amount.parse::<u128>().unwrap();
+		expected_balance -= fee_amount;
+
+		assert_eq!(Balances::balance(&alice), expected_balance);
+	});
+}
+
+#[test]
+fn pallet_cosmos_msg_store_code_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		System::reset_events();
+
+		let alice = alice();
+
+		let tx_raw = fs::read_to_string("./txs/msg_store_code").unwrap();
+		let tx_raw = tx_raw.trim();
+		let tx_bytes = Base64::decode_vec(tx_raw).unwrap();
+
+		let call = pallet_cosmos::Call::<Test>::transact { tx_bytes };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<CosmosSigner, _, (), H160> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Cosmos(call.clone()),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+
+		assert_ok!(call.pre_dispatch_self_contained(&dispatch_info, 0).unwrap());
+		assert_ok!(extrinsic.function.apply_self_contained(alice).unwrap());
+
+		let (_gas_wanted, _gas_used, events) = System::events()
+			.into_iter()
+			.find_map(|record| {
+				if let RuntimeEvent::Cosmos(pallet_cosmos::Event::Executed {
+					gas_wanted,
+					gas_used,
+					events,
+				}) = record.event
+				{
+					Some((gas_wanted, gas_used, events))
+				} else {
+					None
+				}
+			})
+			.unwrap();
+
+		for CosmosEvent { r#type, attributes } in events.iter() {
+			println!("type: {}", String::from_utf8_lossy(&r#type));
+
+			for EventAttribute { key, value } in attributes.iter() {
+				let key = String::from_utf8_lossy(key).to_string();
+				let value = String::from_utf8_lossy(value).to_string();
+
+				if key == "code_id" {
+					assert_eq!(value, "1");
+				}
+				if key == "code_checksum" {
+					assert_eq!(
+						value,
+						"4d8e90dd340993033f1b9e8e3a3ee7f8673c582ca9bcdd8c8cf3c7470d6537d5"
+					);
+				}
+			}
+		}
+	});
+}
+
+#[test]
+fn pallet_cosmos_msg_instantiate_contract2_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		System::reset_events();
+
+		let alice = alice();
+
+		let tx_raw = fs::read_to_string("./txs/msg_store_code").unwrap();
+		let tx_raw = tx_raw.trim();
+		let tx_bytes = Base64::decode_vec(tx_raw).unwrap();
+
+		let call = pallet_cosmos::Call::<Test>::transact { tx_bytes };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<CosmosSigner, _, (), H160> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Cosmos(call.clone()),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+
+		assert_ok!(call.pre_dispatch_self_contained(&dispatch_info, 0).unwrap());
+		assert_ok!(extrinsic.function.apply_self_contained(alice).unwrap());
+
+		System::set_block_number(2);
+		System::reset_events();
+
+		let tx_raw = fs::read_to_string("./txs/msg_instantiate_contract2").unwrap();
+		let tx_raw = tx_raw.trim();
+		let tx_bytes = Base64::decode_vec(tx_raw).unwrap();
+
+		let call = pallet_cosmos::Call::<Test>::transact { tx_bytes };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<CosmosSigner, _, (), H160> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Cosmos(call.clone()),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+
+		assert_ok!(call.pre_dispatch_self_contained(&dispatch_info, 0).unwrap());
+		assert_ok!(extrinsic.function.apply_self_contained(alice).unwrap());
+	});
+}
+
+#[test]
+fn pallet_cosmos_msg_execute_contract_test() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		System::reset_events();
+
+		let alice = alice();
+
+		let tx_raw = fs::read_to_string("./txs/msg_store_code").unwrap();
+		let tx_raw = tx_raw.trim();
+		let tx_bytes = Base64::decode_vec(tx_raw).unwrap();
+
+		let call = pallet_cosmos::Call::<Test>::transact { tx_bytes };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<CosmosSigner, _, (), H160> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Cosmos(call.clone()),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+
+		assert_ok!(call.pre_dispatch_self_contained(&dispatch_info, 0).unwrap());
+		assert_ok!(extrinsic.function.apply_self_contained(alice).unwrap());
+
+		System::set_block_number(2);
+		System::reset_events();
+
+		let tx_raw = fs::read_to_string("./txs/msg_instantiate_contract2").unwrap();
+		let tx_raw = tx_raw.trim();
+		let tx_bytes = Base64::decode_vec(tx_raw).unwrap();
+
+		let call = pallet_cosmos::Call::<Test>::transact { tx_bytes };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<CosmosSigner, _, (), H160> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Cosmos(call.clone()),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+
+		assert_ok!(call.pre_dispatch_self_contained(&dispatch_info, 0).unwrap());
+		assert_ok!(extrinsic.function.apply_self_contained(alice).unwrap());
+
+		System::set_block_number(3);
+		System::reset_events();
+
+		let tx_raw = fs::read_to_string("./txs/msg_execute_contract").unwrap();
+		let tx_raw = tx_raw.trim();
+		let tx_bytes = Base64::decode_vec(tx_raw).unwrap();
+
+		let call = pallet_cosmos::Call::<Test>::transact { tx_bytes };
+		let source = call.check_self_contained().unwrap().unwrap();
+		let extrinsic = CheckedExtrinsic::<CosmosSigner, _, (), H160> {
+			signed: fp_self_contained::CheckedSignature::SelfContained(source),
+			function: RuntimeCall::Cosmos(call.clone()),
+		};
+		let dispatch_info = extrinsic.get_dispatch_info();
+
+		assert_ok!(call.pre_dispatch_self_contained(&dispatch_info, 0).unwrap());
+		let post_info = extrinsic.function.apply_self_contained(alice).unwrap().unwrap();
+
+		let (gas_wanted, gas_used) = System::events()
+			.into_iter()
+			.find_map(|record| {
+				if let RuntimeEvent::Cosmos(pallet_cosmos::Event::Executed {
+					gas_wanted,
+					gas_used,
+					..
+				}) = record.event
+				{
+					Some((gas_wanted, gas_used))
+				} else {
+					None
+				}
+			})
+			.unwrap();
+
+		// Contract execution is charged on top of the base transaction weight.
+		assert!(
+			gas_used > <Test as pallet_cosmos::Config>::WeightInfo::default_weight().ref_time()
+		);
+		assert!(gas_used <= gas_wanted);
+		assert_eq!(post_info.actual_weight, Some(Weight::from_parts(gas_used, 0)));
+	});
+}
+
+#[test]
+fn pallet_cosmos_all_balances_test() {
+	use cosmos_sdk_proto::cosmos::bank::v1beta1::{
+		QueryAllBalancesRequest, QueryAllBalancesResponse,
+	};
+
+	new_test_ext().execute_with(|| {
+		let alice = alice();
+		let bob = bob();
+
+		// The mock converts denoms by parsing the asset id.
+		let native_asset_id = <Test as pallet_cosmos::Config>::NativeAssetId::get();
+		for asset_id in [0, native_asset_id] {
+			let denom = BoundedVec::<u8, <Test as pallet_cosmos::Config>::MaxDenomLimit>::try_from(
+				asset_id.to_string().into_bytes(),
+			)
+			.unwrap();
+			pallet_cosmos::DenomAssetRouter::<Test>::insert(denom.clone(), asset_id);
+			pallet_cosmos::AssetDenomRouter::<Test>::insert(asset_id, denom);
+		}
+
+		let balance = 1_000_000_000_000_000_000u128.to_string();
+		assert_eq!(
+			Cosmos::all_balances(&alice),
+			vec![
+				Coin { amount: balance.clone(), denom: "0".to_string() },
+				Coin { amount: balance.clone(), denom: native_asset_id.to_string() },
+			]
+		);
+		assert_eq!(
+			Cosmos::all_balances(&bob),
+			vec![Coin { amount: balance.clone(), denom: native_asset_id.to_string() }]
+		);
+
+		// The walk visits every registered denom, up to the limit.
+		assert_eq!(
+			Cosmos::balances(&bob, 2),
+			Some(vec![
+				("0".to_string(), 0),
+				(native_asset_id.to_string(), balance.parse().unwrap())
+			])
+		);
+		assert_eq!(Cosmos::balances(&bob, 1), None);
+
+		let request = QueryAllBalancesRequest {
+			address: "cosmos1qd69nuwj95gta4akjgyxtj9ujmz4w8edmqysqw".to_string(),
+			..Default::default()
+		}
+		.encode_to_vec();
+		let response = pallet_cosmos_x_bank::grpc_query::all_balances::<Test>(&request).unwrap();
+		let response = QueryAllBalancesResponse::decode(&*response).unwrap();
+		let balances: Vec<_> =
+			response.balances.into_iter().map(|coin| (coin.denom, coin.amount)).collect();
+		assert_eq!(
+			balances,
+			vec![("0".to_string(), balance.clone()), (native_asset_id.to_string(), balance)]
+		);
+		assert_eq!(response.pagination.unwrap().total, 2);
+	});
+}
+
+#[test]
+fn pallet_cosmos_grpc_query_test() {
+	use cosmos_sdk_proto::cosmos::{
+		auth::v1beta1::{BaseAccount, QueryAccountRequest, QueryAccountResponse},
+		bank::v1beta1::{QueryDenomMetadataRequest, QueryDenomMetadataResponse},
+	};
+
+	new_test_ext().execute_with(|| {
+		let request = QueryDenomMetadataRequest { denom: "0".to_string() }.encode_to_vec();
+		let response = pallet_cosmos_x_bank::grpc_query::denom_metadata::<Test>(&request).unwrap();
+		let metadata = QueryDenomMetadataResponse::decode(&*response).unwrap().metadata.unwrap();
+		assert_eq!(metadata.base, "0");
+		assert_eq!(metadata.display, "stake");
+		assert_eq!(metadata.denom_units.len(), 2);
+		assert_eq!(metadata.denom_units[1].exponent, 18);
+
+		let request = QueryDenomMetadataRequest { denom: "1".to_string() }.encode_to_vec();
+		assert!(pallet_cosmos_x_bank::grpc_query::denom_metadata::<Test>(&request).is_err());
+
+		let address = "cosmos1qd69nuwj95gta4akjgyxtj9ujmz4w8edmqysqw".to_string();
+		let request = QueryAccountRequest { address: address.clone() }.encode_to_vec();
+		let response = pallet_cosmos_x_auth::grpc_query::account::<Test>(&request).unwrap();
+		let account = QueryAccountResponse::decode(&*response).unwrap().account.unwrap();
+		assert_eq!(account.type_url, "/cosmos.auth.v1beta1.BaseAccount");
+		let account = BaseAccount::decode(&*account.value).unwrap();
+		assert_eq!(account.address, address);
+		assert_eq!(account.sequence, 0);
+	});
+}
+
+#[test]
+fn pallet_cosmwasm_ibc_channel_test() {
+	use cosmwasm_std::{
+		Addr, IbcChannel, IbcChannelConnectMsg, IbcChannelOpenMsg, IbcEndpoint, IbcOrder,
+		IbcPacket, IbcPacketReceiveMsg,
+	};
+	use frame_support::traits::fungibles::Inspect as _;
+	use pallet_cosmwasm::runtimes::vm::InitialStorageMutability;
+	use std::collections::BTreeMap;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = alice();
+		let bob = bob();
+		let alice_address = "cosmos1qd69nuwj95gta4akjgyxtj9ujmz4w8edmqysqw";
+
+		// IBC exports are detected at upload and bind the contract port at instantiation.
+		let code = fs::read("../../composable/vm-wasmi/fixtures/cw20_ics20.wasm").unwrap();
+		let (_, code_id) =
+			Cosmwasm::do_upload(&alice, code.try_into().unwrap(), AccessConfig::Everybody).unwrap();
+		let message = format!(
+			r#"{{"default_timeout":3600,"gov_contract":"{}","allowlist":[],"default_gas_limit":null}}"#,
+			alice_address
+		);
+		let contract = pallet_cosmwasm::instantiate::<Test>(
+			alice,
+			code_id,
+			b"salt".to_vec(),
+			None,
+			b"ics20".to_vec(),
+			BTreeMap::new(),
+			GAS,
+			message.into_bytes(),
+		)
+		.unwrap();
+		let port_id = Cosmwasm::do_compute_ibc_contract_port(contract);
+		assert_eq!(Cosmwasm::do_ibc_port_contract(&port_id).unwrap(), contract);
+
+		let channel = IbcChannel::new(
+			IbcEndpoint { port_id: port_id.clone(), channel_id: "channel-0".into() },
+			IbcEndpoint { port_id: "transfer".into(), channel_id: "channel-0".into() },
+			IbcOrder::Unordered,
+			"ics20-1",
+			"connection-0",
+		);
+		let mut shared = Cosmwasm::do_create_vm_shared(GAS, InitialStorageMutability::ReadWrite);
+		assert_eq!(
+			Cosmwasm::do_ibc_channel_open(
+				&mut shared,
+				bob,
+				IbcChannelOpenMsg::new_init(channel.clone())
+			)
+			.unwrap(),
+			None
+		);
+		assert_ok!(Cosmwasm::do_ibc_channel_connect(
+			&mut shared,
+			bob,
+			IbcChannelConnectMsg::new_ack(channel.clone(), "ics20-1")
+		));
+
+		// Outgoing transfer, escrowing the funds in the contract.
+		let balance = Assets::balance(0, alice);
+		let message = r#"{"transfer":{"channel":"channel-0","remote_address":"cosmos1remote","timeout":null}}"#;
+		assert_ok!(pallet_cosmwasm::execute::<Test>(
+			alice,
+			contract,
+			BTreeMap::from([(0, (1_000_000, false))]).try_into().unwrap(),
+			GAS,
+			message.as_bytes().to_vec().try_into().unwrap(),
+		));
+		assert_eq!(Assets::balance(0, alice), balance - 1_000_000);
+
+		let sent_packets = SentPackets::take();
+		assert_eq!(sent_packets.len(), 1);
+		let (sent_port_id, channel_id, data, timeout) = sent_packets[0].clone();
+		assert_eq!(sent_port_id, port_id);
+		assert_eq!(channel_id, "channel-0");
+
+		// Incoming transfer of the same funds, relayed back by the counterparty.
+		let mut packet: serde_json::Value = serde_json::from_slice(&data).unwrap();
+		packet["denom"] = format!("transfer/channel-0/{}", packet["denom"].as_str().unwrap()).into();
+		packet["receiver"] = packet["sender"].take();
+		packet["sender"] = "cosmos1remote".into();
+		let packet = IbcPacket::new(
+			serde_json::to_vec(&packet).unwrap(),
+			channel.counterparty_endpoint.clone(),
+			channel.endpoint.clone(),
+			1,
+			timeout,
+		);
+		let acknowledgement = Cosmwasm::do_ibc_packet_receive(
+			&mut shared,
+			bob,
+			IbcPacketReceiveMsg::new(packet, Addr::unchecked(alice_address)),
+		)
+		.unwrap()
+		.unwrap();
+		assert_eq!(
+			serde_json::from_slice::<serde_json::Value>(&acknowledgement).unwrap(),
+			serde_json::json!({ "result": "MQ==" })
+		);
+		assert_eq!(Assets::balance(0, alice), balance);
+	});
+}
+
+#[test]
+fn pallet_cosmwasm_sudo_test() {
+	use cosmos_sdk_proto::{cosmwasm::wasm::v1::MsgSudoContract, Any};
+	use cosmwasm_std::{ContractResult, Empty, Response};
+	use cosmwasm_vm_wasmi::code_gen::{ModuleDefinition, WasmModule};
+	use frame_support::assert_noop;
+	use pallet_cosmos_types::{
+		context::{traits::Context as _, Context},
+		errors::RootError,
+		events::traits::EventManager as _,
+		msgservice::MsgHandler,
+	};
+	use pallet_cosmos_x_wasm::msgs::MsgSudoContractHandler;
+	use pallet_cosmwasm::types::EntryPoint;
+	use sp_runtime::{traits::Convert, DispatchError};
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = alice();
+		let bob_address = "cosmos1n5jgx64zw38ss76mzuu4uc7jeyqw2vfjk60fdz";
+
+		let response =
+			ContractResult::Ok(Response::<Empty>::new().add_attribute("Hello", "governance!"));
+		let module: WasmModule = ModuleDefinition::with_sudo_response(response).unwrap().into();
+		let (_, code_id) =
+			Cosmwasm::do_upload(&alice, module.code.try_into().unwrap(), AccessConfig::Everybody)
+				.unwrap();
+		let contract = instantiate(alice, code_id, b"salt", b"sudo");
+
+		// The extrinsic is restricted to the root origin.
+		let message = || b"{}".to_vec().try_into().unwrap();
+		assert_noop!(
+			Cosmwasm::sudo(RuntimeOrigin::signed(alice), contract, GAS, message()),
+			DispatchError::BadOrigin
+		);
+
+		System::reset_events();
+		assert_ok!(Cosmwasm::sudo(RuntimeOrigin::root(), contract, GAS, message()));
+		assert!(System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::Cosmwasm(pallet_cosmwasm::Event::Executed {
+				entrypoint: EntryPoint::Sudo,
+				..
+			})
+		)));
+
+		// The message is restricted to the configured authority.
+		let contract_address = AccountToAddr::<Test>::convert(contract);
+		let msg = |authority: &str| {
+			Any::from_msg(&MsgSudoContract {
+				authority: authority.to_string(),
+				contract: contract_address.clone(),
+				msg: b"{}".to_vec(),
+			})
+			.unwrap()
+		};
+		let handler = MsgSudoContractHandler::<Test, WasmAuthority>::default();
+
+		let mut ctx = Context::new(GAS);
+		assert_eq!(
+			handler.handle(&msg(bob_address), &mut ctx),
+			Err(RootError::Unauthorized.into())
+		);
+
+		let mut ctx = Context::new(GAS);
+		assert_ok!(handler.handle(&msg(&WasmAuthority::get()), &mut ctx));
+		assert_eq!(
+			ctx.event_manager().events(),
+			vec![CosmosEvent {
+				r#type: "sudo".into(),
+				attributes: vec![EventAttribute {
+					key: "_contract_address".into(),
+					value: contract_address.into(),
+				}],
+			}]
+		);
+	});
+}
+
+#[test]
+fn pallet_cosmwasm_any_msg_test() {
+	use cosmos_sdk_proto::{
+		cosmos::{
+			bank::v1beta1::{MsgMultiSend, MsgSend},
+			base::v1beta1::Coin,
+		},
+		cosmwasm::wasm::v1::MsgExecuteContract,
+		Any,
+	};
+	use cosmwasm_std::{AnyMsg, ContractResult, Empty, Response};
+	use cosmwasm_vm_wasmi::code_gen::{ModuleDefinition, WasmModule};
+	use pallet_cosmwasm::{
+		runtimes::vm::{CosmwasmVMError, InitialStorageMutability},
+		types::CodeIdentifier,
+	};
+	use sp_runtime::traits::Convert;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = alice();
+		let alice_address = "cosmos1qd69nuwj95gta4akjgyxtj9ujmz4w8edmqysqw";
+		let bob = bob();
+		let bob_address = "cosmos1n5jgx64zw38ss76mzuu4uc7jeyqw2vfjk60fdz";
+
+		// Instantiates a contract emitting `msg`, without salt so that the first contract gets the
+		// classic address of code id 1 and instance id 1.
+		let instantiate = |msg: Any| {
+			let response = ContractResult::Ok(
+				Response::<Empty>::new()
+					.add_message(AnyMsg { type_url: msg.type_url, value: msg.value.into() }),
+			);
+			let module: WasmModule =
+				ModuleDefinition::with_instantiate_response(response).unwrap().into();
+			let (_, code_id) = Cosmwasm::do_upload(
+				&alice,
+				module.code.try_into().unwrap(),
+				AccessConfig::Everybody,
+			)
+			.unwrap();
+			let mut shared =
+				Cosmwasm::do_create_vm_shared(GAS, InitialStorageMutability::ReadWrite);
+			Cosmwasm::do_instantiate(
+				&mut shared,
+				alice,
+				CodeIdentifier::CodeId(code_id),
+				None,
+				None,
+				b"any".to_vec().try_into().unwrap(),
+				Default::default(),
+				b"{}".to_vec().try_into().unwrap(),
+			)
+		};
+		let msg_send = |from_address: &str| {
+			Any::from_msg(&MsgSend {
+				from_address: from_address.to_string(),
+				to_address: bob_address.to_string(),
+				amount: vec![Coin {
+					denom: <Test as pallet_cosmos::Config>::NativeDenom::get().to_string(),
+					amount: "1000".into(),
+				}],
+			})
+			.unwrap()
+		};
+
+		let mut key = sp_io::hashing::sha2_256(b"module").to_vec();
+		key.extend_from_slice(b"wasm\0");
+		key.extend_from_slice(&1u64.to_be_bytes());
+		key.extend_from_slice(&1u64.to_be_bytes());
+		let contract =
+			AccountToAddr::<Test>::convert(sp_io::hashing::sha2_256(&key).to_vec()).unwrap();
+		let contract_address = AccountToAddr::<Test>::convert(contract);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(alice), contract, 2_000));
+
+		// The contract signs a bank transfer, which is dispatched through the message router.
+		let bob_balance = Balances::balance(&bob);
+		assert_eq!(instantiate(msg_send(&contract_address)).unwrap(), contract);
+		assert_eq!(Balances::balance(&bob), bob_balance + 1_000);
+		assert_eq!(Balances::balance(&contract), 1_000);
+
+		// Messages signed by anyone but the contract are rejected.
+		assert!(matches!(
+			instantiate(msg_send(alice_address)),
+			Err(CosmwasmVMError::Pallet(pallet_cosmwasm::Error::NotAuthorized))
+		));
+
+		// Messages outside of the filter are rejected.
+		let msg = Any {
+			type_url: "/cosmos.bank.v1beta1.MsgMultiSend".into(),
+			value: MsgMultiSend::default().encode_to_vec(),
+		};
+		assert!(matches!(instantiate(msg), Err(CosmwasmVMError::Unsupported)));
+
+		// Wasm messages are rejected even though the filter allows them.
+		let msg = Any::from_msg(&MsgExecuteContract {
+			sender: contract_address.clone(),
+			contract: contract_address,
+			msg: b"{}".to_vec(),
+			funds: vec![],
+		})
+		.unwrap();
+		assert!(matches!(instantiate(msg), Err(CosmwasmVMError::Unsupported)));
+	});
+}
+
+#[test]
+fn pallet_cosmwasm_custom_handler_test() {
+	use cosmwasm_std::{ContractResult, CosmosMsg, Response};
+	use cosmwasm_vm_wasmi::code_gen::{ModuleDefinition, WasmModule};
+	use pallet_cosmos_x_wasm::custom_handler::{AssetMetadataResponse, HorizonMsg};
+	use pallet_cosmwasm::runtimes::vm::CosmwasmVMError;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = alice();
+
+		// Instantiates a contract setting the metadata of `denom` when called through sudo.
+		let instantiate = |denom: &str, salt: &[u8]| {
+			let response = ContractResult::Ok(Response::<HorizonMsg>::new().add_message(
+				CosmosMsg::Custom(HorizonMsg::SetAssetMetadata {
+					denom: denom.to_string(),
+					name: "Horizon".into(),
+					symbol: "HZN".into(),
+					decimals: 6,
+				}),
+			));
+			let module: WasmModule = ModuleDefinition::with_sudo_response(response).unwrap().into();
+			let (_, code_id) = Cosmwasm::do_upload(
+				&alice,
+				module.code.try_into().unwrap(),
+				AccessConfig::Everybody,
+			)
+			.unwrap();
+			instantiate(alice, code_id, salt, b"custom")
+		};
+		let query = |contract, denom: &str| {
+			let request = format!(r#"{{"custom":{{"asset_metadata":{{"denom":"{}"}}}}}}"#, denom);
+			pallet_cosmwasm::query::<Test>(contract, GAS, request.into_bytes())
+				.map(|response| serde_json::from_slice::<AssetMetadataResponse>(&response).unwrap())
+		};
+
+		let contract = instantiate("1", b"owner");
+		assert_ok!(Assets::force_create(RuntimeOrigin::root(), 1, contract, true, 1));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(alice), contract, 1_000));
+
+		// Custom queries are answered by the runtime.
+		assert_eq!(
+			query(contract, "0").unwrap(),
+			AssetMetadataResponse { name: "stake".into(), symbol: "stake".into(), decimals: 18 }
+		);
+		assert!(matches!(query(contract, "2"), Err(CosmwasmVMError::Rpc(_))));
+
+		// Custom messages are dispatched by the runtime on behalf of the contract.
+		let message = || b"{}".to_vec().try_into().unwrap();
+		assert_ok!(Cosmwasm::sudo(RuntimeOrigin::root(), contract, GAS, message()));
+		assert_eq!(
+			query(contract, "1").unwrap(),
+			AssetMetadataResponse { name: "Horizon".into(), symbol: "HZN".into(), decimals: 6 }
+		);
+
+		// The metadata of assets owned by others is left untouched.
+		let contract = instantiate("0", b"stranger");
+		assert!(Cosmwasm::sudo(RuntimeOrigin::root(), contract, GAS, message()).is_err());
+		assert_eq!(
+			query(contract, "0").unwrap(),
+			AssetMetadataResponse { name: "stake".into(), symbol: "stake".into(), decimals: 18 }
+		);
+	});
+}
+
+#[test]
+fn pallet_cosmwasm_msg_instantiate_contract_test() {
+	use cosmos_sdk_proto::{cosmwasm::wasm::v1::MsgInstantiateContract, Any};
+	use pallet_cosmos_types::{
+		context::{traits::Context as _, Context},
+		events::traits::EventManager as _,
+		msgservice::MsgHandler,
+	};
+	use pallet_cosmos_x_wasm::msgs::MsgInstantiateContractHandler;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = alice();
+
+		let code_id = upload(&alice, 0);
+		assert_eq!(code_id, 1);
+
+		let msg = Any::from_msg(&MsgInstantiateContract {
+			sender: "cosmos1qd69nuwj95gta4akjgyxtj9ujmz4w8edmqysqw".to_string(),
+			admin: String::new(),
+			code_id,
+			label: "classic".to_string(),
+			msg: b"{}".to_vec(),
+			funds: vec![],
+		})
+		.unwrap();
+		let handler = MsgInstantiateContractHandler::<Test>::default();
+
+		// Addresses are derived from the code id and the instance id, as wasmd does, so the same
+		// message instantiates a new contract every time.
+		for contract in [
+			"cosmos14hj2tavq8fpesdwxxcu44rty3hh90vhujrvcmstl4zr3txmfvw9s4hmalr",
+			"cosmos1suhgf5svhu4usrurvxzlgn54ksxmn8gljarjtxqnapv8kjnp4nrs2zhgh2",
+		] {
+			let mut ctx = Context::new(GAS);
+			assert_ok!(handler.handle(&msg, &mut ctx));
+			assert_eq!(
+				ctx.event_manager().events(),
+				vec![CosmosEvent {
+					r#type: "instantiate".into(),
+					attributes: vec![
+						EventAttribute { key: "_contract_address".into(), value: contract.into() },
+						EventAttribute { key: "code_id".into(), value: "1".into() },
+					],
+				}]
+			);
+		}
+	});
+}
+
+#[test]
+fn pallet_cosmwasm_store_and_instantiate_contract_test() {
+	use cosmos_sdk_proto::{
+		cosmwasm::wasm::v1::{
+			AccessConfig, AccessType, MsgClearAdmin, MsgInstantiateContract,
+			MsgInstantiateContract2, MsgStoreAndInstantiateContract, MsgStoreCode,
+			MsgUpdateInstantiateConfig,
+		},
+		Any,
+	};
+	use pallet_cosmos_types::{
+		context::{traits::Context as _, Context},
+		errors::RootError,
+		events::traits::EventManager as _,
+		msgservice::MsgHandler,
+	};
+	use pallet_cosmos_x_wasm::msgs::{
+		MsgClearAdminHandler, MsgInstantiateContractHandler, MsgStoreAndInstantiateContractHandler,
+		MsgUpdateInstantiateConfigHandler,
+	};
+	use pallet_cosmos_x_wasm_types::errors::WasmError;
+	use pallet_cosmwasm::{types::AccessConfig as CodeAccessConfig, weights::WeightInfo as _};
+
+	frame_support::parameter_types! {
+		/// Bob, standing for the governance account.
+		pub BobAuthority: String = "cosmos1n5jgx64zw38ss76mzuu4uc7jeyqw2vfjk60fdz".into();
+	}
+
+	fn first_msg<M: Message + Default>(path: &str) -> M {
+		let tx_raw = fs::read_to_string(path).unwrap();
+		let tx = Tx::decode(&*Base64::decode_vec(tx_raw.trim()).unwrap()).unwrap();
+		M::decode(&*tx.body.unwrap().messages[0].value).unwrap()
+	}
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice_address = "cosmos1qd69nuwj95gta4akjgyxtj9ujmz4w8edmqysqw";
+		let bob_address = "cosmos1n5jgx64zw38ss76mzuu4uc7jeyqw2vfjk60fdz";
+
+		let MsgStoreCode { wasm_byte_code, .. } = first_msg("./txs/msg_store_code");
+		let MsgInstantiateContract2 { label, msg, .. } =
+			first_msg("./txs/msg_instantiate_contract2");
+		let code_hash =
+			hex::decode("4d8e90dd340993033f1b9e8e3a3ee7f8673c582ca9bcdd8c8cf3c7470d6537d5")
+				.unwrap();
+		let store_and_instantiate = |authority: &str, code_hash: Vec<u8>| {
+			Any::from_msg(&MsgStoreAndInstantiateContract {
+				authority: authority.to_string(),
+				wasm_byte_code: wasm_byte_code.clone(),
+				admin: alice_address.to_string(),
+				label: label.clone(),
+				msg: msg.clone(),
+				code_hash,
+				..Default::default()
+			})
+			.unwrap()
+		};
+		let handler = MsgStoreAndInstantiateContractHandler::<Test, WasmAuthority>::default();
+
+		// The message is restricted to the configured authority.
+		let mut ctx = Context::new(GAS);
+		assert_eq!(
+			handler.handle(&store_and_instantiate(bob_address, code_hash.clone()), &mut ctx),
+			Err(RootError::Unauthorized.into())
+		);
+
+		let mut ctx = Context::new(GAS);
+		assert_eq!(
+			handler.handle(&store_and_instantiate(alice_address, vec![0; 32]), &mut ctx),
+			Err(WasmError::Invalid.into())
+		);
+
+		// The upload is charged and the code is pinned, unless `unpin_code` is set.
+		let mut ctx = Context::new(GAS);
+		assert_ok!(handler.handle(&store_and_instantiate(alice_address, code_hash), &mut ctx));
+		assert!(Cosmwasm::is_pinned(1));
+		let events = ctx.event_manager().events();
+		assert_eq!(events.len(), 3);
+		assert_eq!(events[0].r#type, b"store_code");
+		assert_eq!(events[1].r#type, b"pin_code");
+		assert_eq!(events[2].r#type, b"instantiate");
+		let contract = String::from_utf8(events[2].attributes[0].value.clone()).unwrap();
+
+		// On top of the instantiation, at least the compressed code size is charged for upload.
+		let mut instantiate_ctx = Context::new(GAS);
+		assert_ok!(MsgInstantiateContractHandler::<Test>::default().handle(
+			&Any::from_msg(&MsgInstantiateContract {
+				sender: alice_address.to_string(),
+				admin: String::new(),
+				code_id: 1,
+				label: label.clone(),
+				msg: msg.clone(),
+				funds: vec![],
+			})
+			.unwrap(),
+			&mut instantiate_ctx
+		));
+		let upload = <Test as pallet_cosmwasm::Config>::WeightInfo::upload(
+			wasm_byte_code.len().try_into().unwrap(),
+		);
+		assert!(
+			ctx.gas_meter().consumed_gas() - instantiate_ctx.gas_meter().consumed_gas() >=
+				upload.ref_time()
+		);
+
+		// Only the creator may update the instantiate config of the code.
+		let update_instantiate_config =
+			|sender: &str, permission: AccessType, addresses: Vec<String>| {
+				Any::from_msg(&MsgUpdateInstantiateConfig {
+					sender: sender.to_string(),
+					code_id: 1,
+					new_instantiate_permission: Some(AccessConfig {
+						permission: permission.into(),
+						addresses,
+					}),
+				})
+				.unwrap()
+			};
+		let handler = MsgUpdateInstantiateConfigHandler::<Test, WasmAuthority>::default();
+
+		let mut ctx = Context::new(GAS);
+		assert_eq!(
+			handler.handle(
+				&update_instantiate_config(bob_address, AccessType::Everybody, vec![]),
+				&mut ctx
+			),
+			Err(RootError::Unauthorized.into())
+		);
+		assert_eq!(
+			handler.handle(
+				&update_instantiate_config(alice_address, AccessType::Unspecified, vec![]),
+				&mut ctx
+			),
+			Err(WasmError::Invalid.into())
+		);
+		assert_ok!(handler.handle(
+			&update_instantiate_config(alice_address, AccessType::Nobody, vec![]),
+			&mut ctx
+		));
+		assert_eq!(
+			Cosmwasm::code_info(1).unwrap().instantiate_permission,
+			CodeAccessConfig::Nobody
+		);
+
+		// The instantiate permission of the code is enforced.
+		let instantiate = Any::from_msg(&MsgInstantiateContract {
+			sender: alice_address.to_string(),
+			admin: String::new(),
+			code_id: 1,
+			label: label.clone(),
+			msg: msg.clone(),
+			funds: vec![],
+		})
+		.unwrap();
+		let instantiate_handler = MsgInstantiateContractHandler::<Test>::default();
+		assert_eq!(
+			instantiate_handler.handle(&instantiate, &mut Context::new(GAS)),
+			Err(WasmError::InstantiateFailed.into())
+		);
+
+		assert_ok!(handler.handle(
+			&update_instantiate_config(
+				alice_address,
+				AccessType::AnyOfAddresses,
+				vec![alice_address.to_string()]
+			),
+			&mut ctx
+		));
+		assert_ok!(instantiate_handler.handle(&instantiate, &mut Context::new(GAS)));
+
+		// The admin is cleared only once.
+		let clear_admin = Any::from_msg(&MsgClearAdmin {
+			sender: alice_address.to_string(),
+			contract: contract.clone(),
+		})
+		.unwrap();
+		let handler = MsgClearAdminHandler::<Test>::default();
+
+		let mut ctx = Context::new(GAS);
+		assert_ok!(handler.handle(&clear_admin, &mut ctx));
+		assert_eq!(
+			ctx.event_manager().events(),
+			vec![CosmosEvent {
+				r#type: "update_contract_admin".into(),
+				attributes: vec![
+					EventAttribute { key: "_contract_address".into(), value: contract.into() },
+					EventAttribute { key: "new_admin_address".into(), value: vec![] },
+				],
+			}]
+		);
+		assert_eq!(
+			handler.handle(&clear_admin, &mut Context::new(GAS)),
+			Err(RootError::Unauthorized.into())
+		);
+		let clear_missing_admin = Any::from_msg(&MsgClearAdmin {
+			sender: alice_address.to_string(),
+			contract: bob_address.to_string(),
+		})
+		.unwrap();
+		assert_eq!(
+			handler.handle(&clear_missing_admin, &mut Context::new(GAS)),
+			Err(WasmError::NotFound.into())
+		);
+
+		// The authority may update the instantiate config of any code.
+		assert_ok!(MsgUpdateInstantiateConfigHandler::<Test, BobAuthority>::default().handle(
+			&update_instantiate_config(bob_address, AccessType::Everybody, vec![]),
+			&mut Context::new(GAS)
+		));
+		assert_eq!(
+			Cosmwasm::code_info(1).unwrap().instantiate_permission,
+			CodeAccessConfig::Everybody
+		);
+	});
+}
+
+#[test]
+fn pallet_cosmwasm_code_upload_access_test() {
+	use cosmos_sdk_proto::{cosmwasm::wasm::v1::MsgStoreCode, Any};
+	use cosmwasm_vm_wasmi::code_gen::{ModuleDefinition, WasmModule};
+	use frame_support::assert_noop;
+	use pallet_cosmos_types::{
+		context::{traits::Context as _, Context},
+		errors::RootError,
+		msgservice::MsgHandler,
+	};
+	use pallet_cosmos_x_wasm::msgs::MsgStoreCodeHandler;
+	use sp_runtime::DispatchError;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = alice();
+		let bob = bob();
+
+		// Only the root origin may change the parameter.
+		let access = AccessConfig::AnyOfAddresses(vec![alice].try_into().unwrap());
+		assert_noop!(
+			Cosmwasm::set_code_upload_access(RuntimeOrigin::signed(alice), access.clone()),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(Cosmwasm::set_code_upload_access(RuntimeOrigin::root(), access));
+
+		// The parameter is enforced on the extrinsic.
+		let module: WasmModule = ModuleDefinition::new(vec![], 0, None).unwrap().into();
+		let code = || module.code.clone().try_into().unwrap();
+		assert_noop!(
+			Cosmwasm::upload(RuntimeOrigin::signed(bob), code()),
+			pallet_cosmwasm::Error::<Test>::NotAuthorized
+		);
+		assert_ok!(Cosmwasm::upload(RuntimeOrigin::signed(alice), code()));
+
+		// And on the message.
+		let tx_raw = fs::read_to_string("./txs/msg_store_code").unwrap();
+		let tx = Tx::decode(&*Base64::decode_vec(tx_raw.trim()).unwrap()).unwrap();
+		let msg = MsgStoreCode::decode(&*tx.body.unwrap().messages[0].value).unwrap();
+		let store_code = |sender: &str| {
+			Any::from_msg(&MsgStoreCode { sender: sender.to_string(), ..msg.clone() }).unwrap()
+		};
+		let handler = MsgStoreCodeHandler::<Test>::default();
+
+		assert_eq!(
+			handler.handle(
+				&store_code("cosmos1n5jgx64zw38ss76mzuu4uc7jeyqw2vfjk60fdz"),
+				&mut Context::new(GAS)
+			),
+			Err(RootError::Unauthorized.into())
+		);
+		assert_ok!(handler.handle(
+			&store_code("cosmos1qd69nuwj95gta4akjgyxtj9ujmz4w8edmqysqw"),
+			&mut Context::new(GAS)
+		));
+	});
+}
+
+#[test]
+fn pallet_cosmwasm_genesis_code_upload_access_test() {
+	use sp_runtime::BuildStorage;
+
+	let alice = alice();
+	let access = AccessConfig::AnyOfAddresses(vec![alice].try_into().unwrap());
+
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_cosmwasm::GenesisConfig::<Test> {
+		contracts: vec![],
+		code_upload_access: access.clone(),
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		assert_eq!(Cosmwasm::code_upload_access(), access);
+	});
+}
+
+#[test]
+fn pallet_cosmwasm_wasm_query_test() {
+	use cosmos_sdk_proto::{
+		cosmos::base::query::v1beta1::PageRequest,
+		cosmwasm::wasm::v1::{
+			AccessType, QueryCodeRequest, QueryCodeResponse, QueryContractInfoRequest,
+			QueryContractInfoResponse, QueryContractsByCodeRequest, QueryContractsByCodeResponse,
+			QueryParamsRequest, QueryParamsResponse,
+		},
+	};
+	use cosmwasm_vm_wasmi::code_gen::{ModuleDefinition, WasmModule};
+	use pallet_cosmos_x_wasm::query;
+	use pallet_cosmos_x_wasm_types::errors::WasmError;
+	use sp_runtime::traits::Convert;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = alice();
+		let alice_address = "cosmos1qd69nuwj95gta4akjgyxtj9ujmz4w8edmqysqw";
+
+		let module: WasmModule = ModuleDefinition::new(vec![], 0, None).unwrap().into();
+		let (_, code_id) = Cosmwasm::do_upload(
+			&alice,
+			module.code.clone().try_into().unwrap(),
+			AccessConfig::Everybody,
+		)
+		.unwrap();
+		let mut contracts: Vec<_> = [b"foo", b"bar"]
+			.into_iter()
+			.map(|salt| {
+				let contract = instantiate(alice, code_id, salt, b"query");
+				AccountToAddr::<Test>::convert(contract)
+			})
+			.collect();
+
+		let response = query::contract_info::<Test>(
+			&QueryContractInfoRequest { address: contracts[0].clone() }.encode_to_vec(),
+		)
+		.unwrap();
+		let info = QueryContractInfoResponse::decode(&*response).unwrap().contract_info.unwrap();
+		assert_eq!(info.code_id, code_id);
+		assert_eq!(info.creator, alice_address);
+		assert_eq!(info.label, "query");
+		assert_eq!(
+			query::contract_info::<Test>(
+				&QueryContractInfoRequest { address: alice_address.to_string() }.encode_to_vec()
+			),
+			Err(WasmError::NotFound.into())
+		);
+
+		// Contracts by code are paginated, resuming after the returned key.
+		let contracts_by_code = |key: Vec<u8>| {
+			let request = QueryContractsByCodeRequest {
+				code_id,
+				pagination: Some(PageRequest {
+					key,
+					limit: 1,
+					count_total: true,
+					..Default::default()
+				}),
+			};
+			let response = query::contracts_by_code::<Test>(&request.encode_to_vec()).unwrap();
+			QueryContractsByCodeResponse::decode(&*response).unwrap()
+		};
+		let first = contracts_by_code(vec![]);
+		let first_page = first.pagination.unwrap();
+		assert_eq!(first.contracts.len(), 1);
+		assert_eq!(first_page.total, 2);
+		assert!(!first_page.next_key.is_empty());
+		let second = contracts_by_code(first_page.next_key);
+		assert_eq!(second.contracts.len(), 1);
+		assert!(second.pagination.unwrap().next_key.is_empty());
+		let mut listed = [first.contracts, second.contracts].concat();
+		listed.sort();
+		contracts.sort();
+		assert_eq!(listed, contracts);
+
+		let response = query::code::<Test>(&QueryCodeRequest { code_id }.encode_to_vec()).unwrap();
+		let code = QueryCodeResponse::decode(&*response).unwrap();
+		assert_eq!(code.data, module.code);
+		let code_info = code.code_info.unwrap();
+		assert_eq!(code_info.creator, alice_address);
+		assert_eq!(
+			code_info.instantiate_permission.unwrap().permission,
+			i32::from(AccessType::Everybody)
+		);
+
+		let response = query::params::<Test>(&QueryParamsRequest {}.encode_to_vec()).unwrap();
+		let params = QueryParamsResponse::decode(&*response).unwrap().params.unwrap();
+		assert_eq!(params.code_upload_access.unwrap().permission, i32::from(AccessType::Everybody));
+	});
+}
+
+#[test]
+fn pallet_cosmwasm_contract_history_test() {
+	use cosmos_sdk_proto::cosmwasm::wasm::v1::{
+		AbsoluteTxPosition, ContractCodeHistoryOperationType, QueryContractHistoryRequest,
+		QueryContractHistoryResponse, QueryContractInfoRequest, QueryContractInfoResponse,
+	};
+	use pallet_cosmos_x_wasm::query;
+	use pallet_cosmwasm::types::{CodeIdentifier, ContractCodeHistoryOperation};
+	use sp_runtime::traits::Convert;
+	use std::collections::BTreeMap;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = alice();
+
+		let [old_code_id, new_code_id] = [0, 1].map(|size| upload(&alice, size));
+		let contract = pallet_cosmwasm::instantiate::<Test>(
+			alice,
+			old_code_id,
+			b"salt".to_vec(),
+			Some(alice),
+			b"history".to_vec(),
+			BTreeMap::new(),
+			GAS,
+			b"{\"init\":{}}".to_vec(),
+		)
+		.unwrap();
+
+		System::set_block_number(2);
+		assert_ok!(Cosmwasm::migrate(
+			RuntimeOrigin::signed(alice),
+			contract,
+			CodeIdentifier::CodeId(new_code_id),
+			GAS,
+			b"{\"migrate\":{}}".to_vec().try_into().unwrap(),
+		));
+
+		let entries: Vec<_> = Cosmwasm::contract_history(&contract, None)
+			.unwrap()
+			.map(|(_, entry)| (entry.operation, entry.code_id, entry.updated, entry.msg.to_vec()))
+			.collect();
+		assert_eq!(
+			entries,
+			vec![
+				(ContractCodeHistoryOperation::Init, old_code_id, 1, b"{\"init\":{}}".to_vec()),
+				(
+					ContractCodeHistoryOperation::Migrate,
+					new_code_id,
+					2,
+					b"{\"migrate\":{}}".to_vec()
+				),
+			]
+		);
+
+		let address = AccountToAddr::<Test>::convert(contract);
+		let response = query::contract_history::<Test>(
+			&QueryContractHistoryRequest { address: address.clone(), pagination: None }
+				.encode_to_vec(),
+		)
+		.unwrap();
+		let entries = QueryContractHistoryResponse::decode(&*response).unwrap().entries;
+		assert_eq!(entries.len(), 2);
+		assert_eq!(entries[0].operation, i32::from(ContractCodeHistoryOperationType::Init));
+		assert_eq!(entries[1].operation, i32::from(ContractCodeHistoryOperationType::Migrate));
+		assert_eq!(entries[1].code_id, new_code_id);
+		assert_eq!(entries[1].updated, Some(AbsoluteTxPosition { block_height: 2, tx_index: 0 }));
+
+		let response =
+			query::contract_info::<Test>(&QueryContractInfoRequest { address }.encode_to_vec())
+				.unwrap();
+		let info = QueryContractInfoResponse::decode(&*response).unwrap().contract_info.unwrap();
+		assert_eq!(info.created, Some(AbsoluteTxPosition { block_height: 1, tx_index: 0 }));
+
+		// The oldest entries are dropped once the history is full.
+		let max = <Test as pallet_cosmwasm::Config>::MaxCodeHistoryEntries::get();
+		for code_id in [old_code_id, new_code_id].into_iter().cycle().take(max as usize) {
+			assert_ok!(Cosmwasm::migrate(
+				RuntimeOrigin::signed(alice),
+				contract,
+				CodeIdentifier::CodeId(code_id),
+				GAS,
+				b"{\"migrate\":{}}".to_vec().try_into().unwrap(),
+			));
+		}
+		let seqs: Vec<_> = Cosmwasm::contract_history(&contract, None)
+			.unwrap()
+			.map(|(seq, _)| seq)
+			.collect();
+		assert_eq!(seqs, (2..max + 2).collect::<Vec<_>>());
+		let seqs: Vec<_> = Cosmwasm::contract_history(&contract, Some(max))
+			.unwrap()
+			.map(|(seq, _)| seq)
+			.collect();
+		assert_eq!(seqs, vec![max + 1]);
+	});
+}
+
+#[test]
+fn pallet_cosmwasm_pin_codes_test() {
+	use cosmos_sdk_proto::{
+		cosmwasm::wasm::v1::{
+			MsgPinCodes, MsgUnpinCodes, QueryPinnedCodesRequest, QueryPinnedCodesResponse,
+		},
+		Any,
+	};
+	use frame_support::assert_noop;
+	use pallet_cosmos_types::{
+		context::{traits::Context as _, Context},
+		errors::RootError,
+		events::traits::EventManager as _,
+		msgservice::MsgHandler,
+	};
+	use pallet_cosmos_x_wasm::{
+		msgs::{MsgPinCodesHandler, MsgUnpinCodesHandler},
+		query,
+	};
+	use pallet_cosmwasm::{
+		estimated_weights::EstimatedWeightInfo, runtimes::vm::InitialStorageMutability,
+		types::CodeIdentifier, weights::SubstrateWeight,
+	};
+	use sp_runtime::DispatchError;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = alice();
+		let bob_address = "cosmos1n5jgx64zw38ss76mzuu4uc7jeyqw2vfjk60fdz";
+
+		let [code_id, other_code_id] = [0, 1].map(|size| upload(&alice, size));
+
+		// The extrinsic is restricted to the root origin.
+		assert_noop!(
+			Cosmwasm::pin_codes(RuntimeOrigin::signed(alice), vec![code_id]),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Cosmwasm::pin_codes(RuntimeOrigin::root(), vec![code_id, 42]),
+			pallet_cosmwasm::Error::<Test>::CodeNotFound
+		);
+		assert_ok!(Cosmwasm::pin_codes(RuntimeOrigin::root(), vec![code_id]));
+		assert!(Cosmwasm::is_pinned(code_id));
+		assert!(!Cosmwasm::is_pinned(other_code_id));
+
+		// Pinned codes keep serving their contracts.
+		instantiate(alice, code_id, b"salt", b"pinned");
+
+		// Loading the module of a pinned code spares the read of its instrumented code.
+		let gas_used = |salt: &[u8]| {
+			let mut shared =
+				Cosmwasm::do_create_vm_shared(GAS, InitialStorageMutability::ReadWrite);
+			Cosmwasm::do_instantiate(
+				&mut shared,
+				alice,
+				CodeIdentifier::CodeId(code_id),
+				Some(salt.to_vec().try_into().unwrap()),
+				None,
+				b"pinned".to_vec().try_into().unwrap(),
+				Default::default(),
+				b"{}".to_vec().try_into().unwrap(),
+			)
+			.unwrap();
+			GAS - shared.gas.remaining()
+		};
+		let pinned_gas = gas_used(b"salt-1");
+		assert_ok!(Cosmwasm::unpin_codes(RuntimeOrigin::root(), vec![code_id]));
+		assert_eq!(
+			gas_used(b"salt-2") - pinned_gas,
+			(SubstrateWeight::<Test>::load_module(0) -
+				SubstrateWeight::<Test>::load_pinned_module(0))
+			.ref_time()
+		);
+		assert_ok!(Cosmwasm::pin_codes(RuntimeOrigin::root(), vec![code_id]));
+
+		// The message is restricted to the configured authority.
+		let pin_codes = |authority: &str| {
+			Any::from_msg(&MsgPinCodes {
+				authority: authority.to_string(),
+				code_ids: vec![other_code_id],
+			})
+			.unwrap()
+		};
+		let handler = MsgPinCodesHandler::<Test, WasmAuthority>::default();
+
+		let mut ctx = Context::new(GAS);
+		assert_eq!(
+			handler.handle(&pin_codes(bob_address), &mut ctx),
+			Err(RootError::Unauthorized.into())
+		);
+
+		let mut ctx = Context::new(GAS);
+		assert_ok!(handler.handle(&pin_codes(&WasmAuthority::get()), &mut ctx));
+		assert_eq!(
+			ctx.event_manager().events(),
+			vec![CosmosEvent {
+				r#type: "pin_code".into(),
+				attributes: vec![EventAttribute {
+					key: "code_id".into(),
+					value: other_code_id.to_string().into(),
+				}],
+			}]
+		);
+
+		let pinned_codes = || {
+			let request = QueryPinnedCodesRequest { pagination: None };
+			let response = query::pinned_codes::<Test>(&request.encode_to_vec()).unwrap();
+			let mut code_ids = QueryPinnedCodesResponse::decode(&*response).unwrap().code_ids;
+			code_ids.sort();
+			code_ids
+		};
+		assert_eq!(pinned_codes(), vec![code_id, other_code_id]);
+
+		assert_ok!(Cosmwasm::unpin_codes(RuntimeOrigin::root(), vec![code_id]));
+		let unpin_codes = Any::from_msg(&MsgUnpinCodes {
+			authority: WasmAuthority::get(),
+			code_ids: vec![other_code_id],
+		})
+		.unwrap();
+		assert_ok!(MsgUnpinCodesHandler::<Test, WasmAuthority>::default()
+			.handle(&unpin_codes, &mut Context::new(GAS)));
+		assert!(pinned_codes().is_empty());
+	});
+}
+
+#[test]
+fn pallet_cosmwasm_remove_code_test() {
+	use cosmos_sdk_proto::Any;
+	use frame_support::{assert_noop, traits::ReservableCurrency};
+	use pallet_cosmos_types::{
+		context::{traits::Context as _, Context},
+		errors::RootError,
+		events::traits::EventManager as _,
+		msgservice::MsgHandler,
+	};
+	use pallet_cosmos_x_wasm::msgs::MsgRemoveCodeHandler;
+	use pallet_cosmos_x_wasm_types::{errors::WasmError, proto::MsgRemoveCode};
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		let alice = alice();
+		let bob = bob();
+		let alice_address = "cosmos1qd69nuwj95gta4akjgyxtj9ujmz4w8edmqysqw";
+
+		let [used_code_id, code_id, other_code_id] = [0, 1, 2].map(|size| upload(&alice, size));
+		instantiate(alice, used_code_id, b"salt", b"used");
+
+		// Only the creator, or root, may remove a code no contract runs.
+		assert_noop!(
+			Cosmwasm::remove_code(RuntimeOrigin::signed(bob), code_id),
+			pallet_cosmwasm::Error::<Test>::NotAuthorized
+		);
+		assert_noop!(
+			Cosmwasm::remove_code(RuntimeOrigin::signed(alice), used_code_id),
+			pallet_cosmwasm::Error::<Test>::CodeInUse
+		);
+
+		let reserved = Balances::reserved_balance(alice);
+		let code_len = Cosmwasm::pristine_code(code_id).unwrap().len() as u128;
+		assert_ok!(Cosmwasm::remove_code(RuntimeOrigin::signed(alice), code_id));
+		assert_eq!(
+			Balances::reserved_balance(alice),
+			reserved - code_len * CodeStorageByteDeposit::get() as u128
+		);
+		assert!(Cosmwasm::code_info(code_id).is_err());
+		assert!(Cosmwasm::pristine_code(code_id).is_err());
+		System::assert_last_event(RuntimeEvent::Cosmwasm(pallet_cosmwasm::Event::CodeRemoved {
+			code_id,
+		}));
+
+		// And so on the message.
+		let remove_code = |sender: &str, code_id| {
+			Any::from_msg(&MsgRemoveCode { sender: sender.to_string(), code_id }).unwrap()
+		};
+		let handler = MsgRemoveCodeHandler::<Test, WasmAuthority>::default();
+
+		assert_eq!(
+			handler.handle(
+				&remove_code("cosmos1n5jgx64zw38ss76mzuu4uc7jeyqw2vfjk60fdz", other_code_id),
+				&mut Context::new(GAS)
+			),
+			Err(RootError::Unauthorized.into())
+		);
+		assert_eq!(
+			handler.handle(&remove_code(alice_address, used_code_id), &mut Context::new(GAS)),
+			Err(WasmError::Invalid.into())
+		);
+
+		let mut ctx = Context::new(GAS);
+		assert_ok!(handler.handle(&remove_code(alice_address, other_code_id), &mut ctx));
+		assert_eq!(
+			ctx.event_manager().events(),
+			vec![CosmosEvent {
+				r#type: "remove_code".into(),
+				attributes: vec![EventAttribute {
+					key: "code_id".into(),
+					value: other_code_id.to_string().into(),
+				}],
+			}]
+		);
+		assert!(Cosmwasm::code_info(other_code_id).is_err());
+	});
+}
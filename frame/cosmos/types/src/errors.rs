@@ -30,11 +30,13 @@ pub const ROOT_CODESPACE: u8 = 0;
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum RootError {
 	TxDecodeError = 2,
+	Unauthorized = 4,
 	InsufficientFunds = 5,
 	UnknownRequest = 6,
 	InvalidAddress = 7,
 	InvalidCoins = 10,
 	OutOfGas = 11,
+	InvalidRequest = 18,
 	UnpackAnyError = 34,
 }
 
@@ -40,10 +40,19 @@ use cosmos_sdk_proto::{
 use pallet_cosmos_types::any_match;
 use pallet_cosmos_x_auth_migrations::legacytx::stdsign::{LegacyMsg, StdSignDoc};
 use pallet_cosmos_x_bank_types::msgs::msg_send::MsgSend;
-use pallet_cosmos_x_wasm_types::tx::{
-	msg_execute_contract::MsgExecuteContract, msg_instantiate_contract2::MsgInstantiateContract2,
-	msg_migrate_contract::MsgMigrateContract, msg_store_code::MsgStoreCode,
-	msg_update_admin::MsgUpdateAdmin,
+use pallet_cosmos_x_wasm_types::{
+	proto as wasm_proto,
+	tx::{
+		msg_clear_admin::MsgClearAdmin, msg_execute_contract::MsgExecuteContract,
+		msg_instantiate_contract::MsgInstantiateContract,
+		msg_instantiate_contract2::MsgInstantiateContract2,
+		msg_migrate_contract::MsgMigrateContract, msg_pin_codes::MsgPinCodes,
+		msg_remove_code::MsgRemoveCode,
+		msg_store_and_instantiate_contract::MsgStoreAndInstantiateContract,
+		msg_store_code::MsgStoreCode, msg_sudo_contract::MsgSudoContract,
+		msg_unpin_codes::MsgUnpinCodes, msg_update_admin::MsgUpdateAdmin,
+		msg_update_instantiate_config::MsgUpdateInstantiateConfig,
+	},
 };
 use serde_json::Value;
 
@@ -99,10 +108,18 @@ impl traits::SignModeHandler for SignModeHandler {
 							msg, {
 								bank::v1beta1::MsgSend => MsgSend::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
 								wasm::v1::MsgStoreCode => MsgStoreCode::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+								wasm::v1::MsgInstantiateContract => MsgInstantiateContract::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
 								wasm::v1::MsgInstantiateContract2 => MsgInstantiateContract2::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
 								wasm::v1::MsgExecuteContract => MsgExecuteContract::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
 								wasm::v1::MsgMigrateContract => MsgMigrateContract::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
 								wasm::v1::MsgUpdateAdmin => MsgUpdateAdmin::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+								wasm::v1::MsgClearAdmin => MsgClearAdmin::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+								wasm::v1::MsgStoreAndInstantiateContract => MsgStoreAndInstantiateContract::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+								wasm::v1::MsgUpdateInstantiateConfig => MsgUpdateInstantiateConfig::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+								wasm::v1::MsgSudoContract => MsgSudoContract::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+								wasm::v1::MsgPinCodes => MsgPinCodes::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+								wasm::v1::MsgUnpinCodes => MsgUnpinCodes::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
+								wasm_proto::MsgRemoveCode => MsgRemoveCode::try_from(msg).map(LegacyMsg::get_sign_bytes).map_err(|_| SignModeHandlerError::InvalidMsg),
 							},
 							Err(SignModeHandlerError::InvalidMsg))?;
 
@@ -22,13 +22,23 @@ use alloc::{string::String, vec::Vec};
 use cosmos_sdk_proto::{
 	cosmos::{bank, tx::v1beta1::Tx},
 	cosmwasm::wasm,
+	Any,
 };
 use pallet_cosmos_types::{any_match, tx_msgs::Msg};
 use pallet_cosmos_x_bank_types::msgs::msg_send::MsgSend;
-use pallet_cosmos_x_wasm_types::tx::{
-	msg_execute_contract::MsgExecuteContract, msg_instantiate_contract2::MsgInstantiateContract2,
-	msg_migrate_contract::MsgMigrateContract, msg_store_code::MsgStoreCode,
-	msg_update_admin::MsgUpdateAdmin,
+use pallet_cosmos_x_wasm_types::{
+	proto as wasm_proto,
+	tx::{
+		msg_clear_admin::MsgClearAdmin, msg_execute_contract::MsgExecuteContract,
+		msg_instantiate_contract::MsgInstantiateContract,
+		msg_instantiate_contract2::MsgInstantiateContract2,
+		msg_migrate_contract::MsgMigrateContract, msg_pin_codes::MsgPinCodes,
+		msg_remove_code::MsgRemoveCode,
+		msg_store_and_instantiate_contract::MsgStoreAndInstantiateContract,
+		msg_store_code::MsgStoreCode, msg_sudo_contract::MsgSudoContract,
+		msg_unpin_codes::MsgUnpinCodes, msg_update_admin::MsgUpdateAdmin,
+		msg_update_instantiate_config::MsgUpdateInstantiateConfig,
+	},
 };
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -47,17 +57,7 @@ impl traits::SigVerifiableTx for SigVerifiableTx {
 
 		let body = tx.body.as_ref().ok_or(SigVerifiableTxError::EmptyTxBody)?;
 		for msg in body.messages.iter() {
-			let msg_signers = any_match!(
-				msg, {
-					bank::v1beta1::MsgSend => MsgSend::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
-					wasm::v1::MsgStoreCode => MsgStoreCode::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
-					wasm::v1::MsgInstantiateContract2 => MsgInstantiateContract2::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
-					wasm::v1::MsgExecuteContract => MsgExecuteContract::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
-					wasm::v1::MsgMigrateContract => MsgMigrateContract::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
-					wasm::v1::MsgUpdateAdmin => MsgUpdateAdmin::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
-				},
-				Err(SigVerifiableTxError::InvalidMsg)
-			)?;
+			let msg_signers = Self::get_msg_signers(msg)?;
 
 			for msg_signer in msg_signers.iter() {
 				if !signers.contains(msg_signer) {
@@ -80,6 +80,28 @@ impl traits::SigVerifiableTx for SigVerifiableTx {
 		Ok(signers)
 	}
 
+	fn get_msg_signers(msg: &Any) -> Result<Vec<String>, SigVerifiableTxError> {
+		any_match!(
+			msg, {
+				bank::v1beta1::MsgSend => MsgSend::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm::v1::MsgStoreCode => MsgStoreCode::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm::v1::MsgInstantiateContract => MsgInstantiateContract::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm::v1::MsgInstantiateContract2 => MsgInstantiateContract2::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm::v1::MsgExecuteContract => MsgExecuteContract::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm::v1::MsgMigrateContract => MsgMigrateContract::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm::v1::MsgUpdateAdmin => MsgUpdateAdmin::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm::v1::MsgClearAdmin => MsgClearAdmin::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm::v1::MsgStoreAndInstantiateContract => MsgStoreAndInstantiateContract::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm::v1::MsgUpdateInstantiateConfig => MsgUpdateInstantiateConfig::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm::v1::MsgSudoContract => MsgSudoContract::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm::v1::MsgPinCodes => MsgPinCodes::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm::v1::MsgUnpinCodes => MsgUnpinCodes::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+				wasm_proto::MsgRemoveCode => MsgRemoveCode::try_from(msg).map(Msg::get_signers).map_err(|_| SigVerifiableTxError::InvalidMsg),
+			},
+			Err(SigVerifiableTxError::InvalidMsg)
+		)
+	}
+
 	fn fee_payer(tx: &Tx) -> Result<String, SigVerifiableTxError> {
 		let fee = tx
 			.auth_info
@@ -0,0 +1,65 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::{string::String, vec::Vec};
+use cosmos_sdk_proto::{
+	cosmos::auth::v1beta1::{BaseAccount, QueryAccountRequest, QueryAccountResponse},
+	traits::Message,
+	Any,
+};
+use pallet_cosmos::AddressMapping;
+use pallet_cosmos_types::address::acc_address_from_bech32;
+use sp_core::H160;
+use sp_runtime::SaturatedConversion;
+
+pub const QUERY_ACCOUNT_PATH: &str = "/cosmos.auth.v1beta1.Query/Account";
+
+const BASE_ACCOUNT_TYPE_URL: &str = "/cosmos.auth.v1beta1.BaseAccount";
+
+/// Answer `/cosmos.auth.v1beta1.Query/Account` with the account as a `BaseAccount`.
+///
+/// Accounts have no account number, so it is always zero, and the public key is omitted.
+pub fn account<T: pallet_cosmos::Config>(data: &[u8]) -> Result<Vec<u8>, String> {
+	let QueryAccountRequest { address } =
+		QueryAccountRequest::decode(data).map_err(|_| String::from("invalid request"))?;
+
+	let (_hrp, address_raw) =
+		acc_address_from_bech32(&address).map_err(|_| String::from("invalid address"))?;
+	if address_raw.len() != 20 {
+		return Err("invalid address".into());
+	}
+	let who = T::AddressMapping::into_account_id(H160::from_slice(&address_raw));
+	if !frame_system::Pallet::<T>::account_exists(&who) {
+		return Err("account not found".into());
+	}
+
+	let account = BaseAccount {
+		address,
+		pub_key: None,
+		account_number: 0,
+		sequence: frame_system::Pallet::<T>::account_nonce(&who).saturated_into(),
+	};
+
+	Ok(QueryAccountResponse {
+		account: Some(Any {
+			type_url: BASE_ACCOUNT_TYPE_URL.into(),
+			value: account.encode_to_vec(),
+		}),
+	}
+	.encode_to_vec())
+}
@@ -23,6 +23,7 @@ extern crate alloc;
 
 pub mod basic;
 pub mod fee;
+pub mod grpc_query;
 pub mod msg;
 pub mod sigverify;
 
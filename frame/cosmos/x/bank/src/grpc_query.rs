@@ -0,0 +1,131 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::{string::String, vec, vec::Vec};
+use cosmos_sdk_proto::{
+	cosmos::{
+		bank::v1beta1::{
+			DenomUnit, Metadata, QueryAllBalancesRequest, QueryAllBalancesResponse,
+			QueryDenomMetadataRequest, QueryDenomMetadataResponse,
+		},
+		base::{query::v1beta1::PageResponse, v1beta1::Coin},
+	},
+	traits::Message,
+};
+use frame_support::traits::{
+	fungibles::{metadata::Inspect as _, Inspect as _},
+	Get,
+};
+use pallet_cosmos::AddressMapping;
+use pallet_cosmos_types::address::acc_address_from_bech32;
+use sp_core::H160;
+use sp_runtime::traits::Convert;
+
+pub const QUERY_ALL_BALANCES_PATH: &str = "/cosmos.bank.v1beta1.Query/AllBalances";
+pub const QUERY_DENOM_METADATA_PATH: &str = "/cosmos.bank.v1beta1.Query/DenomMetadata";
+
+/// Answer `/cosmos.bank.v1beta1.Query/AllBalances` with the non-zero balances of the account.
+///
+/// Every balance is returned in a single page, as the number of denoms is bounded by the
+/// registered assets.
+pub fn all_balances<T: pallet_cosmos::Config>(data: &[u8]) -> Result<Vec<u8>, String> {
+	let who = all_balances_request::<T>(data)?;
+
+	let balances = pallet_cosmos::Pallet::<T>::all_balances(&who)
+		.into_iter()
+		.map(|coin| Coin { denom: coin.denom, amount: coin.amount })
+		.collect();
+
+	Ok(all_balances_response(balances))
+}
+
+/// Decode a `/cosmos.bank.v1beta1.Query/AllBalances` request into the queried account.
+pub fn all_balances_request<T: pallet_cosmos::Config>(data: &[u8]) -> Result<T::AccountId, String> {
+	let QueryAllBalancesRequest { address, .. } =
+		QueryAllBalancesRequest::decode(data).map_err(|_| String::from("invalid request"))?;
+
+	let (_hrp, address_raw) =
+		acc_address_from_bech32(&address).map_err(|_| String::from("invalid address"))?;
+	if address_raw.len() != 20 {
+		return Err("invalid address".into());
+	}
+
+	Ok(T::AddressMapping::into_account_id(H160::from_slice(&address_raw)))
+}
+
+/// Encode the `/cosmos.bank.v1beta1.Query/AllBalances` response, in a single page.
+pub fn all_balances_response(balances: Vec<Coin>) -> Vec<u8> {
+	let total = balances.len() as u64;
+
+	QueryAllBalancesResponse {
+		balances,
+		pagination: Some(PageResponse { next_key: vec![], total }),
+	}
+	.encode_to_vec()
+}
+
+/// Answer `/cosmos.bank.v1beta1.Query/DenomMetadata` from the asset metadata.
+///
+/// The display unit is the asset symbol, scaled by the asset decimals. The native denom has no
+/// metadata and is only described by its base unit.
+pub fn denom_metadata<T: pallet_cosmos::Config>(data: &[u8]) -> Result<Vec<u8>, String> {
+	let QueryDenomMetadataRequest { denom } =
+		QueryDenomMetadataRequest::decode(data).map_err(|_| String::from("invalid request"))?;
+
+	let asset_id =
+		T::AssetToDenom::convert(denom.clone()).map_err(|_| String::from("unknown denom"))?;
+
+	let base_unit = DenomUnit { denom: denom.clone(), exponent: 0, aliases: vec![] };
+	let metadata = if asset_id == T::NativeAssetId::get() {
+		Metadata {
+			denom_units: vec![base_unit],
+			base: denom.clone(),
+			display: denom,
+			..Default::default()
+		}
+	} else {
+		if !T::Assets::asset_exists(asset_id.clone()) {
+			return Err("unknown denom".into());
+		}
+
+		let name = String::from_utf8_lossy(&T::Assets::name(asset_id.clone())).into_owned();
+		let symbol = String::from_utf8_lossy(&T::Assets::symbol(asset_id.clone())).into_owned();
+		let decimals = T::Assets::decimals(asset_id);
+
+		let (denom_units, display) = if decimals > 0 && !symbol.is_empty() {
+			let display_unit =
+				DenomUnit { denom: symbol.clone(), exponent: decimals.into(), aliases: vec![] };
+			(vec![base_unit, display_unit], symbol.clone())
+		} else {
+			(vec![base_unit], denom.clone())
+		};
+
+		Metadata {
+			description: String::new(),
+			denom_units,
+			base: denom,
+			display,
+			name,
+			symbol,
+			uri: String::new(),
+			uri_hash: String::new(),
+		}
+	};
+
+	Ok(QueryDenomMetadataResponse { metadata: Some(metadata) }.encode_to_vec())
+}
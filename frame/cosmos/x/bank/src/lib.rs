@@ -21,4 +21,5 @@
 
 extern crate alloc;
 
+pub mod grpc_query;
 pub mod msgs;
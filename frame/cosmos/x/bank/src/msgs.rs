@@ -37,7 +37,7 @@ use pallet_cosmos_types::{
 	gas::traits::GasMeter,
 };
 use pallet_cosmos_x_bank_types::events::{ATTRIBUTE_KEY_RECIPIENT, EVENT_TYPE_TRANSFER};
-use sp_core::H160;
+use sp_core::{crypto::UncheckedFrom, H160, H256};
 use sp_runtime::{traits::Convert, SaturatedConversion};
 
 pub struct MsgSendHandler<T>(PhantomData<T>);
@@ -51,25 +51,15 @@ impl<T> Default for MsgSendHandler<T> {
 impl<T, Context> pallet_cosmos_types::msgservice::MsgHandler<Context> for MsgSendHandler<T>
 where
 	T: pallet_cosmos::Config,
+	T::AccountId: UncheckedFrom<H256>,
 	Context: context::traits::Context,
 {
 	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
 		let MsgSend { from_address, to_address, amount } =
 			MsgSend::decode(&mut &*msg.value).map_err(|_| RootError::UnpackAnyError)?;
 
-		let (_hrp, from_address_raw) =
-			acc_address_from_bech32(&from_address).map_err(|_| RootError::InvalidAddress)?;
-		let (_hrp, to_address_raw) =
-			acc_address_from_bech32(&to_address).map_err(|_| RootError::InvalidAddress)?;
-
-		if from_address_raw.len() != 20 {
-			return Err(RootError::InvalidAddress.into());
-		}
-		if to_address_raw.len() != 20 {
-			return Err(RootError::InvalidAddress.into());
-		}
-		let from_account = T::AddressMapping::into_account_id(H160::from_slice(&from_address_raw));
-		let to_account = T::AddressMapping::into_account_id(H160::from_slice(&to_address_raw));
+		let from_account = into_account_id::<T>(&from_address)?;
+		let to_account = into_account_id::<T>(&to_address)?;
 
 		ctx.gas_meter()
 			.consume_gas(T::DbWeight::get().reads(2).ref_time(), "")
@@ -134,3 +124,18 @@ where
 		Ok(())
 	}
 }
+
+/// Maps an account (20 bytes) or contract (32 bytes) address to its account id.
+fn into_account_id<T>(address: &str) -> Result<T::AccountId, CosmosError>
+where
+	T: pallet_cosmos::Config,
+	T::AccountId: UncheckedFrom<H256>,
+{
+	let (_hrp, address_raw) =
+		acc_address_from_bech32(address).map_err(|_| RootError::InvalidAddress)?;
+	match address_raw.len() {
+		20 => Ok(T::AddressMapping::into_account_id(H160::from_slice(&address_raw))),
+		32 => Ok(T::AccountId::unchecked_from(H256::from_slice(&address_raw))),
+		_ => Err(RootError::InvalidAddress.into()),
+	}
+}
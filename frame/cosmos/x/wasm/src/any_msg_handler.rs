@@ -0,0 +1,97 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::{format, string::String};
+use core::marker::PhantomData;
+use cosmos_sdk_proto::Any;
+use cosmwasm_std::{Binary, Event};
+use frame_support::traits::Contains;
+use pallet_cosmos_types::{
+	context::traits::Context,
+	errors::RootError,
+	events::{traits::EventManager, CosmosEvent, EventAttribute},
+	gas::traits::GasMeter,
+	msgservice::MsgServiceRouter,
+};
+use pallet_cosmos_x_auth_signing::sign_verifiable_tx::traits::SigVerifiableTx;
+use pallet_cosmwasm::runtimes::vm::{CosmwasmVM, CosmwasmVMError};
+
+const WASM_TYPE_URL_PREFIX: &str = "/cosmwasm.wasm.";
+
+/// Dispatches the `Any` messages emitted by contracts through the Cosmos message service router.
+///
+/// Messages must pass [`pallet_cosmos::Config::MsgFilter`] and be signed by the emitting contract
+/// only. They are executed with the gas left to the contract, and the consumed gas and emitted
+/// events are reported back to the VM. Wasm messages are rejected, as contracts must use `WasmMsg`
+/// to call each other within the VM's call depth limit.
+pub struct AnyMsgHandler<T>(PhantomData<T>);
+
+impl<T> pallet_cosmwasm::any_msg_handler::AnyMsgHandler<T> for AnyMsgHandler<T>
+where
+	T: pallet_cosmos::Config + pallet_cosmwasm::Config,
+{
+	fn handle<'a>(
+		vm: &mut CosmwasmVM<'a, T>,
+		type_url: &str,
+		value: &[u8],
+		event_handler: &mut dyn FnMut(Event),
+	) -> Result<Option<Binary>, CosmwasmVMError<T>> {
+		// A wasm message would run in a fresh VM, escaping the caller's call depth limit.
+		if type_url.starts_with(WASM_TYPE_URL_PREFIX) {
+			return Err(CosmwasmVMError::Unsupported);
+		}
+
+		let msg = Any { type_url: type_url.into(), value: value.into() };
+		if !T::MsgFilter::contains(&msg) {
+			return Err(CosmwasmVMError::Unsupported);
+		}
+
+		let contract = String::from(vm.contract_address.clone());
+		let signers = T::SigVerifiableTx::get_msg_signers(&msg)
+			.map_err(|e| CosmwasmVMError::Stargate(format!("{:?}", e)))?;
+		if signers.is_empty() || signers.iter().any(|signer| *signer != contract) {
+			return Err(pallet_cosmwasm::Error::<T>::NotAuthorized.into());
+		}
+
+		let handler = T::MsgServiceRouter::route(&msg).ok_or(CosmwasmVMError::Unsupported)?;
+
+		let mut ctx = T::Context::new(vm.shared.gas.remaining());
+		let outcome = handler.handle(&msg, &mut ctx);
+		vm.charge_raw(ctx.gas_meter().consumed_gas())?;
+		outcome.map_err(|e| {
+			if e == RootError::OutOfGas.into() {
+				CosmwasmVMError::OutOfGas
+			} else {
+				CosmwasmVMError::Stargate(format!("{:?}", e))
+			}
+		})?;
+
+		for CosmosEvent { r#type, attributes } in ctx.event_manager().events() {
+			event_handler(Event::new(String::from_utf8_lossy(&r#type)).add_attributes(
+				attributes.into_iter().map(|EventAttribute { key, value }| {
+					(
+						String::from_utf8_lossy(&key).into_owned(),
+						String::from_utf8_lossy(&value).into_owned(),
+					)
+				}),
+			));
+		}
+
+		Ok(None)
+	}
+}
@@ -0,0 +1,132 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::string::String;
+use core::marker::PhantomData;
+use cosmwasm_std::{Binary, ContractResult, Event, SystemResult};
+use frame_support::traits::{
+	fungibles::{
+		self,
+		metadata::{Inspect as _, Mutate as _},
+		Inspect as _,
+	},
+	Get,
+};
+use pallet_assets::WeightInfo as _;
+use pallet_cosmwasm::runtimes::vm::{CosmwasmVM, CosmwasmVMError};
+use serde::{Deserialize, Serialize};
+use sp_runtime::traits::Convert;
+
+/// Custom queries available to the contracts.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HorizonQuery {
+	/// Metadata of the asset behind `denom`, answered with [`AssetMetadataResponse`].
+	AssetMetadata { denom: String },
+}
+
+/// Custom messages available to the contracts.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HorizonMsg {
+	/// Set the metadata of the asset behind `denom`, which must be owned by the contract.
+	SetAssetMetadata { denom: String, name: String, symbol: String, decimals: u8 },
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct AssetMetadataResponse {
+	pub name: String,
+	pub symbol: String,
+	pub decimals: u8,
+}
+
+pub struct CustomHandler<T>(PhantomData<T>);
+impl<T> pallet_cosmwasm::custom_handler::CustomHandler<T> for CustomHandler<T>
+where
+	T: pallet_cosmos::Config + pallet_cosmwasm::Config,
+	<T as pallet_cosmos::Config>::Assets: fungibles::metadata::Mutate<T::AccountId>,
+{
+	type Query = HorizonQuery;
+	type Message = HorizonMsg;
+
+	fn query<'a>(
+		vm: &mut CosmwasmVM<'a, T>,
+		query: Self::Query,
+	) -> Result<SystemResult<ContractResult<Binary>>, CosmwasmVMError<T>> {
+		match query {
+			HorizonQuery::AssetMetadata { denom } => {
+				vm.charge_raw(T::DbWeight::get().reads(2).ref_time())?;
+				Ok(SystemResult::Ok(asset_metadata::<T>(denom)?.into()))
+			},
+		}
+	}
+
+	fn message<'a>(
+		vm: &mut CosmwasmVM<'a, T>,
+		message: Self::Message,
+		event_handler: &mut dyn FnMut(Event),
+	) -> Result<Option<Binary>, CosmwasmVMError<T>> {
+		match message {
+			HorizonMsg::SetAssetMetadata { denom, name, symbol, decimals } => {
+				vm.charge_raw(
+					pallet_assets::weights::SubstrateWeight::<T>::set_metadata(
+						name.len() as u32,
+						symbol.len() as u32,
+					)
+					.ref_time(),
+				)?;
+
+				let asset_id = <T as pallet_cosmos::Config>::AssetToDenom::convert(denom.clone())
+					.map_err(|_| CosmwasmVMError::AssetConversion)?;
+				<T as pallet_cosmos::Config>::Assets::set(
+					asset_id,
+					vm.contract_address.as_ref(),
+					name.into_bytes(),
+					symbol.into_bytes(),
+					decimals,
+				)
+				.map_err(CosmwasmVMError::SubstrateDispatch)?;
+
+				event_handler(Event::new("set_asset_metadata").add_attribute("denom", denom));
+				Ok(None)
+			},
+		}
+	}
+}
+
+fn asset_metadata<T>(denom: String) -> Result<Result<Binary, String>, CosmwasmVMError<T>>
+where
+	T: pallet_cosmos::Config + pallet_cosmwasm::Config,
+{
+	type Assets<T> = <T as pallet_cosmos::Config>::Assets;
+
+	let asset_id = match <T as pallet_cosmos::Config>::AssetToDenom::convert(denom) {
+		Ok(asset_id) if Assets::<T>::asset_exists(asset_id.clone()) => asset_id,
+		_ => return Ok(Err("unknown denom".into())),
+	};
+
+	let response = AssetMetadataResponse {
+		name: String::from_utf8_lossy(&Assets::<T>::name(asset_id.clone())).into_owned(),
+		symbol: String::from_utf8_lossy(&Assets::<T>::symbol(asset_id.clone())).into_owned(),
+		decimals: Assets::<T>::decimals(asset_id),
+	};
+
+	serde_json::to_vec(&response)
+		.map(|response| Ok(Binary::new(response)))
+		.map_err(|_| CosmwasmVMError::QuerySerialize)
+}
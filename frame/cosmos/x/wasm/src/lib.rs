@@ -21,4 +21,7 @@
 
 extern crate alloc;
 
+pub mod any_msg_handler;
+pub mod custom_handler;
 pub mod msgs;
+pub mod query;
@@ -16,18 +16,24 @@
 // You should have received a copy of the GNU General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use alloc::{string::ToString, vec, vec::Vec};
+use alloc::{
+	string::{String, ToString},
+	vec,
+	vec::Vec,
+};
 use core::{marker::PhantomData, str::FromStr};
 use core2::io::Read;
 use cosmos_sdk_proto::{
 	cosmos::base::v1beta1::Coin,
 	cosmwasm::wasm::v1::{
-		MsgExecuteContract, MsgInstantiateContract2, MsgMigrateContract, MsgStoreCode,
-		MsgUpdateAdmin,
+		AccessConfig, AccessType, MsgClearAdmin, MsgExecuteContract, MsgInstantiateContract,
+		MsgInstantiateContract2, MsgMigrateContract, MsgPinCodes, MsgStoreAndInstantiateContract,
+		MsgStoreCode, MsgSudoContract, MsgUnpinCodes, MsgUpdateAdmin, MsgUpdateInstantiateConfig,
 	},
 	prost::Message,
 	Any,
 };
+use frame_support::traits::Get;
 use hp_crypto::EcdsaExt;
 use libflate::gzip::Decoder;
 use pallet_cosmos::AddressMapping;
@@ -36,24 +42,29 @@ use pallet_cosmos_types::{
 	context,
 	errors::{CosmosError, RootError},
 	events::{traits::EventManager, CosmosEvent, EventAttribute},
-	gas::traits::GasMeter,
+	gas::{traits::GasMeter, Gas},
 	msgservice::MsgHandler,
 };
 use pallet_cosmos_x_wasm_types::{
 	errors::WasmError,
 	events::{
-		ATTRIBUTE_KEY_CHECKSUM, ATTRIBUTE_KEY_CODE_ID, ATTRIBUTE_KEY_CONTRACT_ADDR,
-		ATTRIBUTE_KEY_NEW_ADMIN, EVENT_TYPE_EXECUTE, EVENT_TYPE_INSTANTIATE, EVENT_TYPE_MIGRATE,
-		EVENT_TYPE_STORE_CODE, EVENT_TYPE_UPDATE_CONTRACT_ADMIN,
+		ATTRIBUTE_KEY_CHECKSUM, ATTRIBUTE_KEY_CODE_ID, ATTRIBUTE_KEY_CODE_PERMISSION,
+		ATTRIBUTE_KEY_CONTRACT_ADDR, ATTRIBUTE_KEY_NEW_ADMIN, EVENT_TYPE_EXECUTE,
+		EVENT_TYPE_INSTANTIATE, EVENT_TYPE_MIGRATE, EVENT_TYPE_PIN_CODE, EVENT_TYPE_REMOVE_CODE,
+		EVENT_TYPE_STORE_CODE, EVENT_TYPE_SUDO, EVENT_TYPE_UNPIN_CODE,
+		EVENT_TYPE_UPDATE_CODE_ACCESS_CONFIG, EVENT_TYPE_UPDATE_CONTRACT_ADMIN,
 	},
+	proto::MsgRemoveCode,
 };
 use pallet_cosmwasm::{
-	runtimes::vm::InitialStorageMutability,
+	runtimes::vm::{CosmwasmVMError, CosmwasmVMShared, InitialStorageMutability},
 	types::{
-		CodeIdentifier, ContractCodeOf, ContractLabelOf, ContractMessageOf, ContractSaltOf, FundsOf,
+		AccessConfig as CodeAccessConfig, AccessConfigOf, CodeIdentifier, ContractCodeOf,
+		ContractLabelOf, ContractMessageOf, ContractSaltOf, FundsOf,
 	},
+	weights::WeightInfo as _,
 };
-use sp_core::H160;
+use sp_core::{sha2_256, H160};
 use sp_runtime::{traits::Convert, SaturatedConversion};
 
 pub struct MsgStoreCodeHandler<T>(PhantomData<T>);
@@ -71,7 +82,7 @@ where
 {
 	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
 		// TODO: Apply actual weights
-		let MsgStoreCode { sender, wasm_byte_code, instantiate_permission: _ } =
+		let MsgStoreCode { sender, wasm_byte_code, instantiate_permission } =
 			MsgStoreCode::decode(&mut &*msg.value).map_err(|_| RootError::TxDecodeError)?;
 
 		let (_hrp, address_raw) =
@@ -80,15 +91,15 @@ where
 			return Err(RootError::InvalidAddress.into());
 		}
 		let who = T::AddressMapping::into_account_id(H160::from_slice(&address_raw));
-		let mut decoder = Decoder::new(&wasm_byte_code[..]).map_err(|_| WasmError::CreateFailed)?;
-		let mut decoded_code = Vec::new();
-		decoder.read_to_end(&mut decoded_code).map_err(|_| WasmError::CreateFailed)?;
-
-		let code: ContractCodeOf<T> =
-			decoded_code.try_into().map_err(|_| WasmError::CreateFailed)?;
+		let code = decode_wasm_byte_code::<T>(&wasm_byte_code)?;
+		let instantiate_permission = match instantiate_permission {
+			Some(permission) => convert_access_config::<T>(permission)?,
+			None => CodeAccessConfig::Everybody,
+		};
 
-		let (code_hash, code_id) = pallet_cosmwasm::Pallet::<T>::do_upload(&who, code)
-			.map_err(|_| WasmError::CreateFailed)?;
+		let (code_hash, code_id) =
+			pallet_cosmwasm::Pallet::<T>::do_upload(&who, code, instantiate_permission)
+				.map_err(convert_upload_error)?;
 
 		// TODO: Same events emitted pallet_cosmos and pallet_cosmwasm
 		let msg_event = CosmosEvent {
@@ -111,6 +122,85 @@ where
 	}
 }
 
+pub struct MsgInstantiateContractHandler<T>(PhantomData<T>);
+
+impl<T> Default for MsgInstantiateContractHandler<T> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T, Context> MsgHandler<Context> for MsgInstantiateContractHandler<T>
+where
+	T: pallet_cosmos::Config + pallet_cosmwasm::Config,
+	T::AccountId: EcdsaExt,
+	Context: context::traits::Context,
+{
+	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
+		let MsgInstantiateContract { sender, admin, code_id, label, msg, funds } =
+			MsgInstantiateContract::decode(&mut &*msg.value)
+				.map_err(|_| RootError::TxDecodeError)?;
+
+		if sender.is_empty() {
+			return Err(WasmError::Empty.into());
+		}
+		let (_hrp, address_raw) =
+			acc_address_from_bech32(&sender).map_err(|_| RootError::InvalidAddress)?;
+		if address_raw.len() != 20 {
+			return Err(RootError::InvalidAddress.into());
+		}
+		let who = T::AddressMapping::into_account_id(H160::from_slice(&address_raw));
+		let gas = ctx.gas_meter().gas_remaining();
+		let mut shared = pallet_cosmwasm::Pallet::<T>::do_create_vm_shared(
+			gas,
+			InitialStorageMutability::ReadWrite,
+		);
+		let code_identifier = CodeIdentifier::CodeId(code_id);
+
+		let admin_account = if !admin.is_empty() {
+			let admin_account =
+				T::AccountToAddr::convert(admin).map_err(|_| RootError::InvalidAddress)?;
+			Some(admin_account)
+		} else {
+			None
+		};
+
+		let label: ContractLabelOf<T> =
+			label.as_bytes().to_vec().try_into().map_err(|_| RootError::TxDecodeError)?;
+		let funds = convert_funds::<T>(&funds)?;
+		let message: ContractMessageOf<T> = msg.try_into().map_err(|_| RootError::TxDecodeError)?;
+
+		let outcome = pallet_cosmwasm::Pallet::<T>::do_instantiate(
+			&mut shared,
+			who,
+			code_identifier,
+			None,
+			admin_account,
+			label,
+			funds,
+			message,
+		);
+		let contract = consume_vm_gas(ctx, gas, &shared, outcome, WasmError::InstantiateFailed)?;
+		let contract = T::AccountToAddr::convert(contract);
+
+		// TODO: Same events emitted pallet_cosmos and pallet_cosmwasm
+		let msg_event = CosmosEvent {
+			r#type: EVENT_TYPE_INSTANTIATE.into(),
+			attributes: vec![
+				EventAttribute { key: ATTRIBUTE_KEY_CONTRACT_ADDR.into(), value: contract.into() },
+				EventAttribute {
+					key: ATTRIBUTE_KEY_CODE_ID.into(),
+					value: code_id.to_string().into(),
+				},
+			],
+		};
+
+		ctx.event_manager().emit_event(msg_event);
+
+		Ok(())
+	}
+}
+
 pub struct MsgInstantiateContract2Handler<T>(PhantomData<T>);
 
 impl<T> Default for MsgInstantiateContract2Handler<T> {
@@ -125,7 +215,6 @@ where
 	T::AccountId: EcdsaExt,
 	Context: context::traits::Context,
 {
-	// TODO: Consume gas
 	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
 		// TODO: Ignore fix_msg
 		let MsgInstantiateContract2 { sender, admin, code_id, label, msg, funds, salt, fix_msg: _ } =
@@ -162,17 +251,17 @@ where
 		let funds = convert_funds::<T>(&funds)?;
 		let message: ContractMessageOf<T> = msg.try_into().map_err(|_| RootError::TxDecodeError)?;
 
-		let contract = pallet_cosmwasm::Pallet::<T>::do_instantiate(
+		let outcome = pallet_cosmwasm::Pallet::<T>::do_instantiate(
 			&mut shared,
 			who,
 			code_identifier,
-			salt,
+			Some(salt),
 			admin_account,
 			label,
 			funds,
 			message,
-		)
-		.map_err(|_| WasmError::InstantiateFailed)?;
+		);
+		let contract = consume_vm_gas(ctx, gas, &shared, outcome, WasmError::InstantiateFailed)?;
 		let contract = T::AccountToAddr::convert(contract);
 
 		// TODO: Same events emitted pallet_cosmos and pallet_cosmwasm
@@ -193,6 +282,159 @@ where
 	}
 }
 
+/// Handler of `MsgStoreAndInstantiateContract`, restricted to the governance `Authority`.
+///
+/// The code is pinned unless `unpin_code` is set, as in wasmd.
+pub struct MsgStoreAndInstantiateContractHandler<T, Authority>(PhantomData<(T, Authority)>);
+
+impl<T, Authority> Default for MsgStoreAndInstantiateContractHandler<T, Authority> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T, Authority, Context> MsgHandler<Context>
+	for MsgStoreAndInstantiateContractHandler<T, Authority>
+where
+	T: pallet_cosmos::Config + pallet_cosmwasm::Config,
+	T::AccountId: EcdsaExt,
+	Authority: Get<String>,
+	Context: context::traits::Context,
+{
+	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
+		let MsgStoreAndInstantiateContract {
+			authority,
+			wasm_byte_code,
+			instantiate_permission,
+			unpin_code,
+			admin,
+			label,
+			msg,
+			funds,
+			code_hash: expected_code_hash,
+			..
+		} = MsgStoreAndInstantiateContract::decode(&mut &*msg.value)
+			.map_err(|_| RootError::TxDecodeError)?;
+
+		if authority.is_empty() {
+			return Err(WasmError::Empty.into());
+		}
+		if authority != Authority::get() {
+			return Err(RootError::Unauthorized.into());
+		}
+		let (_hrp, address_raw) =
+			acc_address_from_bech32(&authority).map_err(|_| RootError::InvalidAddress)?;
+		if address_raw.len() != 20 {
+			return Err(RootError::InvalidAddress.into());
+		}
+		let who = T::AddressMapping::into_account_id(H160::from_slice(&address_raw));
+		let code = decode_wasm_byte_code::<T>(&wasm_byte_code)?;
+		if !expected_code_hash.is_empty() && expected_code_hash != sha2_256(&code) {
+			return Err(WasmError::Invalid.into());
+		}
+		let instantiate_permission =
+			instantiate_permission.map(convert_access_config::<T>).transpose()?;
+
+		let weight =
+			<T as pallet_cosmwasm::Config>::WeightInfo::upload(code.len().saturated_into());
+		ctx.gas_meter()
+			.consume_gas(weight.ref_time(), "")
+			.map_err(|_| RootError::OutOfGas)?;
+
+		// The authority instantiates the code regardless of the permission, which only applies
+		// once the contract is created.
+		let (code_hash, code_id) =
+			pallet_cosmwasm::Pallet::<T>::do_upload(&who, code, CodeAccessConfig::Everybody)
+				.map_err(convert_upload_error)?;
+
+		if !unpin_code {
+			let weight = <T as pallet_cosmwasm::Config>::WeightInfo::pin_codes(1);
+			ctx.gas_meter()
+				.consume_gas(weight.ref_time(), "")
+				.map_err(|_| RootError::OutOfGas)?;
+			pallet_cosmwasm::Pallet::<T>::do_pin_code(code_id).map_err(|_| WasmError::NotFound)?;
+		}
+
+		let gas = ctx.gas_meter().gas_remaining();
+		let mut shared = pallet_cosmwasm::Pallet::<T>::do_create_vm_shared(
+			gas,
+			InitialStorageMutability::ReadWrite,
+		);
+
+		let admin_account = if !admin.is_empty() {
+			let admin_account =
+				T::AccountToAddr::convert(admin).map_err(|_| RootError::InvalidAddress)?;
+			Some(admin_account)
+		} else {
+			None
+		};
+
+		let label: ContractLabelOf<T> =
+			label.as_bytes().to_vec().try_into().map_err(|_| RootError::TxDecodeError)?;
+		let funds = convert_funds::<T>(&funds)?;
+		let message: ContractMessageOf<T> = msg.try_into().map_err(|_| RootError::TxDecodeError)?;
+
+		let outcome = pallet_cosmwasm::Pallet::<T>::do_instantiate(
+			&mut shared,
+			who,
+			CodeIdentifier::CodeId(code_id),
+			None,
+			admin_account,
+			label,
+			funds,
+			message,
+		);
+		let contract = consume_vm_gas(ctx, gas, &shared, outcome, WasmError::InstantiateFailed)?;
+		let contract = T::AccountToAddr::convert(contract);
+		if let Some(instantiate_permission) = instantiate_permission {
+			pallet_cosmwasm::Pallet::<T>::do_update_instantiate_config(
+				code_id,
+				instantiate_permission,
+			)
+			.map_err(|_| WasmError::NotFound)?;
+		}
+
+		let store_code_event = CosmosEvent {
+			r#type: EVENT_TYPE_STORE_CODE.into(),
+			attributes: vec![
+				EventAttribute {
+					key: ATTRIBUTE_KEY_CODE_ID.into(),
+					value: code_id.to_string().into(),
+				},
+				EventAttribute {
+					key: ATTRIBUTE_KEY_CHECKSUM.into(),
+					value: hex::encode(code_hash.0).into(),
+				},
+			],
+		};
+		let instantiate_event = CosmosEvent {
+			r#type: EVENT_TYPE_INSTANTIATE.into(),
+			attributes: vec![
+				EventAttribute { key: ATTRIBUTE_KEY_CONTRACT_ADDR.into(), value: contract.into() },
+				EventAttribute {
+					key: ATTRIBUTE_KEY_CODE_ID.into(),
+					value: code_id.to_string().into(),
+				},
+			],
+		};
+
+		let mut events = vec![store_code_event];
+		if !unpin_code {
+			events.push(CosmosEvent {
+				r#type: EVENT_TYPE_PIN_CODE.into(),
+				attributes: vec![EventAttribute {
+					key: ATTRIBUTE_KEY_CODE_ID.into(),
+					value: code_id.to_string().into(),
+				}],
+			});
+		}
+		events.push(instantiate_event);
+		ctx.event_manager().emit_events(events);
+
+		Ok(())
+	}
+}
+
 pub struct MsgExecuteContractHandler<T>(PhantomData<T>);
 
 impl<T> Default for MsgExecuteContractHandler<T> {
@@ -207,7 +449,6 @@ where
 	T::AccountId: EcdsaExt,
 	Context: context::traits::Context,
 {
-	// TODO: Consume gas
 	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
 		let MsgExecuteContract { sender, contract, msg, funds } =
 			MsgExecuteContract::decode(&mut &*msg.value).map_err(|_| RootError::TxDecodeError)?;
@@ -232,14 +473,14 @@ where
 		let funds: FundsOf<T> = convert_funds::<T>(&funds)?;
 		let message: ContractMessageOf<T> = msg.try_into().map_err(|_| RootError::TxDecodeError)?;
 
-		pallet_cosmwasm::Pallet::<T>::do_execute(
+		let outcome = pallet_cosmwasm::Pallet::<T>::do_execute(
 			&mut shared,
 			who,
 			contract_account,
 			funds,
 			message,
-		)
-		.map_err(|_| WasmError::ExecuteFailed)?;
+		);
+		consume_vm_gas(ctx, gas, &shared, outcome, WasmError::ExecuteFailed)?;
 
 		// TODO: Same events emitted pallet_cosmos and pallet_cosmwasm
 		let msg_event = CosmosEvent {
@@ -293,14 +534,14 @@ where
 		let new_code_identifier = CodeIdentifier::CodeId(code_id);
 		let message: ContractMessageOf<T> = msg.try_into().map_err(|_| RootError::TxDecodeError)?;
 
-		pallet_cosmwasm::Pallet::<T>::do_migrate(
+		let outcome = pallet_cosmwasm::Pallet::<T>::do_migrate(
 			&mut shared,
 			who,
 			contract_account,
 			new_code_identifier,
 			message,
-		)
-		.map_err(|_| WasmError::MigrationFailed)?;
+		);
+		consume_vm_gas(ctx, gas, &shared, outcome, WasmError::MigrationFailed)?;
 
 		// TODO: Same events emitted pallet_cosmos and pallet_cosmwasm
 		let msg_event = CosmosEvent {
@@ -363,13 +604,17 @@ where
 		let contract_account =
 			T::AccountToAddr::convert(contract.clone()).map_err(|_| RootError::TxDecodeError)?;
 
-		pallet_cosmwasm::Pallet::<T>::do_update_admin(
+		let outcome = pallet_cosmwasm::Pallet::<T>::do_update_admin(
 			&mut shared,
 			who,
 			contract_account,
 			new_admin_account,
-		)
-		.map_err(|_| WasmError::MigrationFailed)?;
+		);
+		let error = outcome
+			.as_ref()
+			.err()
+			.map_or(WasmError::MigrationFailed.into(), convert_admin_error::<T>);
+		consume_vm_gas(ctx, gas, &shared, outcome, error)?;
 
 		// TODO: Same events emitted pallet_cosmos and pallet_cosmwasm
 		let msg_event = CosmosEvent {
@@ -386,6 +631,439 @@ where
 	}
 }
 
+pub struct MsgClearAdminHandler<T>(PhantomData<T>);
+
+impl<T> Default for MsgClearAdminHandler<T> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T, Context> MsgHandler<Context> for MsgClearAdminHandler<T>
+where
+	T: pallet_cosmos::Config + pallet_cosmwasm::Config,
+	Context: context::traits::Context,
+{
+	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
+		let MsgClearAdmin { sender, contract } =
+			MsgClearAdmin::decode(&mut &*msg.value).map_err(|_| RootError::TxDecodeError)?;
+
+		if sender.is_empty() {
+			return Err(WasmError::Empty.into());
+		}
+		let (_hrp, address_raw) =
+			acc_address_from_bech32(&sender).map_err(|_| RootError::InvalidAddress)?;
+		if address_raw.len() != 20 {
+			return Err(RootError::InvalidAddress.into());
+		}
+		let who = T::AddressMapping::into_account_id(H160::from_slice(&address_raw));
+		let gas = ctx.gas_meter().gas_remaining();
+		let mut shared = pallet_cosmwasm::Pallet::<T>::do_create_vm_shared(
+			gas,
+			InitialStorageMutability::ReadWrite,
+		);
+
+		let contract_account =
+			T::AccountToAddr::convert(contract.clone()).map_err(|_| RootError::TxDecodeError)?;
+
+		let outcome =
+			pallet_cosmwasm::Pallet::<T>::do_update_admin(&mut shared, who, contract_account, None);
+		let error = outcome
+			.as_ref()
+			.err()
+			.map_or(WasmError::MigrationFailed.into(), convert_admin_error::<T>);
+		consume_vm_gas(ctx, gas, &shared, outcome, error)?;
+
+		let msg_event = CosmosEvent {
+			r#type: EVENT_TYPE_UPDATE_CONTRACT_ADMIN.into(),
+			attributes: vec![
+				EventAttribute { key: ATTRIBUTE_KEY_CONTRACT_ADDR.into(), value: contract.into() },
+				EventAttribute { key: ATTRIBUTE_KEY_NEW_ADMIN.into(), value: vec![] },
+			],
+		};
+
+		ctx.event_manager().emit_event(msg_event);
+
+		Ok(())
+	}
+}
+
+/// Handler of `MsgUpdateInstantiateConfig`, replacing the instantiate permission of a code.
+///
+/// Either the creator of the code or `Authority`, the bech32 address of the governance account,
+/// may submit the message.
+pub struct MsgUpdateInstantiateConfigHandler<T, Authority>(PhantomData<(T, Authority)>);
+
+impl<T, Authority> Default for MsgUpdateInstantiateConfigHandler<T, Authority> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T, Authority, Context> MsgHandler<Context> for MsgUpdateInstantiateConfigHandler<T, Authority>
+where
+	T: pallet_cosmos::Config + pallet_cosmwasm::Config,
+	Authority: Get<String>,
+	Context: context::traits::Context,
+{
+	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
+		let MsgUpdateInstantiateConfig { sender, code_id, new_instantiate_permission } =
+			MsgUpdateInstantiateConfig::decode(&mut &*msg.value)
+				.map_err(|_| RootError::TxDecodeError)?;
+
+		if sender.is_empty() {
+			return Err(WasmError::Empty.into());
+		}
+		let who = if sender == Authority::get() {
+			None
+		} else {
+			let (_hrp, address_raw) =
+				acc_address_from_bech32(&sender).map_err(|_| RootError::InvalidAddress)?;
+			if address_raw.len() != 20 {
+				return Err(RootError::InvalidAddress.into());
+			}
+			Some(T::AddressMapping::into_account_id(H160::from_slice(&address_raw)))
+		};
+		let permission =
+			convert_access_config::<T>(new_instantiate_permission.ok_or(WasmError::Empty)?)?;
+
+		// The code info is read, then written back with the new permission.
+		let weight = T::DbWeight::get().reads_writes(1, 1);
+		ctx.gas_meter()
+			.consume_gas(weight.ref_time(), "")
+			.map_err(|_| RootError::OutOfGas)?;
+
+		let code_info =
+			pallet_cosmwasm::Pallet::<T>::code_info(code_id).map_err(|_| WasmError::NotFound)?;
+		if who.is_some_and(|who| code_info.creator != who) {
+			return Err(RootError::Unauthorized.into());
+		}
+		let permission_name = permission.permission();
+		pallet_cosmwasm::Pallet::<T>::do_update_instantiate_config(code_id, permission)
+			.map_err(|_| WasmError::NotFound)?;
+
+		let msg_event = CosmosEvent {
+			r#type: EVENT_TYPE_UPDATE_CODE_ACCESS_CONFIG.into(),
+			attributes: vec![
+				EventAttribute {
+					key: ATTRIBUTE_KEY_CODE_PERMISSION.into(),
+					value: permission_name.into(),
+				},
+				EventAttribute {
+					key: ATTRIBUTE_KEY_CODE_ID.into(),
+					value: code_id.to_string().into(),
+				},
+			],
+		};
+
+		ctx.event_manager().emit_event(msg_event);
+
+		Ok(())
+	}
+}
+
+/// Handler of `MsgSudoContract`, calling the `sudo` entrypoint of a contract.
+///
+/// Only `Authority`, the bech32 address of the governance account, may submit the message.
+pub struct MsgSudoContractHandler<T, Authority>(PhantomData<(T, Authority)>);
+
+impl<T, Authority> Default for MsgSudoContractHandler<T, Authority> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T, Authority, Context> MsgHandler<Context> for MsgSudoContractHandler<T, Authority>
+where
+	T: pallet_cosmos::Config + pallet_cosmwasm::Config,
+	Authority: Get<String>,
+	Context: context::traits::Context,
+{
+	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
+		let MsgSudoContract { authority, contract, msg } =
+			MsgSudoContract::decode(&mut &*msg.value).map_err(|_| RootError::TxDecodeError)?;
+
+		if authority.is_empty() {
+			return Err(WasmError::Empty.into());
+		}
+		if authority != Authority::get() {
+			return Err(RootError::Unauthorized.into());
+		}
+		let gas = ctx.gas_meter().gas_remaining();
+		let mut shared = pallet_cosmwasm::Pallet::<T>::do_create_vm_shared(
+			gas,
+			InitialStorageMutability::ReadWrite,
+		);
+
+		let contract_account =
+			T::AccountToAddr::convert(contract.clone()).map_err(|_| RootError::TxDecodeError)?;
+		let message: ContractMessageOf<T> = msg.try_into().map_err(|_| RootError::TxDecodeError)?;
+
+		let outcome = pallet_cosmwasm::Pallet::<T>::do_sudo(&mut shared, contract_account, message);
+		consume_vm_gas(ctx, gas, &shared, outcome, WasmError::ExecuteFailed)?;
+
+		let msg_event = CosmosEvent {
+			r#type: EVENT_TYPE_SUDO.into(),
+			attributes: vec![EventAttribute {
+				key: ATTRIBUTE_KEY_CONTRACT_ADDR.into(),
+				value: contract.into(),
+			}],
+		};
+
+		ctx.event_manager().emit_event(msg_event);
+
+		Ok(())
+	}
+}
+
+/// Handler of `MsgPinCodes`, keeping the modules of codes from being evicted from the cache.
+///
+/// Only `Authority`, the bech32 address of the governance account, may submit the message.
+pub struct MsgPinCodesHandler<T, Authority>(PhantomData<(T, Authority)>);
+
+impl<T, Authority> Default for MsgPinCodesHandler<T, Authority> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T, Authority, Context> MsgHandler<Context> for MsgPinCodesHandler<T, Authority>
+where
+	T: pallet_cosmos::Config + pallet_cosmwasm::Config,
+	Authority: Get<String>,
+	Context: context::traits::Context,
+{
+	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
+		let MsgPinCodes { authority, code_ids } =
+			MsgPinCodes::decode(&mut &*msg.value).map_err(|_| RootError::TxDecodeError)?;
+
+		if authority.is_empty() {
+			return Err(WasmError::Empty.into());
+		}
+		if authority != Authority::get() {
+			return Err(RootError::Unauthorized.into());
+		}
+		let weight =
+			<T as pallet_cosmwasm::Config>::WeightInfo::pin_codes(code_ids.len().saturated_into());
+		ctx.gas_meter()
+			.consume_gas(weight.ref_time(), "")
+			.map_err(|_| RootError::OutOfGas)?;
+
+		for code_id in code_ids {
+			pallet_cosmwasm::Pallet::<T>::do_pin_code(code_id).map_err(|_| WasmError::NotFound)?;
+
+			let msg_event = CosmosEvent {
+				r#type: EVENT_TYPE_PIN_CODE.into(),
+				attributes: vec![EventAttribute {
+					key: ATTRIBUTE_KEY_CODE_ID.into(),
+					value: code_id.to_string().into(),
+				}],
+			};
+			ctx.event_manager().emit_event(msg_event);
+		}
+
+		Ok(())
+	}
+}
+
+/// Handler of `MsgUnpinCodes`, letting the modules of codes be evicted from the cache.
+///
+/// Only `Authority`, the bech32 address of the governance account, may submit the message.
+pub struct MsgUnpinCodesHandler<T, Authority>(PhantomData<(T, Authority)>);
+
+impl<T, Authority> Default for MsgUnpinCodesHandler<T, Authority> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T, Authority, Context> MsgHandler<Context> for MsgUnpinCodesHandler<T, Authority>
+where
+	T: pallet_cosmos::Config + pallet_cosmwasm::Config,
+	Authority: Get<String>,
+	Context: context::traits::Context,
+{
+	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
+		let MsgUnpinCodes { authority, code_ids } =
+			MsgUnpinCodes::decode(&mut &*msg.value).map_err(|_| RootError::TxDecodeError)?;
+
+		if authority.is_empty() {
+			return Err(WasmError::Empty.into());
+		}
+		if authority != Authority::get() {
+			return Err(RootError::Unauthorized.into());
+		}
+		let weight = <T as pallet_cosmwasm::Config>::WeightInfo::unpin_codes(
+			code_ids.len().saturated_into(),
+		);
+		ctx.gas_meter()
+			.consume_gas(weight.ref_time(), "")
+			.map_err(|_| RootError::OutOfGas)?;
+
+		for code_id in code_ids {
+			pallet_cosmwasm::Pallet::<T>::do_unpin_code(code_id)
+				.map_err(|_| WasmError::NotFound)?;
+
+			let msg_event = CosmosEvent {
+				r#type: EVENT_TYPE_UNPIN_CODE.into(),
+				attributes: vec![EventAttribute {
+					key: ATTRIBUTE_KEY_CODE_ID.into(),
+					value: code_id.to_string().into(),
+				}],
+			};
+			ctx.event_manager().emit_event(msg_event);
+		}
+
+		Ok(())
+	}
+}
+
+/// Handler of `MsgRemoveCode`, removing a code no contract runs and releasing its deposit.
+///
+/// Either the creator of the code or `Authority`, the bech32 address of the governance account,
+/// may submit the message.
+pub struct MsgRemoveCodeHandler<T, Authority>(PhantomData<(T, Authority)>);
+
+impl<T, Authority> Default for MsgRemoveCodeHandler<T, Authority> {
+	fn default() -> Self {
+		Self(Default::default())
+	}
+}
+
+impl<T, Authority, Context> MsgHandler<Context> for MsgRemoveCodeHandler<T, Authority>
+where
+	T: pallet_cosmos::Config + pallet_cosmwasm::Config,
+	Authority: Get<String>,
+	Context: context::traits::Context,
+{
+	fn handle(&self, msg: &Any, ctx: &mut Context) -> Result<(), CosmosError> {
+		let MsgRemoveCode { sender, code_id } =
+			MsgRemoveCode::decode(&mut &*msg.value).map_err(|_| RootError::TxDecodeError)?;
+
+		if sender.is_empty() {
+			return Err(WasmError::Empty.into());
+		}
+		let who = if sender == Authority::get() {
+			None
+		} else {
+			let (_hrp, address_raw) =
+				acc_address_from_bech32(&sender).map_err(|_| RootError::InvalidAddress)?;
+			if address_raw.len() != 20 {
+				return Err(RootError::InvalidAddress.into());
+			}
+			Some(T::AddressMapping::into_account_id(H160::from_slice(&address_raw)))
+		};
+		let weight = <T as pallet_cosmwasm::Config>::WeightInfo::remove_code();
+		ctx.gas_meter()
+			.consume_gas(weight.ref_time(), "")
+			.map_err(|_| RootError::OutOfGas)?;
+
+		pallet_cosmwasm::Pallet::<T>::do_remove_code(who.as_ref(), code_id).map_err(
+			|e| match e {
+				pallet_cosmwasm::Error::<T>::NotAuthorized =>
+					CosmosError::from(RootError::Unauthorized),
+				pallet_cosmwasm::Error::<T>::CodeInUse => WasmError::Invalid.into(),
+				_ => WasmError::NotFound.into(),
+			},
+		)?;
+
+		let msg_event = CosmosEvent {
+			r#type: EVENT_TYPE_REMOVE_CODE.into(),
+			attributes: vec![EventAttribute {
+				key: ATTRIBUTE_KEY_CODE_ID.into(),
+				value: code_id.to_string().into(),
+			}],
+		};
+		ctx.event_manager().emit_event(msg_event);
+
+		Ok(())
+	}
+}
+
+/// Consume the gas used by the CosmWasm VM from the Cosmos gas meter and map the VM outcome.
+///
+/// The VM is seeded with `gas_limit`, so the difference with the remaining VM gas is what the
+/// contract execution actually used. Gas is consumed whether the execution succeeded or not.
+fn consume_vm_gas<T, Context, R>(
+	ctx: &mut Context,
+	gas_limit: Gas,
+	shared: &CosmwasmVMShared,
+	outcome: Result<R, CosmwasmVMError<T>>,
+	error: impl Into<CosmosError>,
+) -> Result<R, CosmosError>
+where
+	T: pallet_cosmwasm::Config,
+	Context: context::traits::Context,
+{
+	let gas_remaining = shared.gas.remaining();
+	let gas_used = gas_limit.saturating_sub(gas_remaining);
+	ctx.gas_meter().consume_gas(gas_used, "").map_err(|_| RootError::OutOfGas)?;
+
+	outcome.map_err(|e| match e {
+		CosmwasmVMError::OutOfGas => RootError::OutOfGas.into(),
+		_ if gas_remaining == 0 => RootError::OutOfGas.into(),
+		_ => error.into(),
+	})
+}
+
+/// Map a failed admin update as in wasmd, a missing contract being not found and a sender other
+/// than the admin unauthorized.
+fn convert_admin_error<T: pallet_cosmwasm::Config>(e: &CosmwasmVMError<T>) -> CosmosError {
+	match e {
+		CosmwasmVMError::Pallet(pallet_cosmwasm::Error::<T>::NotAuthorized) =>
+			RootError::Unauthorized.into(),
+		CosmwasmVMError::Pallet(pallet_cosmwasm::Error::<T>::ContractNotFound) =>
+			WasmError::NotFound.into(),
+		_ => WasmError::MigrationFailed.into(),
+	}
+}
+
+/// Decompress the gzipped wasm byte code of a store code message.
+fn decode_wasm_byte_code<T: pallet_cosmwasm::Config>(
+	wasm_byte_code: &[u8],
+) -> Result<ContractCodeOf<T>, CosmosError> {
+	let mut decoder = Decoder::new(wasm_byte_code).map_err(|_| WasmError::CreateFailed)?;
+	let mut decoded_code = Vec::new();
+	decoder.read_to_end(&mut decoded_code).map_err(|_| WasmError::CreateFailed)?;
+
+	decoded_code.try_into().map_err(|_| WasmError::CreateFailed.into())
+}
+
+/// Map a failed upload, denied uploads being reported as unauthorized as in wasmd.
+fn convert_upload_error<T: pallet_cosmwasm::Config>(e: pallet_cosmwasm::Error<T>) -> CosmosError {
+	match e {
+		pallet_cosmwasm::Error::<T>::NotAuthorized => RootError::Unauthorized.into(),
+		_ => WasmError::CreateFailed.into(),
+	}
+}
+
+/// Convert an `AccessConfig` message into the permission stored along a code.
+fn convert_access_config<T: pallet_cosmwasm::Config>(
+	config: AccessConfig,
+) -> Result<AccessConfigOf<T>, CosmosError> {
+	match config.permission {
+		permission if permission == AccessType::Nobody as i32 => Ok(CodeAccessConfig::Nobody),
+		permission if permission == AccessType::Everybody as i32 => Ok(CodeAccessConfig::Everybody),
+		permission if permission == AccessType::AnyOfAddresses as i32 => {
+			if config.addresses.is_empty() {
+				return Err(WasmError::Empty.into());
+			}
+			let addresses = config
+				.addresses
+				.into_iter()
+				.map(|address| {
+					T::AccountToAddr::convert(address).map_err(|_| RootError::InvalidAddress.into())
+				})
+				.collect::<Result<Vec<_>, CosmosError>>()?;
+
+			Ok(CodeAccessConfig::AnyOfAddresses(
+				addresses.try_into().map_err(|_| WasmError::Invalid)?,
+			))
+		},
+		_ => Err(WasmError::Invalid.into()),
+	}
+}
+
 fn convert_funds<T: pallet_cosmwasm::Config>(coins: &[Coin]) -> Result<FundsOf<T>, CosmosError> {
 	// TODO: Handle native asset
 	let mut funds = FundsOf::<T>::default();
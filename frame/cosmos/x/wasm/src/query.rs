@@ -0,0 +1,317 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! wasmd compatible `cosmwasm.wasm.v1.Query` service.
+//!
+//! Requests and responses are protobuf encoded, as carried by the ABCI queries.
+
+use alloc::{string::String, vec, vec::Vec};
+use cosmos_sdk_proto::{
+	cosmos::base::query::v1beta1::{PageRequest, PageResponse},
+	cosmwasm::wasm::v1::{
+		AbsoluteTxPosition, AccessConfig, AccessType, CodeInfoResponse, ContractCodeHistoryEntry,
+		ContractCodeHistoryOperationType, ContractInfo, Model, Params,
+		QueryAllContractStateRequest, QueryAllContractStateResponse, QueryCodeRequest,
+		QueryCodeResponse, QueryCodesRequest, QueryCodesResponse, QueryContractHistoryRequest,
+		QueryContractHistoryResponse, QueryContractInfoRequest, QueryContractInfoResponse,
+		QueryContractsByCodeRequest, QueryContractsByCodeResponse, QueryParamsRequest,
+		QueryParamsResponse, QueryPinnedCodesRequest, QueryPinnedCodesResponse,
+		QueryRawContractStateRequest, QueryRawContractStateResponse,
+	},
+	prost::Message,
+};
+use pallet_cosmos_types::errors::{CosmosError, RootError};
+use pallet_cosmos_x_wasm_types::errors::WasmError;
+use pallet_cosmwasm::types::{
+	AccessConfig as CodeAccessConfig, AccessConfigOf, AccountIdOf, CodeInfoOf,
+	ContractCodeHistoryEntryOf, ContractCodeHistoryOperation,
+};
+use parity_scale_codec::{Decode, Encode};
+use sp_runtime::traits::Convert;
+
+/// Page size used when the request does not set a limit, as in the Cosmos SDK.
+const DEFAULT_PAGE_LIMIT: u64 = 100;
+
+/// Query the metadata of a contract.
+pub fn contract_info<T: pallet_cosmwasm::Config>(request: &[u8]) -> Result<Vec<u8>, CosmosError> {
+	let QueryContractInfoRequest { address } = decode_request(request)?;
+	let contract = contract_address::<T>(&address)?;
+	let info =
+		pallet_cosmwasm::Pallet::<T>::contract_info(&contract).map_err(|_| WasmError::NotFound)?;
+	let code_info =
+		pallet_cosmwasm::Pallet::<T>::code_info(info.code_id).map_err(|_| WasmError::NotFound)?;
+	let created = pallet_cosmwasm::Pallet::<T>::contract_history(&contract, None)
+		.map_err(|_| WasmError::NotFound)?
+		.next()
+		.map(|(_, entry)| tx_position(entry.updated));
+	let ibc_port_id = if code_info.ibc_capable {
+		pallet_cosmwasm::Pallet::<T>::do_compute_ibc_contract_port(contract)
+	} else {
+		String::new()
+	};
+
+	Ok(QueryContractInfoResponse {
+		address,
+		contract_info: Some(ContractInfo {
+			code_id: info.code_id,
+			creator: T::AccountToAddr::convert(info.instantiator),
+			admin: info.admin.map(T::AccountToAddr::convert).unwrap_or_default(),
+			label: String::from_utf8_lossy(&info.label).into(),
+			created,
+			ibc_port_id,
+			extension: None,
+		}),
+	}
+	.encode_to_vec())
+}
+
+/// Query the code history of a contract, oldest entry first.
+pub fn contract_history<T: pallet_cosmwasm::Config>(
+	request: &[u8],
+) -> Result<Vec<u8>, CosmosError> {
+	let QueryContractHistoryRequest { address, pagination } = decode_request(request)?;
+	let contract = contract_address::<T>(&address)?;
+	let page = pagination.unwrap_or_default();
+	let start_after = match page.key.as_slice() {
+		[] => None,
+		key => Some(decode_position(key)?.try_into().map_err(|_| RootError::InvalidRequest)?),
+	};
+	let history = pallet_cosmwasm::Pallet::<T>::contract_history(&contract, start_after)
+		.map_err(|_| WasmError::NotFound)?;
+
+	let (entries, pagination) = paginate(
+		history.map(|(seq, entry)| {
+			((seq as u64).to_be_bytes().to_vec(), code_history_entry::<T>(entry))
+		}),
+		&page,
+	)?;
+
+	Ok(QueryContractHistoryResponse { entries, pagination }.encode_to_vec())
+}
+
+/// Query the contracts instantiated from a code, or migrated to it.
+pub fn contracts_by_code<T: pallet_cosmwasm::Config>(
+	request: &[u8],
+) -> Result<Vec<u8>, CosmosError> {
+	let QueryContractsByCodeRequest { code_id, pagination } = decode_request(request)?;
+	let page = pagination.unwrap_or_default();
+	let start_after = match page.key.as_slice() {
+		[] => None,
+		mut key => Some(AccountIdOf::<T>::decode(&mut key).map_err(|_| RootError::InvalidRequest)?),
+	};
+
+	let (contracts, pagination) = paginate(
+		pallet_cosmwasm::Pallet::<T>::contracts_by_code(code_id, start_after)
+			.map(|contract| (contract.encode(), T::AccountToAddr::convert(contract))),
+		&page,
+	)?;
+
+	Ok(QueryContractsByCodeResponse { contracts, pagination }.encode_to_vec())
+}
+
+/// Query every (key, value) pair of a contract storage.
+pub fn all_contract_state<T: pallet_cosmwasm::Config>(
+	request: &[u8],
+) -> Result<Vec<u8>, CosmosError> {
+	let QueryAllContractStateRequest { address, pagination } = decode_request(request)?;
+	let contract = contract_address::<T>(&address)?;
+	let page = pagination.unwrap_or_default();
+	let start_after = (!page.key.is_empty()).then(|| page.key.clone());
+
+	let state = pallet_cosmwasm::Pallet::<T>::contract_state(&contract, start_after)
+		.map_err(|_| WasmError::NotFound)?;
+	let (models, pagination) =
+		paginate(state.map(|(key, value)| (key.clone(), Model { key, value })), &page)?;
+
+	Ok(QueryAllContractStateResponse { models, pagination }.encode_to_vec())
+}
+
+/// Query a single entry of a contract storage.
+pub fn raw_contract_state<T: pallet_cosmwasm::Config>(
+	request: &[u8],
+) -> Result<Vec<u8>, CosmosError> {
+	let QueryRawContractStateRequest { address, query_data } = decode_request(request)?;
+	let contract = contract_address::<T>(&address)?;
+	let data = pallet_cosmwasm::Pallet::<T>::contract_raw_state(&contract, &query_data)
+		.map_err(|_| WasmError::NotFound)?
+		.unwrap_or_default();
+
+	Ok(QueryRawContractStateResponse { data }.encode_to_vec())
+}
+
+/// Query the metadata and the original byte code of a code.
+pub fn code<T: pallet_cosmwasm::Config>(request: &[u8]) -> Result<Vec<u8>, CosmosError> {
+	let QueryCodeRequest { code_id } = decode_request(request)?;
+	let info = pallet_cosmwasm::Pallet::<T>::code_info(code_id).map_err(|_| WasmError::NotFound)?;
+	let data =
+		pallet_cosmwasm::Pallet::<T>::pristine_code(code_id).map_err(|_| WasmError::NotFound)?;
+
+	Ok(QueryCodeResponse {
+		code_info: Some(code_info_response::<T>(code_id, info)),
+		data: data.into_inner(),
+	}
+	.encode_to_vec())
+}
+
+/// Query the metadata of the stored codes.
+pub fn codes<T: pallet_cosmwasm::Config>(request: &[u8]) -> Result<Vec<u8>, CosmosError> {
+	let QueryCodesRequest { pagination } = decode_request(request)?;
+	let page = pagination.unwrap_or_default();
+	let start_after = match page.key.as_slice() {
+		[] => None,
+		key => Some(decode_position(key)?),
+	};
+
+	let (code_infos, pagination) = paginate(
+		pallet_cosmwasm::Pallet::<T>::codes(start_after).map(|(code_id, info)| {
+			(code_id.to_be_bytes().to_vec(), code_info_response::<T>(code_id, info))
+		}),
+		&page,
+	)?;
+
+	Ok(QueryCodesResponse { code_infos, pagination }.encode_to_vec())
+}
+
+/// Query the pinned codes.
+pub fn pinned_codes<T: pallet_cosmwasm::Config>(request: &[u8]) -> Result<Vec<u8>, CosmosError> {
+	let QueryPinnedCodesRequest { pagination } = decode_request(request)?;
+	let page = pagination.unwrap_or_default();
+	let start_after = match page.key.as_slice() {
+		[] => None,
+		key => Some(decode_position(key)?),
+	};
+
+	let (code_ids, pagination) = paginate(
+		pallet_cosmwasm::Pallet::<T>::pinned_codes(start_after)
+			.map(|code_id| (code_id.to_be_bytes().to_vec(), code_id)),
+		&page,
+	)?;
+
+	Ok(QueryPinnedCodesResponse { code_ids, pagination }.encode_to_vec())
+}
+
+/// Query the parameters of the module.
+pub fn params<T: pallet_cosmwasm::Config>(request: &[u8]) -> Result<Vec<u8>, CosmosError> {
+	let QueryParamsRequest {} = decode_request(request)?;
+
+	Ok(QueryParamsResponse {
+		params: Some(Params {
+			code_upload_access: Some(access_config::<T>(
+				pallet_cosmwasm::Pallet::<T>::code_upload_access(),
+			)),
+			// Codes are uploaded with everybody allowed to instantiate them by default.
+			instantiate_default_permission: AccessType::Everybody.into(),
+		}),
+	}
+	.encode_to_vec())
+}
+
+fn decode_request<M: Message + Default>(request: &[u8]) -> Result<M, CosmosError> {
+	M::decode(request).map_err(|_| RootError::InvalidRequest.into())
+}
+
+fn decode_position(key: &[u8]) -> Result<u64, CosmosError> {
+	let key = key.try_into().map_err(|_| RootError::InvalidRequest)?;
+	Ok(u64::from_be_bytes(key))
+}
+
+/// The transaction index is not tracked, so that only the block height is reported.
+fn tx_position(block_height: u64) -> AbsoluteTxPosition {
+	AbsoluteTxPosition { block_height, tx_index: 0 }
+}
+
+fn code_history_entry<T: pallet_cosmwasm::Config>(
+	entry: ContractCodeHistoryEntryOf<T>,
+) -> ContractCodeHistoryEntry {
+	let operation = match entry.operation {
+		ContractCodeHistoryOperation::Init => ContractCodeHistoryOperationType::Init,
+		ContractCodeHistoryOperation::Migrate => ContractCodeHistoryOperationType::Migrate,
+		ContractCodeHistoryOperation::Genesis => ContractCodeHistoryOperationType::Genesis,
+	};
+	ContractCodeHistoryEntry {
+		operation: operation.into(),
+		code_id: entry.code_id,
+		updated: Some(tx_position(entry.updated)),
+		msg: entry.msg.into_inner(),
+	}
+}
+
+fn contract_address<T: pallet_cosmwasm::Config>(
+	address: &str,
+) -> Result<AccountIdOf<T>, CosmosError> {
+	T::AccountToAddr::convert(String::from(address)).map_err(|_| RootError::InvalidAddress.into())
+}
+
+fn access_config<T: pallet_cosmwasm::Config>(config: AccessConfigOf<T>) -> AccessConfig {
+	match config {
+		CodeAccessConfig::Nobody =>
+			AccessConfig { permission: AccessType::Nobody.into(), addresses: vec![] },
+		CodeAccessConfig::Everybody =>
+			AccessConfig { permission: AccessType::Everybody.into(), addresses: vec![] },
+		CodeAccessConfig::AnyOfAddresses(addresses) => AccessConfig {
+			permission: AccessType::AnyOfAddresses.into(),
+			addresses: addresses.into_iter().map(T::AccountToAddr::convert).collect(),
+		},
+	}
+}
+
+fn code_info_response<T: pallet_cosmwasm::Config>(
+	code_id: u64,
+	info: CodeInfoOf<T>,
+) -> CodeInfoResponse {
+	CodeInfoResponse {
+		code_id,
+		creator: T::AccountToAddr::convert(info.creator),
+		data_hash: info.pristine_code_hash.to_vec(),
+		instantiate_permission: Some(access_config::<T>(info.instantiate_permission)),
+	}
+}
+
+/// Collect a page out of `items`, each of them yielded along the key it is resumed after.
+///
+/// The returned `next_key` is the key of the last item of the page, and is only set when more
+/// items are available.
+fn paginate<V>(
+	items: impl Iterator<Item = (Vec<u8>, V)>,
+	page: &PageRequest,
+) -> Result<(Vec<V>, Option<PageResponse>), CosmosError> {
+	if page.reverse || (!page.key.is_empty() && page.offset > 0) {
+		return Err(RootError::InvalidRequest.into());
+	}
+	let limit = if page.limit == 0 { DEFAULT_PAGE_LIMIT } else { page.limit };
+	let offset = usize::try_from(page.offset).map_err(|_| RootError::InvalidRequest)?;
+
+	let mut items = items.skip(offset).peekable();
+	let mut values = Vec::new();
+	let mut last_key = Vec::new();
+	while (values.len() as u64) < limit {
+		let Some((key, value)) = items.next() else { break };
+		values.push(value);
+		last_key = key;
+	}
+	let next_key = if items.peek().is_some() { last_key } else { Vec::new() };
+	let total = if page.count_total && page.key.is_empty() {
+		page.offset
+			.saturating_add(values.len() as u64)
+			.saturating_add(items.count() as u64)
+	} else {
+		0
+	};
+
+	Ok((values, Some(PageResponse { next_key, total })))
+}
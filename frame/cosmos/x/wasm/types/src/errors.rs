@@ -24,8 +24,10 @@ pub enum WasmError {
 	CreateFailed = 2,
 	InstantiateFailed = 4,
 	ExecuteFailed = 5,
+	NotFound = 8,
 	MigrationFailed = 11,
 	Empty = 12,
+	Invalid = 14,
 }
 
 impl From<WasmError> for CosmosError {
@@ -20,8 +20,14 @@ pub const EVENT_TYPE_INSTANTIATE: &str = "instantiate";
 pub const EVENT_TYPE_EXECUTE: &str = "execute";
 pub const EVENT_TYPE_MIGRATE: &str = "migrate";
 pub const EVENT_TYPE_UPDATE_CONTRACT_ADMIN: &str = "update_contract_admin";
+pub const EVENT_TYPE_SUDO: &str = "sudo";
+pub const EVENT_TYPE_UPDATE_CODE_ACCESS_CONFIG: &str = "update_code_access_config";
+pub const EVENT_TYPE_PIN_CODE: &str = "pin_code";
+pub const EVENT_TYPE_UNPIN_CODE: &str = "unpin_code";
+pub const EVENT_TYPE_REMOVE_CODE: &str = "remove_code";
 
 pub const ATTRIBUTE_KEY_CONTRACT_ADDR: &str = "_contract_address";
 pub const ATTRIBUTE_KEY_CODE_ID: &str = "code_id";
 pub const ATTRIBUTE_KEY_CHECKSUM: &str = "code_checksum";
 pub const ATTRIBUTE_KEY_NEW_ADMIN: &str = "new_admin_address";
+pub const ATTRIBUTE_KEY_CODE_PERMISSION: &str = "code_permission";
@@ -21,4 +21,5 @@ extern crate alloc;
 
 pub mod errors;
 pub mod events;
+pub mod proto;
 pub mod tx;
@@ -0,0 +1,37 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Messages of the Horizon extensions to the wasm module, which wasmd does not define.
+
+use alloc::string::String;
+use cosmos_sdk_proto::prost::Name;
+
+/// Removes a code no contract runs, releasing the deposit reserved for its upload.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MsgRemoveCode {
+	/// Creator of the code, or the governance authority.
+	#[prost(string, tag = "1")]
+	pub sender: String,
+	/// The code to remove.
+	#[prost(uint64, tag = "2")]
+	pub code_id: u64,
+}
+
+impl Name for MsgRemoveCode {
+	const NAME: &'static str = "MsgRemoveCode";
+	const PACKAGE: &'static str = "horizon.wasm.v1";
+}
@@ -15,8 +15,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+pub mod msg_clear_admin;
 pub mod msg_execute_contract;
+pub mod msg_instantiate_contract;
 pub mod msg_instantiate_contract2;
 pub mod msg_migrate_contract;
+pub mod msg_pin_codes;
+pub mod msg_remove_code;
+pub mod msg_store_and_instantiate_contract;
 pub mod msg_store_code;
+pub mod msg_sudo_contract;
+pub mod msg_unpin_codes;
 pub mod msg_update_admin;
+pub mod msg_update_instantiate_config;
@@ -0,0 +1,58 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloc::{string::String, vec, vec::Vec};
+use cosmos_sdk_proto::{cosmwasm::wasm, prost::Message, Any};
+use pallet_cosmos_types::{coin::Coin, tx_msgs::Msg};
+use pallet_cosmos_x_auth_migrations::legacytx::stdsign::LegacyMsg;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MsgInstantiateContract {
+	pub admin: String,
+	pub code_id: u64,
+	pub funds: Vec<Coin>,
+	pub label: String,
+	pub msg: Vec<u8>,
+	pub sender: String,
+}
+
+impl TryFrom<&Any> for MsgInstantiateContract {
+	type Error = ();
+
+	fn try_from(any: &Any) -> Result<Self, Self::Error> {
+		let msg = wasm::v1::MsgInstantiateContract::decode(&mut &*any.value).map_err(|_| ())?;
+		Ok(Self {
+			admin: msg.admin,
+			code_id: msg.code_id,
+			funds: msg.funds.iter().map(Into::into).collect(),
+			label: msg.label,
+			msg: msg.msg,
+			sender: msg.sender,
+		})
+	}
+}
+
+impl LegacyMsg for MsgInstantiateContract {
+	const AMINO_NAME: &'static str = "wasm/MsgInstantiateContract";
+}
+
+impl Msg for MsgInstantiateContract {
+	fn get_signers(self) -> Vec<String> {
+		vec![self.sender.clone()]
+	}
+}
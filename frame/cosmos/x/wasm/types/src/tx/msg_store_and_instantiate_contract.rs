@@ -0,0 +1,70 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::tx::msg_store_code::AccessConfig;
+use alloc::{string::String, vec, vec::Vec};
+use cosmos_sdk_proto::{cosmwasm::wasm, prost::Message, Any};
+use pallet_cosmos_types::{coin::Coin, tx_msgs::Msg};
+use pallet_cosmos_x_auth_migrations::legacytx::stdsign::LegacyMsg;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MsgStoreAndInstantiateContract {
+	pub admin: String,
+	pub authority: String,
+	pub builder: String,
+	pub code_hash: Vec<u8>,
+	pub funds: Vec<Coin>,
+	pub instantiate_permission: Option<AccessConfig>,
+	pub label: String,
+	pub msg: Vec<u8>,
+	pub source: String,
+	pub unpin_code: bool,
+	pub wasm_byte_code: Vec<u8>,
+}
+
+impl TryFrom<&Any> for MsgStoreAndInstantiateContract {
+	type Error = ();
+
+	fn try_from(any: &Any) -> Result<Self, Self::Error> {
+		let msg =
+			wasm::v1::MsgStoreAndInstantiateContract::decode(&mut &*any.value).map_err(|_| ())?;
+		Ok(Self {
+			admin: msg.admin,
+			authority: msg.authority,
+			builder: msg.builder,
+			code_hash: msg.code_hash,
+			funds: msg.funds.iter().map(Into::into).collect(),
+			instantiate_permission: msg.instantiate_permission.map(Into::into),
+			label: msg.label,
+			msg: msg.msg,
+			source: msg.source,
+			unpin_code: msg.unpin_code,
+			wasm_byte_code: msg.wasm_byte_code,
+		})
+	}
+}
+
+impl LegacyMsg for MsgStoreAndInstantiateContract {
+	const AMINO_NAME: &'static str = "wasm/MsgStoreAndInstantiateContract";
+}
+
+impl Msg for MsgStoreAndInstantiateContract {
+	fn get_signers(self) -> Vec<String> {
+		vec![self.authority.clone()]
+	}
+}
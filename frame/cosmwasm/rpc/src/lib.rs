@@ -10,7 +10,7 @@ use jsonrpsee::{
 	proc_macros::rpc,
 	types::{ErrorObject, ErrorObjectOwned},
 };
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::Bytes;
 use sp_runtime::traits::Block as BlockT;
@@ -28,6 +28,33 @@ mod cosmwasm_api {
 			query_request: Bytes,
 			at: Option<BlockHash>,
 		) -> RpcResult<Bytes>;
+
+		#[method(name = "cosmwasm_contractInfo")]
+		fn contract_info(&self, request: Bytes, at: Option<BlockHash>) -> RpcResult<Bytes>;
+
+		#[method(name = "cosmwasm_contractHistory")]
+		fn contract_history(&self, request: Bytes, at: Option<BlockHash>) -> RpcResult<Bytes>;
+
+		#[method(name = "cosmwasm_contractsByCode")]
+		fn contracts_by_code(&self, request: Bytes, at: Option<BlockHash>) -> RpcResult<Bytes>;
+
+		#[method(name = "cosmwasm_allContractState")]
+		fn all_contract_state(&self, request: Bytes, at: Option<BlockHash>) -> RpcResult<Bytes>;
+
+		#[method(name = "cosmwasm_rawContractState")]
+		fn raw_contract_state(&self, request: Bytes, at: Option<BlockHash>) -> RpcResult<Bytes>;
+
+		#[method(name = "cosmwasm_code")]
+		fn code(&self, request: Bytes, at: Option<BlockHash>) -> RpcResult<Bytes>;
+
+		#[method(name = "cosmwasm_codes")]
+		fn codes(&self, request: Bytes, at: Option<BlockHash>) -> RpcResult<Bytes>;
+
+		#[method(name = "cosmwasm_pinnedCodes")]
+		fn pinned_codes(&self, request: Bytes, at: Option<BlockHash>) -> RpcResult<Bytes>;
+
+		#[method(name = "cosmwasm_params")]
+		fn params(&self, request: Bytes, at: Option<BlockHash>) -> RpcResult<Bytes>;
 	}
 }
 
@@ -50,6 +77,46 @@ fn runtime_error_into_rpc_error<E: ToString>(e: E) -> ErrorObjectOwned {
 	)
 }
 
+fn runtime_result_into_rpc_result<E: ToString, Error: AsRef<[u8]>>(
+	result: Result<Result<Vec<u8>, Error>, E>,
+) -> RpcResult<Bytes> {
+	result
+		.map_err(runtime_error_into_rpc_error)?
+		.map(Bytes::from)
+		.map_err(|e| runtime_error_into_rpc_error(String::from_utf8_lossy(e.as_ref())))
+}
+
+/// Carries the error returned by the runtime in the data of the RPC error, for the client to
+/// recover its codespace and code.
+fn query_result_into_rpc_result<E: ToString, Error: AsRef<[u8]>>(
+	result: Result<Result<Vec<u8>, Error>, E>,
+) -> RpcResult<Bytes> {
+	result.map_err(runtime_error_into_rpc_error)?.map(Bytes::from).map_err(|e| {
+		ErrorObject::owned(9876, "Query failed", Some(Bytes::from(e.as_ref().to_vec())))
+	})
+}
+
+impl<C, Block, Error> Cosmwasm<C, (Block, Error)>
+where
+	Block: BlockT,
+	Error: Codec,
+	C: ProvideRuntimeApi<Block>,
+	C::Api: CosmwasmRuntimeApi<Block, Error>,
+{
+	/// The x/wasm queries are only served by the runtimes implementing version 2 of the API.
+	fn ensure_queries_supported(&self, at: <Block as BlockT>::Hash) -> RpcResult<()> {
+		let version = self
+			.client
+			.runtime_api()
+			.api_version::<dyn CosmwasmRuntimeApi<Block, Error>>(at)
+			.map_err(runtime_error_into_rpc_error)?;
+		if !version.is_some_and(|version| version >= 2) {
+			return Err(runtime_error_into_rpc_error("Query is not supported by the runtime"));
+		}
+		Ok(())
+	}
+}
+
 impl<C, Block, Error> CosmwasmApiServer<<Block as BlockT>::Hash, Error>
 	for Cosmwasm<C, (Block, Error)>
 where
@@ -69,11 +136,93 @@ where
 	) -> RpcResult<Bytes> {
 		let api = self.client.runtime_api();
 		let at = at.unwrap_or_else(|| self.client.info().best_hash);
-		let runtime_api_result = api
-			.query(at, contract, gas, query_request.to_vec())
-			.map_err(runtime_error_into_rpc_error)?;
-		runtime_api_result
-			.map(Bytes::from)
-			.map_err(|e| runtime_error_into_rpc_error(String::from_utf8_lossy(e.as_ref())))
+		runtime_result_into_rpc_result(api.query(at, contract, gas, query_request.to_vec()))
+	}
+
+	fn contract_info(
+		&self,
+		request: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Bytes> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.ensure_queries_supported(at)?;
+		let api = self.client.runtime_api();
+		query_result_into_rpc_result(api.contract_info(at, request.to_vec()))
+	}
+
+	fn contract_history(
+		&self,
+		request: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Bytes> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.ensure_queries_supported(at)?;
+		let api = self.client.runtime_api();
+		query_result_into_rpc_result(api.contract_history(at, request.to_vec()))
+	}
+
+	fn contracts_by_code(
+		&self,
+		request: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Bytes> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.ensure_queries_supported(at)?;
+		let api = self.client.runtime_api();
+		query_result_into_rpc_result(api.contracts_by_code(at, request.to_vec()))
+	}
+
+	fn all_contract_state(
+		&self,
+		request: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Bytes> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.ensure_queries_supported(at)?;
+		let api = self.client.runtime_api();
+		query_result_into_rpc_result(api.all_contract_state(at, request.to_vec()))
+	}
+
+	fn raw_contract_state(
+		&self,
+		request: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Bytes> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.ensure_queries_supported(at)?;
+		let api = self.client.runtime_api();
+		query_result_into_rpc_result(api.raw_contract_state(at, request.to_vec()))
+	}
+
+	fn code(&self, request: Bytes, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Bytes> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.ensure_queries_supported(at)?;
+		let api = self.client.runtime_api();
+		query_result_into_rpc_result(api.code(at, request.to_vec()))
+	}
+
+	fn codes(&self, request: Bytes, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Bytes> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.ensure_queries_supported(at)?;
+		let api = self.client.runtime_api();
+		query_result_into_rpc_result(api.codes(at, request.to_vec()))
+	}
+
+	fn pinned_codes(
+		&self,
+		request: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Bytes> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.ensure_queries_supported(at)?;
+		let api = self.client.runtime_api();
+		query_result_into_rpc_result(api.pinned_codes(at, request.to_vec()))
+	}
+
+	fn params(&self, request: Bytes, at: Option<<Block as BlockT>::Hash>) -> RpcResult<Bytes> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.ensure_queries_supported(at)?;
+		let api = self.client.runtime_api();
+		query_result_into_rpc_result(api.params(at, request.to_vec()))
 	}
 }
@@ -8,7 +8,11 @@ use alloc::{string::String, vec::Vec};
 use codec::Codec;
 
 // Cosmwasm Runtime API declaration.
+//
+// Version 2 adds the `cosmwasm.wasm.v1.Query` methods, whose errors are the SCALE encoded
+// `CosmosError` of the query.
 sp_api::decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait CosmwasmRuntimeApi<Error>
 	where
 		Error: Codec
@@ -18,5 +22,41 @@ sp_api::decl_runtime_apis! {
 			gas: u64,
 			query_request: Vec<u8>,
 		) -> Result<Vec<u8>, Error>;
+
+		/// `cosmwasm.wasm.v1.Query/ContractInfo`, with protobuf encoded request and response.
+		#[api_version(2)]
+		fn contract_info(request: Vec<u8>) -> Result<Vec<u8>, Error>;
+
+		/// `cosmwasm.wasm.v1.Query/ContractHistory`, with protobuf encoded request and response.
+		#[api_version(2)]
+		fn contract_history(request: Vec<u8>) -> Result<Vec<u8>, Error>;
+
+		/// `cosmwasm.wasm.v1.Query/ContractsByCode`, with protobuf encoded request and response.
+		#[api_version(2)]
+		fn contracts_by_code(request: Vec<u8>) -> Result<Vec<u8>, Error>;
+
+		/// `cosmwasm.wasm.v1.Query/AllContractState`, with protobuf encoded request and response.
+		#[api_version(2)]
+		fn all_contract_state(request: Vec<u8>) -> Result<Vec<u8>, Error>;
+
+		/// `cosmwasm.wasm.v1.Query/RawContractState`, with protobuf encoded request and response.
+		#[api_version(2)]
+		fn raw_contract_state(request: Vec<u8>) -> Result<Vec<u8>, Error>;
+
+		/// `cosmwasm.wasm.v1.Query/Code`, with protobuf encoded request and response.
+		#[api_version(2)]
+		fn code(request: Vec<u8>) -> Result<Vec<u8>, Error>;
+
+		/// `cosmwasm.wasm.v1.Query/Codes`, with protobuf encoded request and response.
+		#[api_version(2)]
+		fn codes(request: Vec<u8>) -> Result<Vec<u8>, Error>;
+
+		/// `cosmwasm.wasm.v1.Query/PinnedCodes`, with protobuf encoded request and response.
+		#[api_version(2)]
+		fn pinned_codes(request: Vec<u8>) -> Result<Vec<u8>, Error>;
+
+		/// `cosmwasm.wasm.v1.Query/Params`, with protobuf encoded request and response.
+		#[api_version(2)]
+		fn params(request: Vec<u8>) -> Result<Vec<u8>, Error>;
 	}
 }
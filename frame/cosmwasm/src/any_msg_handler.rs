@@ -0,0 +1,32 @@
+use crate::{
+	runtimes::vm::{CosmwasmVM, CosmwasmVMError},
+	Config,
+};
+use cosmwasm_std::{Binary, Event};
+
+/// A handler for protobuf encoded messages emitted by contracts through `CosmosMsg::Any` (or the
+/// deprecated `CosmosMsg::Stargate`).
+pub trait AnyMsgHandler<T: Config> {
+	/// Dispatch the message on behalf of the contract executing in `vm`.
+	///
+	/// The gas consumed by the message must be charged to `vm` and the emitted events forwarded to
+	/// `event_handler`, so that both flow back into the submessage reply.
+	fn handle<'a>(
+		vm: &mut CosmwasmVM<'a, T>,
+		type_url: &str,
+		value: &[u8],
+		event_handler: &mut dyn FnMut(Event),
+	) -> Result<Option<Binary>, CosmwasmVMError<T>>;
+}
+
+/// Default implementation, rejecting every message.
+impl<T: Config> AnyMsgHandler<T> for () {
+	fn handle<'a>(
+		_vm: &mut CosmwasmVM<'a, T>,
+		_type_url: &str,
+		_value: &[u8],
+		_event_handler: &mut dyn FnMut(Event),
+	) -> Result<Option<Binary>, CosmwasmVMError<T>> {
+		Err(CosmwasmVMError::Unsupported)
+	}
+}
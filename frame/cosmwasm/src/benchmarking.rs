@@ -0,0 +1,416 @@
+//! Benchmarks for cosmwasm.
+//!
+//! Only the functions hand-estimated in [`crate::estimated_weights`] are covered, the others being
+//! already part of the benchmark output in [`crate::weights`].
+
+use super::*;
+use crate::types::{AccessConfig, ContractCodeHistoryOperation};
+use alloc::{vec, vec::Vec};
+use cosmwasm_std::Order;
+use cosmwasm_vm::executor::constants::{MAX_LENGTH_DB_KEY, MAX_LENGTH_DB_VALUE};
+use cosmwasm_vm_wasmi::code_gen::{ModuleDefinition, WasmModule};
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use hex_literal::hex;
+
+const GAS: u64 = 100_000_000_000;
+
+/// Prehashed message signed by the secp256r1 key `[0x42; 32]`.
+const SECP256R1_MESSAGE_HASH: [u8; 32] =
+	hex!("714d4bdfdbd0bd630ebdf93b1f6eba7d3c752e92bbab6c9d3d9c93e1777348bb");
+/// Signature of [`SECP256R1_MESSAGE_HASH`], in its compact `r || s` form.
+const SECP256R1_SIGNATURE: [u8; 64] = hex!("471c3e758c4904285bba7e53118ed0f524adeb0757d25bd2f8e7b0d76dfa714c5e575a8f63a35d944f35551b288196a7529475f8155c906a1a72b7b3adef2858");
+/// Recovery id of [`SECP256R1_SIGNATURE`].
+const SECP256R1_RECOVERY_PARAM: u8 = 1;
+/// Uncompressed public key of the secp256r1 key `[0x42; 32]`.
+const SECP256R1_PUBLIC_KEY: [u8; 65] = hex!("043ad3861a95621392516bb593ef05583ed2e5866f5cb6260a3017237fd89b90afd0961c7e37075a6791a39c61f56295b02b6d26567b615e60aa41ee1c8e83388d");
+
+/// Message hashed to the BLS12-381 points, along with its domain separation tag.
+const BLS12_381_MESSAGE: &[u8] = b"message";
+const BLS12_381_DST: &[u8] = b"dst";
+/// SHA-256, the only hash function supported to hash to BLS12-381 points.
+const BLS12_381_HASH_FUNCTION: u32 = 0;
+
+fn funded_account<T: Config>(name: &'static str) -> AccountIdOf<T> {
+	let account = account(name, 0, 0);
+	T::NativeAsset::make_free_balance_be(&account, u64::MAX.saturated_into());
+	account
+}
+
+/// Upload a code whose size grows with `additional_binary_size`, so that distinct sizes upload
+/// distinct codes.
+fn upload<T: Config>(creator: &AccountIdOf<T>, additional_binary_size: usize) -> CosmwasmCodeId {
+	let module: WasmModule =
+		ModuleDefinition::new(vec![], additional_binary_size, None).unwrap().into();
+	let (_, code_id) =
+		Pallet::<T>::do_upload(creator, module.code.try_into().unwrap(), AccessConfig::Everybody)
+			.unwrap();
+	code_id
+}
+
+fn instantiate_contract<T: Config>(
+	creator: &AccountIdOf<T>,
+	code_id: CosmwasmCodeId,
+) -> AccountIdOf<T> {
+	crate::instantiate::<T>(
+		creator.clone(),
+		code_id,
+		b"salt".to_vec(),
+		None,
+		b"benchmark".to_vec(),
+		BTreeMap::new(),
+		GAS,
+		b"{}".to_vec(),
+	)
+	.unwrap()
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn db_next_key() {
+		let creator = funded_account::<T>("creator");
+		let contract = instantiate_contract::<T>(&creator, upload::<T>(&creator, 0));
+		let mut shared = Pallet::<T>::do_create_vm_shared(GAS, InitialStorageMutability::ReadWrite);
+		let mut vm = Pallet::<T>::cosmwasm_new_vm(&mut shared, creator, contract, vec![]).unwrap();
+		let vm = vm.0.data_mut();
+		Pallet::<T>::do_db_write(vm, b"key", b"value").unwrap();
+		let iterator_id = Pallet::<T>::do_db_scan(vm, None, None, Order::Ascending).unwrap();
+		let key;
+
+		#[block]
+		{
+			key = Pallet::<T>::do_db_next_key(vm, iterator_id).unwrap();
+		}
+
+		assert_eq!(key, Some(b"key".to_vec()));
+	}
+
+	#[benchmark]
+	fn db_next_value() {
+		let creator = funded_account::<T>("creator");
+		let contract = instantiate_contract::<T>(&creator, upload::<T>(&creator, 0));
+		let mut shared = Pallet::<T>::do_create_vm_shared(GAS, InitialStorageMutability::ReadWrite);
+		let mut vm = Pallet::<T>::cosmwasm_new_vm(&mut shared, creator, contract, vec![]).unwrap();
+		let vm = vm.0.data_mut();
+		Pallet::<T>::do_db_write(vm, b"key", b"value").unwrap();
+		let iterator_id = Pallet::<T>::do_db_scan(vm, None, None, Order::Ascending).unwrap();
+		let value;
+
+		#[block]
+		{
+			value = Pallet::<T>::do_db_next_value(vm, iterator_id).unwrap();
+		}
+
+		assert_eq!(value, Some(b"value".to_vec()));
+	}
+
+	#[benchmark]
+	fn db_read_bytes(
+		k: Linear<1, { MAX_LENGTH_DB_KEY as u32 }>,
+		v: Linear<0, { MAX_LENGTH_DB_VALUE as u32 }>,
+	) {
+		let creator = funded_account::<T>("creator");
+		let contract = instantiate_contract::<T>(&creator, upload::<T>(&creator, 0));
+		let mut shared = Pallet::<T>::do_create_vm_shared(GAS, InitialStorageMutability::ReadWrite);
+		let mut vm = Pallet::<T>::cosmwasm_new_vm(&mut shared, creator, contract, vec![]).unwrap();
+		let vm = vm.0.data_mut();
+		let key = vec![1; k as usize];
+		Pallet::<T>::do_db_write(vm, &key, &vec![1; v as usize]).unwrap();
+		let value;
+
+		#[block]
+		{
+			value = Pallet::<T>::do_db_read(vm, &key).unwrap();
+		}
+
+		assert_eq!(value.map(|value| value.len()), Some(v as usize));
+	}
+
+	#[benchmark]
+	fn db_write_bytes(
+		k: Linear<1, { MAX_LENGTH_DB_KEY as u32 }>,
+		v: Linear<0, { MAX_LENGTH_DB_VALUE as u32 }>,
+	) {
+		let creator = funded_account::<T>("creator");
+		let contract = instantiate_contract::<T>(&creator, upload::<T>(&creator, 0));
+		let mut shared = Pallet::<T>::do_create_vm_shared(GAS, InitialStorageMutability::ReadWrite);
+		let mut vm = Pallet::<T>::cosmwasm_new_vm(&mut shared, creator, contract, vec![]).unwrap();
+		let vm = vm.0.data_mut();
+		let (key, value) = (vec![1; k as usize], vec![1; v as usize]);
+
+		#[block]
+		{
+			Pallet::<T>::do_db_write(vm, &key, &value).unwrap();
+		}
+	}
+
+	#[benchmark]
+	fn db_remove_bytes(
+		k: Linear<1, { MAX_LENGTH_DB_KEY as u32 }>,
+		v: Linear<0, { MAX_LENGTH_DB_VALUE as u32 }>,
+	) {
+		let creator = funded_account::<T>("creator");
+		let contract = instantiate_contract::<T>(&creator, upload::<T>(&creator, 0));
+		let mut shared = Pallet::<T>::do_create_vm_shared(GAS, InitialStorageMutability::ReadWrite);
+		let mut vm = Pallet::<T>::cosmwasm_new_vm(&mut shared, creator, contract, vec![]).unwrap();
+		let vm = vm.0.data_mut();
+		let key = vec![1; k as usize];
+		Pallet::<T>::do_db_write(vm, &key, &vec![1; v as usize]).unwrap();
+
+		#[block]
+		{
+			Pallet::<T>::do_db_remove(vm, &key).unwrap();
+		}
+
+		assert_eq!(Pallet::<T>::do_db_read(vm, &key).unwrap(), None);
+	}
+
+	#[benchmark]
+	fn secp256r1_recover_pubkey() {
+		let public_key;
+
+		#[block]
+		{
+			public_key = Pallet::<T>::do_secp256r1_recover_pubkey(
+				&SECP256R1_MESSAGE_HASH,
+				&SECP256R1_SIGNATURE,
+				SECP256R1_RECOVERY_PARAM,
+			);
+		}
+
+		assert_eq!(public_key, Ok(SECP256R1_PUBLIC_KEY.to_vec()));
+	}
+
+	#[benchmark]
+	fn secp256r1_verify() {
+		let verified;
+
+		#[block]
+		{
+			verified = Pallet::<T>::do_secp256r1_verify(
+				&SECP256R1_MESSAGE_HASH,
+				&SECP256R1_SIGNATURE,
+				&SECP256R1_PUBLIC_KEY,
+			);
+		}
+
+		assert!(verified);
+	}
+
+	#[benchmark]
+	fn bls12_381_aggregate_g1(n: Linear<1, 100>) {
+		let g1 = Pallet::<T>::do_bls12_381_hash_to_g1(
+			BLS12_381_HASH_FUNCTION,
+			BLS12_381_MESSAGE,
+			BLS12_381_DST,
+		)
+		.unwrap();
+		let g1s = g1.repeat(n as usize);
+		let aggregated;
+
+		#[block]
+		{
+			aggregated = Pallet::<T>::do_bls12_381_aggregate_g1(&g1s);
+		}
+
+		assert!(aggregated.is_ok());
+	}
+
+	#[benchmark]
+	fn bls12_381_aggregate_g2(n: Linear<1, 100>) {
+		let g2 = Pallet::<T>::do_bls12_381_hash_to_g2(
+			BLS12_381_HASH_FUNCTION,
+			BLS12_381_MESSAGE,
+			BLS12_381_DST,
+		)
+		.unwrap();
+		let g2s = g2.repeat(n as usize);
+		let aggregated;
+
+		#[block]
+		{
+			aggregated = Pallet::<T>::do_bls12_381_aggregate_g2(&g2s);
+		}
+
+		assert!(aggregated.is_ok());
+	}
+
+	#[benchmark]
+	fn bls12_381_pairing_equality(n: Linear<1, 100>) {
+		let g1 = Pallet::<T>::do_bls12_381_hash_to_g1(
+			BLS12_381_HASH_FUNCTION,
+			BLS12_381_MESSAGE,
+			BLS12_381_DST,
+		)
+		.unwrap();
+		let g2 = Pallet::<T>::do_bls12_381_hash_to_g2(
+			BLS12_381_HASH_FUNCTION,
+			BLS12_381_MESSAGE,
+			BLS12_381_DST,
+		)
+		.unwrap();
+		let (ps, qs) = (g1.repeat(n as usize), g2.repeat(n as usize));
+		let equal;
+
+		#[block]
+		{
+			equal = Pallet::<T>::do_bls12_381_pairing_equality(&ps, &qs, &g1, &g2);
+		}
+
+		assert!(equal.is_ok());
+	}
+
+	#[benchmark]
+	fn bls12_381_hash_to_g1() {
+		let point;
+
+		#[block]
+		{
+			point = Pallet::<T>::do_bls12_381_hash_to_g1(
+				BLS12_381_HASH_FUNCTION,
+				BLS12_381_MESSAGE,
+				BLS12_381_DST,
+			);
+		}
+
+		assert!(point.is_ok());
+	}
+
+	#[benchmark]
+	fn bls12_381_hash_to_g2() {
+		let point;
+
+		#[block]
+		{
+			point = Pallet::<T>::do_bls12_381_hash_to_g2(
+				BLS12_381_HASH_FUNCTION,
+				BLS12_381_MESSAGE,
+				BLS12_381_DST,
+			);
+		}
+
+		assert!(point.is_ok());
+	}
+
+	#[benchmark]
+	fn sudo() {
+		let creator = funded_account::<T>("creator");
+		let contract = instantiate_contract::<T>(&creator, upload::<T>(&creator, 0));
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, contract, GAS, b"{}".to_vec().try_into().unwrap());
+	}
+
+	#[benchmark]
+	fn set_code_upload_access() {
+		#[extrinsic_call]
+		_(RawOrigin::Root, AccessConfig::Nobody);
+
+		assert_eq!(CodeUploadAccess::<T>::get(), AccessConfig::Nobody);
+	}
+
+	#[benchmark]
+	fn append_contract_history(n: Linear<0, { T::MaxMessageSize::get() }>) {
+		let creator = funded_account::<T>("creator");
+		let contract = instantiate_contract::<T>(&creator, upload::<T>(&creator, 0));
+		// Fill the history, so that appending drops the oldest entry.
+		for _ in 0..T::MaxCodeHistoryEntries::get() {
+			Pallet::<T>::do_append_contract_history(
+				&contract,
+				ContractCodeHistoryOperation::Migrate,
+				&[],
+			)
+			.unwrap();
+		}
+		let message = vec![0; n as usize];
+
+		#[block]
+		{
+			Pallet::<T>::do_append_contract_history(
+				&contract,
+				ContractCodeHistoryOperation::Migrate,
+				&message,
+			)
+			.unwrap();
+		}
+	}
+
+	#[benchmark]
+	fn pin_codes(n: Linear<1, 100>) {
+		let creator = funded_account::<T>("creator");
+		let code_ids: Vec<_> = (0..n).map(|i| upload::<T>(&creator, i as usize)).collect();
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, code_ids.clone());
+
+		assert!(code_ids.iter().all(|code_id| PinnedCodes::<T>::contains_key(code_id)));
+	}
+
+	#[benchmark]
+	fn unpin_codes(n: Linear<1, 100>) {
+		let creator = funded_account::<T>("creator");
+		let code_ids: Vec<_> = (0..n).map(|i| upload::<T>(&creator, i as usize)).collect();
+		for code_id in &code_ids {
+			Pallet::<T>::do_pin_code(*code_id).unwrap();
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, code_ids.clone());
+
+		assert!(code_ids.iter().all(|code_id| !PinnedCodes::<T>::contains_key(code_id)));
+	}
+
+	#[benchmark]
+	fn load_module(n: Linear<0, { T::MaxCodeSize::get() / 2 }>) {
+		let creator = funded_account::<T>("creator");
+		let code_id = upload::<T>(&creator, n as usize);
+		let mut shared = Pallet::<T>::do_create_vm_shared(GAS, InitialStorageMutability::ReadWrite);
+		module_cache::clear();
+
+		#[block]
+		{
+			Pallet::<T>::get_module_from_cache(&mut shared, code_id).unwrap();
+		}
+	}
+
+	#[benchmark]
+	fn load_pinned_module(n: Linear<0, { T::MaxCodeSize::get() / 2 }>) {
+		let creator = funded_account::<T>("creator");
+		let code_id = upload::<T>(&creator, n as usize);
+		Pallet::<T>::do_pin_code(code_id).unwrap();
+		// Have the node keep the instrumented code.
+		let mut shared = Pallet::<T>::do_create_vm_shared(GAS, InitialStorageMutability::ReadWrite);
+		Pallet::<T>::get_module_from_cache(&mut shared, code_id).unwrap();
+		let mut shared = Pallet::<T>::do_create_vm_shared(GAS, InitialStorageMutability::ReadWrite);
+		module_cache::clear();
+
+		#[block]
+		{
+			Pallet::<T>::get_module_from_cache(&mut shared, code_id).unwrap();
+		}
+	}
+
+	#[benchmark]
+	fn reinstrument(n: Linear<0, { T::MaxCodeSize::get() / 2 }>) {
+		let creator = funded_account::<T>("creator");
+		let code_id = upload::<T>(&creator, n as usize);
+
+		#[extrinsic_call]
+		force_reinstrument(RawOrigin::Root, code_id);
+	}
+
+	#[benchmark]
+	fn remove_code() {
+		let creator = funded_account::<T>("creator");
+		let code_id = upload::<T>(&creator, 0);
+		Pallet::<T>::do_pin_code(code_id).unwrap();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(creator), code_id);
+
+		assert!(!CodeIdToInfo::<T>::contains_key(code_id));
+	}
+}
@@ -2,6 +2,12 @@ use crate::{Config, Pallet, SUBSTRATE_ECDSA_SIGNATURE_LEN};
 use alloc::{vec, vec::Vec};
 
 use sp_core::{ecdsa, ed25519};
+
+/// Length of the message hash expected by the secp256r1 functions.
+const SECP256R1_MESSAGE_HASH_LEN: usize = 32;
+/// Length of the secp256r1 signature in its compact `r || s` form.
+const SECP256R1_SIGNATURE_LEN: usize = 64;
+
 impl<T: Config> Pallet<T> {
 	pub(crate) fn do_secp256k1_recover_pubkey(
 		message_hash: &[u8],
@@ -63,6 +69,72 @@ impl<T: Config> Pallet<T> {
 		sp_io::crypto::ecdsa_verify_prehashed(&signature, &message_hash, &public_key)
 	}
 
+	pub(crate) fn do_secp256r1_recover_pubkey(
+		message_hash: &[u8],
+		signature: &[u8],
+		recovery_param: u8,
+	) -> Result<Vec<u8>, ()> {
+		// `recovery_param` must be 0 or 1. Other values are not supported from CosmWasm.
+		if recovery_param >= 2 {
+			return Err(())
+		}
+
+		if message_hash.len() != SECP256R1_MESSAGE_HASH_LEN ||
+			signature.len() != SECP256R1_SIGNATURE_LEN
+		{
+			return Err(())
+		}
+
+		hp_io::cosmos::secp256r1_ecdsa_recover(signature, message_hash, recovery_param).ok_or(())
+	}
+
+	pub(crate) fn do_secp256r1_verify(
+		message_hash: &[u8],
+		signature: &[u8],
+		public_key: &[u8],
+	) -> bool {
+		if message_hash.len() != SECP256R1_MESSAGE_HASH_LEN ||
+			signature.len() != SECP256R1_SIGNATURE_LEN
+		{
+			return false
+		}
+
+		hp_io::cosmos::secp256r1_ecdsa_verify(signature, message_hash, public_key)
+	}
+
+	pub(crate) fn do_bls12_381_aggregate_g1(g1s: &[u8]) -> Result<Vec<u8>, u32> {
+		hp_io::cosmos::bls12_381_aggregate_g1(g1s)
+	}
+
+	pub(crate) fn do_bls12_381_aggregate_g2(g2s: &[u8]) -> Result<Vec<u8>, u32> {
+		hp_io::cosmos::bls12_381_aggregate_g2(g2s)
+	}
+
+	pub(crate) fn do_bls12_381_pairing_equality(
+		ps: &[u8],
+		qs: &[u8],
+		r: &[u8],
+		s: &[u8],
+	) -> Result<bool, u32> {
+		hp_io::cosmos::bls12_381_pairing_equality(ps, qs, r, s)
+	}
+
+	pub(crate) fn do_bls12_381_hash_to_g1(
+		hash_function: u32,
+		msg: &[u8],
+		dst: &[u8],
+	) -> Result<Vec<u8>, u32> {
+		hp_io::cosmos::bls12_381_hash_to_g1(hash_function, msg, dst)
+	}
+
+	pub(crate) fn do_bls12_381_hash_to_g2(
+		hash_function: u32,
+		msg: &[u8],
+		dst: &[u8],
+	) -> Result<Vec<u8>, u32> {
+		hp_io::cosmos::bls12_381_hash_to_g2(hash_function, msg, dst)
+	}
+
 	pub(crate) fn do_ed25519_batch_verify(
 		messages: &[&[u8]],
 		signatures: &[&[u8]],
@@ -0,0 +1,59 @@
+use crate::{
+	runtimes::vm::{CosmwasmVM, CosmwasmVMError},
+	Config,
+};
+use core::fmt::Debug;
+use cosmwasm_std::{Binary, Empty, Event, SystemResult};
+use cosmwasm_vm::executor::CosmwasmQueryResult;
+use serde::de::DeserializeOwned;
+
+/// Chain specific bindings, answering `QueryRequest::Custom` and dispatching `CosmosMsg::Custom`
+/// emitted by contracts.
+///
+/// Custom payloads that do not deserialize into the runtime bindings abort the contract call.
+pub trait CustomHandler<T: Config> {
+	/// Payload of `QueryRequest::Custom`.
+	type Query: DeserializeOwned + Debug;
+
+	/// Payload of `CosmosMsg::Custom`.
+	type Message: DeserializeOwned + Debug;
+
+	/// Answer the query on behalf of the contract executing in `vm`.
+	///
+	/// The storage reads made to answer the query must be charged to `vm`.
+	fn query<'a>(
+		vm: &mut CosmwasmVM<'a, T>,
+		query: Self::Query,
+	) -> Result<SystemResult<CosmwasmQueryResult>, CosmwasmVMError<T>>;
+
+	/// Dispatch the message on behalf of the contract executing in `vm`.
+	///
+	/// The weight of the message must be charged to `vm` and the emitted events forwarded to
+	/// `event_handler`, so that both flow back into the submessage reply.
+	fn message<'a>(
+		vm: &mut CosmwasmVM<'a, T>,
+		message: Self::Message,
+		event_handler: &mut dyn FnMut(Event),
+	) -> Result<Option<Binary>, CosmwasmVMError<T>>;
+}
+
+/// Default implementation, without any custom binding.
+impl<T: Config> CustomHandler<T> for () {
+	type Query = Empty;
+	type Message = Empty;
+
+	fn query<'a>(
+		_vm: &mut CosmwasmVM<'a, T>,
+		_query: Self::Query,
+	) -> Result<SystemResult<CosmwasmQueryResult>, CosmwasmVMError<T>> {
+		Err(CosmwasmVMError::Unsupported)
+	}
+
+	fn message<'a>(
+		_vm: &mut CosmwasmVM<'a, T>,
+		_message: Self::Message,
+		_event_handler: &mut dyn FnMut(Event),
+	) -> Result<Option<Binary>, CosmwasmVMError<T>> {
+		Err(CosmwasmVMError::Unsupported)
+	}
+}
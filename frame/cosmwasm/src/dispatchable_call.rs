@@ -1,7 +1,11 @@
 use crate::{
 	pallet_hook::PalletHook,
-	runtimes::vm::{ContractBackend, CosmwasmVMError, CosmwasmVMShared},
+	runtimes::{
+		abstraction::GasOutcome,
+		vm::{ContractBackend, CosmwasmVMError, CosmwasmVMShared},
+	},
 	types::*,
+	weights::WeightInfo,
 	Config, Pallet,
 };
 use alloc::vec::Vec;
@@ -49,6 +53,7 @@ impl<I, O, T: Config> DispatchableCall<I, O, T> {
 			From<CosmwasmVMError<T>> + Into<CosmwasmVMError<T>>,
 		I: AsFunctionName,
 	{
+		self.record_code_history(shared, &message)?;
 		Pallet::<T>::top_level_dispatch(
 			shared,
 			self.entrypoint,
@@ -90,6 +95,7 @@ impl<I, O, T: Config> DispatchableCall<I, O, T> {
 		for<'x> VmErrorOf<OwnedWasmiVM<DefaultCosmwasmVM<'x, T>>>:
 			From<CosmwasmVMError<T>> + Into<CosmwasmVMError<T>>,
 	{
+		self.record_code_history(shared, message)?;
 		// Call `cosmwasm_call` to transfer funds and create the vm instance before
 		// calling the callback.
 		Pallet::<T>::sub_level_dispatch(
@@ -113,4 +119,26 @@ impl<I, O, T: Config> DispatchableCall<I, O, T> {
 			},
 		)
 	}
+
+	/// Record the code history entry of the `instantiate` and `migrate` entrypoints.
+	///
+	/// * `shared` - Shared state of the Cosmwasm VM.
+	/// * `message` - Message to be passed to the entrypoint.
+	fn record_code_history(
+		&self,
+		shared: &mut CosmwasmVMShared,
+		message: &[u8],
+	) -> Result<(), CosmwasmVMError<T>> {
+		let operation = match self.entrypoint {
+			EntryPoint::Instantiate => ContractCodeHistoryOperation::Init,
+			EntryPoint::Migrate => ContractCodeHistoryOperation::Migrate,
+			_ => return Ok(()),
+		};
+		let gas = T::WeightInfo::append_contract_history(message.len() as u32).ref_time();
+		if let GasOutcome::Halt = shared.gas.charge(gas) {
+			return Err(CosmwasmVMError::OutOfGas)
+		}
+		Pallet::<T>::do_append_contract_history(&self.contract, operation, message)
+			.map_err(Into::into)
+	}
 }
@@ -2,13 +2,14 @@ use crate::{
 	dispatchable_call::DispatchableCall,
 	runtimes::{abstraction::CosmwasmAccount, vm::CosmwasmVMShared},
 	types::*,
-	CodeIdToInfo, Config, ContractToInfo, CurrentNonce, Error, Event, Pallet,
+	CodeIdToContracts, CodeIdToInfo, Config, ContractToInfo, CurrentInstanceId, CurrentNonce,
+	Error, Event, Pallet,
 };
 
 use composable_support::abstractions::utils::increment::Increment;
 use core::marker::PhantomData;
 use cosmwasm_vm::{
-	executor::{ExecuteCall, InstantiateCall, MigrateCall, ReplyCall},
+	executor::{ExecuteCall, InstantiateCall, MigrateCall, ReplyCall, SudoCall},
 	system::CosmwasmCodeId,
 };
 
@@ -16,17 +17,26 @@ use frame_support::ensure;
 /// Prepares for `instantiate` entrypoint call.
 ///
 /// * `instantiator` - Address of the account that calls this entrypoint.
+/// * `salt` - Salt of the predictable contract address. Without salt, the classic address is
+///   derived from the code id and the next instance id.
 pub(crate) fn setup_instantiate_call<T: Config>(
 	instantiator: AccountIdOf<T>,
 	code_id: CosmwasmCodeId,
-	salt: &[u8],
+	salt: Option<&[u8]>,
 	admin: Option<AccountIdOf<T>>,
 	label: ContractLabelOf<T>,
-) -> Result<DispatchableCall<InstantiateCall, AccountIdOf<T>, T>, Error<T>> {
-	let code_hash = CodeIdToInfo::<T>::get(code_id)
-		.ok_or(Error::<T>::CodeNotFound)?
-		.pristine_code_hash;
-	let contract = Pallet::<T>::derive_contract_address(&instantiator, salt, &code_hash)?;
+) -> Result<DispatchableCall<InstantiateCall<CustomMessageOf<T>>, AccountIdOf<T>, T>, Error<T>> {
+	let code_info = CodeIdToInfo::<T>::get(code_id).ok_or(Error::<T>::CodeNotFound)?;
+	ensure!(code_info.instantiate_permission.allowed(&instantiator), Error::<T>::NotAuthorized);
+	let code_hash = code_info.pristine_code_hash;
+	let contract = match salt {
+		Some(salt) => Pallet::<T>::derive_contract_address(&instantiator, salt, &code_hash)?,
+		None => {
+			let instance_id =
+				CurrentInstanceId::<T>::increment().map_err(|_| Error::<T>::IncrementFailed)?;
+			Pallet::<T>::derive_contract_address_classic(code_id, instance_id)?
+		},
+	};
 	// Make sure that contract address does not already exist
 	ensure!(Pallet::<T>::contract_exists(&contract).is_err(), Error::<T>::ContractAlreadyExists);
 	let nonce = CurrentNonce::<T>::increment().map_err(|_| Error::<T>::NonceOverflow)?;
@@ -34,12 +44,16 @@ pub(crate) fn setup_instantiate_call<T: Config>(
 	let contract_info =
 		ContractInfoOf::<T> { instantiator: instantiator.clone(), code_id, trie_id, admin, label };
 	ContractToInfo::<T>::insert(&contract, &contract_info);
-	CodeIdToInfo::<T>::try_mutate(code_id, |entry| -> Result<(), Error<T>> {
+	CodeIdToContracts::<T>::insert(code_id, &contract, ());
+	let ibc_capable = CodeIdToInfo::<T>::try_mutate(code_id, |entry| -> Result<bool, Error<T>> {
 		let code_info = entry.as_mut().ok_or(Error::<T>::CodeNotFound)?;
 		code_info.refcount =
 			code_info.refcount.checked_add(1).ok_or(Error::<T>::RefcountOverflow)?;
-		Ok(())
+		Ok(code_info.ibc_capable)
 	})?;
+	if ibc_capable {
+		Pallet::<T>::do_bind_ibc_port(&contract)?;
+	}
 	Pallet::<T>::deposit_event(Event::<T>::Instantiated {
 		contract: contract.clone(),
 		info: contract_info,
@@ -60,7 +74,7 @@ pub(crate) fn setup_instantiate_call<T: Config>(
 pub(crate) fn setup_execute_call<T: Config>(
 	executor: AccountIdOf<T>,
 	contract: AccountIdOf<T>,
-) -> Result<DispatchableCall<ExecuteCall, (), T>, Error<T>> {
+) -> Result<DispatchableCall<ExecuteCall<CustomMessageOf<T>>, (), T>, Error<T>> {
 	Ok(DispatchableCall {
 		entrypoint: EntryPoint::Execute,
 		sender: executor,
@@ -77,7 +91,7 @@ pub(crate) fn setup_execute_call<T: Config>(
 pub(crate) fn setup_reply_call<T: Config>(
 	executor: AccountIdOf<T>,
 	contract: AccountIdOf<T>,
-) -> Result<DispatchableCall<ReplyCall, (), T>, Error<T>> {
+) -> Result<DispatchableCall<ReplyCall<CustomMessageOf<T>>, (), T>, Error<T>> {
 	Ok(DispatchableCall {
 		entrypoint: EntryPoint::Reply,
 		sender: executor,
@@ -87,6 +101,22 @@ pub(crate) fn setup_reply_call<T: Config>(
 	})
 }
 
+/// Prepares for `sudo` entrypoint call.
+///
+/// * `contract` - Address of the contract to be called. There is no sender for this entrypoint, the
+///   contract is used instead.
+pub(crate) fn setup_sudo_call<T: Config>(
+	contract: AccountIdOf<T>,
+) -> Result<DispatchableCall<SudoCall<CustomMessageOf<T>>, (), T>, Error<T>> {
+	Ok(DispatchableCall {
+		entrypoint: EntryPoint::Sudo,
+		sender: contract.clone(),
+		contract,
+		output: (),
+		marker: PhantomData,
+	})
+}
+
 /// Prepares for `migrate` entrypoint call.
 ///
 /// * `migrator` - Address of the account that calls this entrypoint.
@@ -98,7 +128,7 @@ pub(crate) fn setup_migrate_call<T: Config>(
 	contract: AccountIdOf<T>,
 	new_code_id: CosmwasmCodeId,
 	call_migrate: bool,
-) -> Result<DispatchableCall<MigrateCall, (), T>, Error<T>> {
+) -> Result<DispatchableCall<MigrateCall<CustomMessageOf<T>>, (), T>, Error<T>> {
 	// If the migrate already happened, no need to do that again.
 	// This is the case for sub-message execution where `migrate` is
 	// called by the VM.
@@ -0,0 +1,264 @@
+//! Hand-estimated weights for cosmwasm.
+//!
+//! THESE WEIGHTS ARE NOT THE OUTPUT OF THE BENCHMARK CLI. They cover the calls and host functions
+//! missing from [`crate::weights`], and are derived by hand from the benchmarked weights of the
+//! closest operations. Their benchmarks live in `benchmarking.rs`, except for `query_grpc` which
+//! depends on the runtime router, and this file is to be deleted once [`crate::weights`] is
+//! regenerated with them. The per byte prices are the slopes of the `db_*_bytes` benchmarks.
+
+use crate::weights::{SubstrateWeight, WeightInfo};
+use frame_support::{
+	traits::Get,
+	weights::{
+		constants::{RocksDbWeight, WEIGHT_REF_TIME_PER_MICROS},
+		Weight,
+	},
+};
+
+/// Hand-estimated weight functions needed for cosmwasm.
+pub trait EstimatedWeightInfo {
+	fn db_next_key() -> Weight;
+	fn db_next_value() -> Weight;
+	fn secp256r1_recover_pubkey() -> Weight;
+	fn secp256r1_verify() -> Weight;
+	fn bls12_381_aggregate_g1(n: u32) -> Weight;
+	fn bls12_381_aggregate_g2(n: u32) -> Weight;
+	fn bls12_381_pairing_equality(n: u32) -> Weight;
+	fn bls12_381_hash_to_g1() -> Weight;
+	fn bls12_381_hash_to_g2() -> Weight;
+	fn query_grpc() -> Weight;
+	fn sudo() -> Weight;
+	fn set_code_upload_access() -> Weight;
+	fn append_contract_history(n: u32) -> Weight;
+	fn pin_codes(n: u32) -> Weight;
+	fn unpin_codes(n: u32) -> Weight;
+	fn load_module(n: u32) -> Weight;
+	fn load_pinned_module(n: u32) -> Weight;
+	fn reinstrument(n: u32) -> Weight;
+	fn remove_code() -> Weight;
+	fn db_read_key_byte() -> Weight;
+	fn db_read_value_byte() -> Weight;
+	fn db_write_key_byte() -> Weight;
+	fn db_write_value_byte() -> Weight;
+	fn db_remove_value_byte() -> Weight;
+}
+
+impl<T: frame_system::Config> EstimatedWeightInfo for SubstrateWeight<T> {
+	// Same work as `db_next`, which reads the key and the value of the next entry.
+	fn db_next_key() -> Weight {
+		<Self as WeightInfo>::db_next()
+	}
+	// Same work as `db_next`, which reads the key and the value of the next entry.
+	fn db_next_value() -> Weight {
+		<Self as WeightInfo>::db_next()
+	}
+	// Unlike secp256k1, secp256r1 has no endomorphism to speed up scalar multiplications.
+	fn secp256r1_recover_pubkey() -> Weight {
+		<Self as WeightInfo>::secp256k1_recover_pubkey().saturating_mul(3)
+	}
+	// Unlike secp256k1, secp256r1 has no endomorphism to speed up scalar multiplications.
+	fn secp256r1_verify() -> Weight {
+		<Self as WeightInfo>::secp256k1_verify().saturating_mul(3)
+	}
+	// Timing used by the cosmwasm-vm `GasConfig`.
+	fn bls12_381_aggregate_g1(n: u32) -> Weight {
+		Weight::from_parts(136 * WEIGHT_REF_TIME_PER_MICROS, 0).saturating_add(
+			Weight::from_parts(24 * WEIGHT_REF_TIME_PER_MICROS, 0).saturating_mul(n as u64),
+		)
+	}
+	// Timing used by the cosmwasm-vm `GasConfig`.
+	fn bls12_381_aggregate_g2(n: u32) -> Weight {
+		Weight::from_parts(207 * WEIGHT_REF_TIME_PER_MICROS, 0).saturating_add(
+			Weight::from_parts(49 * WEIGHT_REF_TIME_PER_MICROS, 0).saturating_mul(n as u64),
+		)
+	}
+	// Timing used by the cosmwasm-vm `GasConfig`.
+	fn bls12_381_pairing_equality(n: u32) -> Weight {
+		Weight::from_parts(2112 * WEIGHT_REF_TIME_PER_MICROS, 0).saturating_add(
+			Weight::from_parts(163 * WEIGHT_REF_TIME_PER_MICROS, 0).saturating_mul(n as u64),
+		)
+	}
+	// Timing used by the cosmwasm-vm `GasConfig`.
+	fn bls12_381_hash_to_g1() -> Weight {
+		Weight::from_parts(563 * WEIGHT_REF_TIME_PER_MICROS, 0)
+	}
+	// Timing used by the cosmwasm-vm `GasConfig`.
+	fn bls12_381_hash_to_g2() -> Weight {
+		Weight::from_parts(871 * WEIGHT_REF_TIME_PER_MICROS, 0)
+	}
+	// Routed queries read a couple of storage items, like `query_raw`.
+	fn query_grpc() -> Weight {
+		<Self as WeightInfo>::query_raw()
+	}
+	// Runs an entrypoint of an existing contract, like `execute` without funds.
+	fn sudo() -> Weight {
+		<Self as WeightInfo>::execute(0)
+	}
+	// A root call whose only work is writing `CodeUploadAccess`.
+	fn set_code_upload_access() -> Weight {
+		Weight::from_parts(9_000_000, 0).saturating_add(T::DbWeight::get().writes(1))
+	}
+	// Reads the history bounds, writes them with the new entry of `n` message bytes, and removes
+	// the oldest entry once the history is full.
+	fn append_contract_history(n: u32) -> Weight {
+		Weight::from_parts(5_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads_writes(1, 3))
+			.saturating_add(Self::db_write_value_byte().saturating_mul(n as u64))
+	}
+	// Reads the info of each of the `n` codes and marks it pinned, without loading its module.
+	fn pin_codes(n: u32) -> Weight {
+		Weight::from_parts(7_500_000, 0).saturating_add(
+			Weight::from_parts(10_000_000, 0)
+				.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+				.saturating_mul(n as u64),
+		)
+	}
+	// Same work as `pin_codes`.
+	fn unpin_codes(n: u32) -> Weight {
+		Self::pin_codes(n)
+	}
+	// Reads `CodeIdToInfo`, `PinnedCodes` and the `n` bytes of `InstrumentedCode`, then parses and
+	// validates the module at the pace of `PreparedModule::new`.
+	fn load_module(n: u32) -> Weight {
+		Weight::from_parts(31_600_000, 0)
+			.saturating_add(Weight::from_parts(10_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(3))
+	}
+	// Same work as `load_module`, except that the `n` bytes of the instrumented code are copied
+	// from the node instead of read from `InstrumentedCode`.
+	fn load_pinned_module(n: u32) -> Weight {
+		Weight::from_parts(32_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads(2))
+	}
+	// Reads `CodeIdToInfo` and the `n` bytes of `PristineCode`, instruments the code, then writes
+	// it to `InstrumentedCode` along with the updated code info.
+	fn reinstrument(n: u32) -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(Weight::from_parts(43_500, 0).saturating_mul(n as u64))
+			.saturating_add(T::DbWeight::get().reads_writes(2, 2))
+	}
+	// Reads the code info, the length of `PristineCode` and the account of the creator, unreserves
+	// the deposit, then removes the code, its instrumented code, hash, pin and info.
+	fn remove_code() -> Weight {
+		Weight::from_parts(38_500_000, 0).saturating_add(T::DbWeight::get().reads_writes(3, 6))
+	}
+	// Slope over `k` of `db_read_bytes`, on top of `db_read`. Hand-picked until benchmarked: as in
+	// the `KVGasConfig` of the Cosmos SDK, a byte written costs several times a byte read.
+	fn db_read_key_byte() -> Weight {
+		Weight::from_parts(39_732, 0)
+	}
+	// Slope over `v` of `db_read_bytes`. Same price as a byte of key until benchmarked.
+	fn db_read_value_byte() -> Weight {
+		Self::db_read_key_byte()
+	}
+	// A quarter of the slope over `k` of `db_write_bytes`, on top of `db_write`, since the key is
+	// written along with its `3k + 1` bytes of indexes. Hand-picked until benchmarked, see
+	// `db_read_key_byte`.
+	fn db_write_key_byte() -> Weight {
+		Weight::from_parts(193_950, 0)
+	}
+	// Slope over `v` of `db_write_bytes`. Same price as a byte of key until benchmarked.
+	fn db_write_value_byte() -> Weight {
+		Self::db_write_key_byte()
+	}
+	// Slope over `v` of `db_remove_bytes`, on top of `db_remove`. The removed value is measured and
+	// pruned from the trie without being written, so it is priced as a byte read until benchmarked.
+	fn db_remove_value_byte() -> Weight {
+		Self::db_read_value_byte()
+	}
+}
+
+// For backwards compatibility and tests
+impl EstimatedWeightInfo for () {
+	fn db_next_key() -> Weight {
+		<Self as WeightInfo>::db_next()
+	}
+	fn db_next_value() -> Weight {
+		<Self as WeightInfo>::db_next()
+	}
+	fn secp256r1_recover_pubkey() -> Weight {
+		<Self as WeightInfo>::secp256k1_recover_pubkey().saturating_mul(3)
+	}
+	fn secp256r1_verify() -> Weight {
+		<Self as WeightInfo>::secp256k1_verify().saturating_mul(3)
+	}
+	fn bls12_381_aggregate_g1(n: u32) -> Weight {
+		Weight::from_parts(136 * WEIGHT_REF_TIME_PER_MICROS, 0).saturating_add(
+			Weight::from_parts(24 * WEIGHT_REF_TIME_PER_MICROS, 0).saturating_mul(n as u64),
+		)
+	}
+	fn bls12_381_aggregate_g2(n: u32) -> Weight {
+		Weight::from_parts(207 * WEIGHT_REF_TIME_PER_MICROS, 0).saturating_add(
+			Weight::from_parts(49 * WEIGHT_REF_TIME_PER_MICROS, 0).saturating_mul(n as u64),
+		)
+	}
+	fn bls12_381_pairing_equality(n: u32) -> Weight {
+		Weight::from_parts(2112 * WEIGHT_REF_TIME_PER_MICROS, 0).saturating_add(
+			Weight::from_parts(163 * WEIGHT_REF_TIME_PER_MICROS, 0).saturating_mul(n as u64),
+		)
+	}
+	fn bls12_381_hash_to_g1() -> Weight {
+		Weight::from_parts(563 * WEIGHT_REF_TIME_PER_MICROS, 0)
+	}
+	fn bls12_381_hash_to_g2() -> Weight {
+		Weight::from_parts(871 * WEIGHT_REF_TIME_PER_MICROS, 0)
+	}
+	fn query_grpc() -> Weight {
+		<Self as WeightInfo>::query_raw()
+	}
+	fn sudo() -> Weight {
+		<Self as WeightInfo>::execute(0)
+	}
+	fn set_code_upload_access() -> Weight {
+		Weight::from_parts(9_000_000, 0).saturating_add(RocksDbWeight::get().writes(1))
+	}
+	fn append_contract_history(n: u32) -> Weight {
+		Weight::from_parts(5_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads_writes(1, 3))
+			.saturating_add(Self::db_write_value_byte().saturating_mul(n as u64))
+	}
+	fn pin_codes(n: u32) -> Weight {
+		Weight::from_parts(7_500_000, 0).saturating_add(
+			Weight::from_parts(10_000_000, 0)
+				.saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+				.saturating_mul(n as u64),
+		)
+	}
+	fn unpin_codes(n: u32) -> Weight {
+		Self::pin_codes(n)
+	}
+	fn load_module(n: u32) -> Weight {
+		Weight::from_parts(31_600_000, 0)
+			.saturating_add(Weight::from_parts(10_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(3))
+	}
+	fn load_pinned_module(n: u32) -> Weight {
+		Weight::from_parts(32_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads(2))
+	}
+	fn reinstrument(n: u32) -> Weight {
+		Weight::from_parts(17_000_000, 0)
+			.saturating_add(Weight::from_parts(43_500, 0).saturating_mul(n as u64))
+			.saturating_add(RocksDbWeight::get().reads_writes(2, 2))
+	}
+	fn remove_code() -> Weight {
+		Weight::from_parts(38_500_000, 0).saturating_add(RocksDbWeight::get().reads_writes(3, 6))
+	}
+	fn db_read_key_byte() -> Weight {
+		Weight::from_parts(39_732, 0)
+	}
+	fn db_read_value_byte() -> Weight {
+		Self::db_read_key_byte()
+	}
+	fn db_write_key_byte() -> Weight {
+		Weight::from_parts(193_950, 0)
+	}
+	fn db_write_value_byte() -> Weight {
+		Self::db_write_key_byte()
+	}
+	fn db_remove_value_byte() -> Weight {
+		Self::db_read_value_byte()
+	}
+}
@@ -0,0 +1,36 @@
+use crate::{
+	runtimes::vm::{CosmwasmVM, CosmwasmVMError},
+	Config,
+};
+use alloc::{string::String, vec::Vec};
+
+/// Whitelist of the protobuf queries (`QueryRequest::Grpc` and `QueryRequest::Stargate`) that
+/// contracts are allowed to make.
+///
+/// Responses are handed to the contract as is. They must be protobuf encoded and depend only on
+/// the runtime state, so that every node observes the same contract execution.
+pub trait GrpcQueryRouter<T: Config> {
+	/// Answer the protobuf encoded request `data` at the fully qualified service `path`, on behalf
+	/// of the contract executing in `vm`.
+	///
+	/// The flat `query_grpc` weight is charged beforehand. The reads whose number depends on the
+	/// state, e.g. one per registered denom, must be charged to `vm`.
+	///
+	/// Returns `None` if `path` is not whitelisted.
+	fn route<'a>(
+		vm: &mut CosmwasmVM<'a, T>,
+		path: &str,
+		data: &[u8],
+	) -> Result<Option<Result<Vec<u8>, String>>, CosmwasmVMError<T>>;
+}
+
+/// Default implementation, whitelisting nothing.
+impl<T: Config> GrpcQueryRouter<T> for () {
+	fn route<'a>(
+		_vm: &mut CosmwasmVM<'a, T>,
+		_path: &str,
+		_data: &[u8],
+	) -> Result<Option<Result<Vec<u8>, String>>, CosmwasmVMError<T>> {
+		Ok(None)
+	}
+}
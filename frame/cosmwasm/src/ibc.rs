@@ -1,43 +1,269 @@
 use crate::{
 	prelude::*,
-	runtimes::vm::CosmwasmVMError,
-	types::{AccountIdOf, DefaultCosmwasmVM},
-	Config, Pallet,
+	runtimes::vm::{CosmwasmVM, CosmwasmVMError, CosmwasmVMShared},
+	types::*,
+	Config, Error, IbcPortToContract, Pallet,
 };
+use cosmwasm_std::{
+	Binary, Coin, ContractResult, Ibc3ChannelOpenResponse, IbcChannelCloseMsg,
+	IbcChannelConnectMsg, IbcChannelOpenMsg, IbcPacketAckMsg, IbcPacketReceiveMsg,
+	IbcPacketTimeoutMsg, IbcTimeout,
+};
+use cosmwasm_vm::{
+	executor::{
+		cosmwasm_call,
+		ibc::{
+			IbcChannelCloseCall, IbcChannelConnectCall, IbcChannelOpenCall, IbcChannelOpenResult,
+			IbcPacketAckCall, IbcPacketReceiveCall, IbcPacketTimeoutCall,
+		},
+	},
+	system::{
+		cosmwasm_system_entrypoint, CosmwasmCallVM, CosmwasmDynamicVM, StargateCosmwasmCallVM,
+	},
+	vm::VmErrorOf,
+};
+use cosmwasm_vm_wasmi::{
+	validation::CodeValidation,
+	version::{Version, Version1x},
+	OwnedWasmiVM,
+};
+use serde::Serialize;
+
+/// The channel layer of the runtime, used to dispatch the `IbcMsg::SendPacket` and
+/// `IbcMsg::CloseChannel` emitted by contracts.
+///
+/// Contracts are bound to the `wasm.<address>` port, see
+/// [`Pallet::do_compute_ibc_contract_port`]. In the other direction, the channel layer delivers
+/// the channel handshake and packet callbacks through the `Pallet::do_ibc_channel_*` and
+/// `Pallet::do_ibc_packet_*` functions.
+///
+/// ICS-20 transfers are not supported, `IbcMsg::Transfer` is always rejected.
+pub trait IbcHandler<T: Config> {
+	/// Send a raw packet from `port_id` over `channel_id`.
+	fn send_packet<'a>(
+		vm: &mut CosmwasmVM<'a, T>,
+		port_id: String,
+		channel_id: String,
+		data: Binary,
+		timeout: IbcTimeout,
+	) -> Result<(), CosmwasmVMError<T>>;
+
+	/// Start the closing handshake of the channel `channel_id` bound to `port_id`.
+	fn close_channel<'a>(
+		vm: &mut CosmwasmVM<'a, T>,
+		port_id: String,
+		channel_id: String,
+	) -> Result<(), CosmwasmVMError<T>>;
+}
+
+/// Default implementation, for runtimes without IBC.
+impl<T: Config> IbcHandler<T> for () {
+	fn send_packet<'a>(
+		_vm: &mut CosmwasmVM<'a, T>,
+		_port_id: String,
+		_channel_id: String,
+		_data: Binary,
+		_timeout: IbcTimeout,
+	) -> Result<(), CosmwasmVMError<T>> {
+		Err(CosmwasmVMError::Unsupported)
+	}
+
+	fn close_channel<'a>(
+		_vm: &mut CosmwasmVM<'a, T>,
+		_port_id: String,
+		_channel_id: String,
+	) -> Result<(), CosmwasmVMError<T>> {
+		Err(CosmwasmVMError::Unsupported)
+	}
+}
 
 impl<T: Config> Pallet<T> {
 	/// Check whether a contract export the mandatory IBC functions and is consequently IBC capable.
-	pub(crate) fn do_check_ibc_capability(_module: &parity_wasm::elements::Module) -> bool {
-		false
+	pub(crate) fn do_check_ibc_capability(module: &parity_wasm::elements::Module) -> bool {
+		CodeValidation::new(module).validate_exports(Version1x::IBC_EXPORTS).is_ok()
+	}
+
+	/// Bind the `wasm.<address>` port to the IBC capable `contract`.
+	pub(crate) fn do_bind_ibc_port(contract: &AccountIdOf<T>) -> Result<(), Error<T>> {
+		let port_id: IbcPortId = Self::do_compute_ibc_contract_port(contract.clone())
+			.into_bytes()
+			.try_into()
+			.map_err(|_| Error::<T>::Ibc)?;
+		IbcPortToContract::<T>::insert(port_id, contract);
+		Ok(())
+	}
+
+	/// Contract bound to the IBC port `port_id`.
+	pub fn do_ibc_port_contract(port_id: &str) -> Result<AccountIdOf<T>, CosmwasmVMError<T>> {
+		IbcPortId::try_from(port_id.as_bytes().to_vec())
+			.ok()
+			.and_then(IbcPortToContract::<T>::get)
+			.ok_or_else(|| CosmwasmVMError::Ibc(format!("no contract bound to port {}", port_id)))
+	}
+
+	/// Call the `ibc_channel_open` entrypoint of the contract bound to the channel port.
+	///
+	/// Returns the channel version requested by the contract, if any.
+	pub fn do_ibc_channel_open(
+		shared: &mut CosmwasmVMShared,
+		relayer: AccountIdOf<T>,
+		message: IbcChannelOpenMsg,
+	) -> Result<Option<Ibc3ChannelOpenResponse>, CosmwasmVMError<T>> {
+		let contract = Self::do_ibc_port_contract(&message.channel().endpoint.port_id)?;
+		let message =
+			serde_json::to_vec(&message).map_err(|_| CosmwasmVMError::<T>::ExecuteSerialize)?;
+		Self::sub_level_dispatch(shared, relayer, contract, Default::default(), |mut vm| {
+			match cosmwasm_call::<IbcChannelOpenCall, _>(&mut vm, &message)? {
+				IbcChannelOpenResult(ContractResult::Ok(response)) => Ok(response),
+				IbcChannelOpenResult(ContractResult::Err(e)) => Err(CosmwasmVMError::Ibc(e)),
+			}
+		})
+	}
+
+	/// Call the `ibc_channel_connect` entrypoint of the contract bound to the channel port.
+	pub fn do_ibc_channel_connect(
+		shared: &mut CosmwasmVMShared,
+		relayer: AccountIdOf<T>,
+		message: IbcChannelConnectMsg,
+	) -> Result<(), CosmwasmVMError<T>> {
+		let port_id = message.channel().endpoint.port_id.clone();
+		Self::do_ibc_entrypoint::<IbcChannelConnectCall<CustomMessageOf<T>>>(
+			shared,
+			EntryPoint::IbcChannelConnect,
+			relayer,
+			&port_id,
+			&message,
+		)
+		.map(|_| ())
+	}
+
+	/// Call the `ibc_channel_close` entrypoint of the contract bound to the channel port.
+	pub fn do_ibc_channel_close(
+		shared: &mut CosmwasmVMShared,
+		relayer: AccountIdOf<T>,
+		message: IbcChannelCloseMsg,
+	) -> Result<(), CosmwasmVMError<T>> {
+		let port_id = message.channel().endpoint.port_id.clone();
+		Self::do_ibc_entrypoint::<IbcChannelCloseCall<CustomMessageOf<T>>>(
+			shared,
+			EntryPoint::IbcChannelClose,
+			relayer,
+			&port_id,
+			&message,
+		)
+		.map(|_| ())
+	}
+
+	/// Call the `ibc_packet_receive` entrypoint of the contract bound to the packet destination
+	/// port.
+	///
+	/// Returns the acknowledgement written by the contract, if any.
+	pub fn do_ibc_packet_receive(
+		shared: &mut CosmwasmVMShared,
+		relayer: AccountIdOf<T>,
+		message: IbcPacketReceiveMsg,
+	) -> Result<Option<Binary>, CosmwasmVMError<T>> {
+		let port_id = message.packet.dest.port_id.clone();
+		Self::do_ibc_entrypoint::<IbcPacketReceiveCall<CustomMessageOf<T>>>(
+			shared,
+			EntryPoint::IbcPacketReceive,
+			relayer,
+			&port_id,
+			&message,
+		)
+	}
+
+	/// Call the `ibc_packet_ack` entrypoint of the contract bound to the packet source port.
+	pub fn do_ibc_packet_ack(
+		shared: &mut CosmwasmVMShared,
+		relayer: AccountIdOf<T>,
+		message: IbcPacketAckMsg,
+	) -> Result<(), CosmwasmVMError<T>> {
+		let port_id = message.original_packet.src.port_id.clone();
+		Self::do_ibc_entrypoint::<IbcPacketAckCall<CustomMessageOf<T>>>(
+			shared,
+			EntryPoint::IbcPacketAck,
+			relayer,
+			&port_id,
+			&message,
+		)
+		.map(|_| ())
+	}
+
+	/// Call the `ibc_packet_timeout` entrypoint of the contract bound to the packet source port.
+	pub fn do_ibc_packet_timeout(
+		shared: &mut CosmwasmVMShared,
+		relayer: AccountIdOf<T>,
+		message: IbcPacketTimeoutMsg,
+	) -> Result<(), CosmwasmVMError<T>> {
+		let port_id = message.packet.src.port_id.clone();
+		Self::do_ibc_entrypoint::<IbcPacketTimeoutCall<CustomMessageOf<T>>>(
+			shared,
+			EntryPoint::IbcPacketTimeout,
+			relayer,
+			&port_id,
+			&message,
+		)
+		.map(|_| ())
+	}
+
+	/// Run an IBC entrypoint of the contract bound to `port_id` as a top level call, dispatching
+	/// the submessages it emits.
+	fn do_ibc_entrypoint<I>(
+		shared: &mut CosmwasmVMShared,
+		entrypoint: EntryPoint,
+		relayer: AccountIdOf<T>,
+		port_id: &str,
+		message: &impl Serialize,
+	) -> Result<Option<Binary>, CosmwasmVMError<T>>
+	where
+		for<'x> OwnedWasmiVM<DefaultCosmwasmVM<'x, T>>:
+			CosmwasmCallVM<I> + CosmwasmDynamicVM<I> + StargateCosmwasmCallVM,
+		for<'x> VmErrorOf<OwnedWasmiVM<DefaultCosmwasmVM<'x, T>>>:
+			From<CosmwasmVMError<T>> + Into<CosmwasmVMError<T>>,
+	{
+		let contract = Self::do_ibc_port_contract(port_id)?;
+		let message =
+			serde_json::to_vec(message).map_err(|_| CosmwasmVMError::<T>::ExecuteSerialize)?;
+		Self::top_level_dispatch(
+			shared,
+			entrypoint,
+			relayer,
+			contract,
+			Default::default(),
+			|mut vm| cosmwasm_system_entrypoint::<I, _>(&mut vm, &message).map_err(Into::into),
+		)
 	}
 
 	pub fn do_ibc_transfer(
 		_vm: &mut DefaultCosmwasmVM<T>,
 		_channel_id: String,
 		_to_address: String,
-		_amount: cosmwasm_std::Coin,
-		_timeout: cosmwasm_std::IbcTimeout,
+		_amount: Coin,
+		_timeout: IbcTimeout,
 	) -> Result<(), CosmwasmVMError<T>> {
 		Err(CosmwasmVMError::<T>::Unsupported)
 	}
 
 	pub(crate) fn do_ibc_send_packet(
-		_vm: &mut DefaultCosmwasmVM<T>,
-		_channel_id: String,
-		_data: cosmwasm_std::Binary,
-		_timeout: cosmwasm_std::IbcTimeout,
+		vm: &mut DefaultCosmwasmVM<T>,
+		channel_id: String,
+		data: Binary,
+		timeout: IbcTimeout,
 	) -> Result<(), CosmwasmVMError<T>> {
-		Err(CosmwasmVMError::<T>::Unsupported)
+		let port_id = Self::do_compute_ibc_contract_port(vm.contract_address.clone().into_inner());
+		T::IbcHandler::send_packet(vm, port_id, channel_id, data, timeout)
 	}
 
 	pub(crate) fn do_ibc_close_channel(
-		_vm: &mut DefaultCosmwasmVM<T>,
-		_channel_id: String,
+		vm: &mut DefaultCosmwasmVM<T>,
+		channel_id: String,
 	) -> Result<(), CosmwasmVMError<T>> {
-		Err(CosmwasmVMError::<T>::Unsupported)
+		let port_id = Self::do_compute_ibc_contract_port(vm.contract_address.clone().into_inner());
+		T::IbcHandler::close_channel(vm, port_id, channel_id)
 	}
 
-	pub(crate) fn do_compute_ibc_contract_port(address: AccountIdOf<T>) -> String {
+	pub fn do_compute_ibc_contract_port(address: AccountIdOf<T>) -> String {
 		format!("wasm.{}", Pallet::<T>::account_to_cosmwasm_addr(address))
 	}
 }
@@ -42,11 +42,19 @@ use alloc::string::ToString;
 
 pub use pallet::*;
 use sp_core::H256;
+pub mod any_msg_handler;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
 pub mod crypto;
+pub mod custom_handler;
 pub mod dispatchable_call;
 pub mod entrypoint;
+pub mod estimated_weights;
+pub mod grpc_query;
 pub mod ibc;
 pub mod instrument;
+pub mod migrations;
+mod module_cache;
 pub mod pallet_hook;
 mod prelude;
 pub mod runtimes;
@@ -58,37 +66,41 @@ const SUBSTRATE_ECDSA_SIGNATURE_LEN: usize = 65;
 use crate::{
 	entrypoint::*,
 	instrument::{gas_and_stack_instrumentation, INSTRUMENTATION_VERSION},
+	module_cache::CachedModule,
 	pallet_hook::PalletHook,
 	runtimes::{
-		abstraction::{CosmwasmAccount, Gas, VMPallet},
+		abstraction::{CosmwasmAccount, Gas, GasOutcome, VMPallet},
+		iterator::ContractIterator,
 		vm::{
 			ContractBackend, CosmwasmVM, CosmwasmVMCache, CosmwasmVMError, CosmwasmVMShared,
-			InitialStorageMutability,
+			InitialStorageMutability, LoadedModule,
 		},
 	},
 	types::*,
+	weights::WeightInfo,
 };
 use alloc::{
 	collections::{btree_map::Entry, BTreeMap},
 	format,
 	string::String,
+	sync::Arc,
 	vec::Vec,
 };
 use composable_support::abstractions::utils::increment::Increment;
 use cosmwasm_std::{
 	Addr, Attribute as CosmwasmEventAttribute, Binary as CosmwasmBinary, BlockInfo,
 	CodeInfoResponse, Coin, ContractInfo as CosmwasmContractInfo, ContractInfoResponse, Env,
-	Event as CosmwasmEvent, MessageInfo, Timestamp, TransactionInfo,
+	Event as CosmwasmEvent, MessageInfo, Order, Timestamp, TransactionInfo,
 };
 use cosmwasm_vm::{
 	executor::{cosmwasm_call, QueryCall, QueryResponse},
-	system::{cosmwasm_system_query, CosmwasmCodeId, CosmwasmContractMeta},
+	system::{cosmwasm_system_query, CosmwasmCodeId, CosmwasmContractMeta, SystemError},
 };
 use cosmwasm_vm_wasmi::{
-	new_wasmi_vm,
+	new_wasmi_vm_from_module,
 	validation::{CodeValidation, ValidationError},
 	version::{Version, Version1x},
-	OwnedWasmiVM,
+	OwnedWasmiVM, PreparedModule, WasmiVMError,
 };
 use frame_support::{
 	dispatch::{DispatchErrorWithPostInfo, DispatchResultWithPostInfo, PostDispatchInfo},
@@ -96,12 +108,13 @@ use frame_support::{
 	storage::child::ChildInfo,
 	traits::{
 		fungibles::{Inspect as FungiblesInspect, Mutate as FungiblesMutate},
-		tokens::Preservation,
-		Currency, ExistenceRequirement, Get, ReservableCurrency, UnixTime,
+		tokens::{Fortitude, Precision, Preservation},
+		Currency, ExistenceRequirement, Get, ReservableCurrency, UnixTime, WithdrawReasons,
 	},
+	weights::WeightMeter,
 	ReversibleStorageHasher, StorageHasher,
 };
-use sp_runtime::traits::SaturatedConversion;
+use sp_runtime::traits::{Convert, SaturatedConversion};
 use wasmi::AsContext;
 use wasmi_validation::PlainValidator;
 
@@ -109,8 +122,16 @@ use wasmi_validation::PlainValidator;
 #[frame_support::pallet]
 pub mod pallet {
 	use crate::{
-		instrument::CostRules, pallet_hook::PalletHook, runtimes::vm::InitialStorageMutability,
-		types::*, weights::WeightInfo,
+		any_msg_handler::AnyMsgHandler,
+		custom_handler::CustomHandler,
+		estimated_weights::EstimatedWeightInfo,
+		grpc_query::GrpcQueryRouter,
+		ibc::IbcHandler,
+		instrument::{CostRules, INSTRUMENTATION_VERSION},
+		pallet_hook::PalletHook,
+		runtimes::vm::InitialStorageMutability,
+		types::*,
+		weights::WeightInfo,
 	};
 	use alloc::{string::String, vec::Vec};
 	use composable_support::abstractions::{
@@ -130,7 +151,10 @@ pub mod pallet {
 		},
 		transactional, PalletId, Twox64Concat,
 	};
-	use frame_system::{ensure_signed, pallet_prelude::OriginFor};
+	use frame_system::{
+		ensure_root, ensure_signed, ensure_signed_or_root,
+		pallet_prelude::{BlockNumberFor, OriginFor},
+	};
 	use sp_core::crypto::UncheckedFrom;
 	use sp_runtime::traits::{Convert, MaybeDisplay};
 
@@ -144,6 +168,14 @@ pub mod pallet {
 		Emitted { contract: AccountIdOf<T>, ty: Vec<u8>, attributes: Vec<(Vec<u8>, Vec<u8>)> },
 		Migrated { contract: AccountIdOf<T>, to: CosmwasmCodeId },
 		AdminUpdated { contract: AccountIdOf<T>, new_admin: Option<AccountIdOf<T>> },
+		CodeUploadAccessUpdated { access: AccessConfigOf<T> },
+		CodePinned { code_id: CosmwasmCodeId },
+		CodeUnpinned { code_id: CosmwasmCodeId },
+		CodeReinstrumented { code_id: CosmwasmCodeId },
+		ReinstrumentationProgressed { next_code_id: CosmwasmCodeId, reinstrumented: u32 },
+		ReinstrumentationCompleted { instrumentation_version: u16 },
+		KeyIndexingCompleted,
+		CodeRemoved { code_id: CosmwasmCodeId },
 	}
 
 	#[pallet::error]
@@ -195,6 +227,11 @@ pub mod pallet {
 		ExecuteSerialize,
 		Xcm,
 		IncrementFailed,
+		BurnFailed,
+		Stargate,
+		MessageTooBig,
+		CodeInUse,
+		ContractNotIndexed,
 	}
 
 	#[pallet::config]
@@ -253,6 +290,15 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxFundsAssets: Get<u32>;
 
+		/// Max entries kept in the code history of a contract, the oldest are dropped first.
+		#[pallet::constant]
+		type MaxCodeHistoryEntries: Get<u32>;
+
+		/// Max unpinned modules kept prepared in memory, the least recently used are evicted
+		/// first.
+		#[pallet::constant]
+		type ModuleCacheSize: Get<u32>;
+
 		/// Max wasm table size.
 		#[pallet::constant]
 		type CodeTableSizeLimit: Get<u32>;
@@ -279,14 +325,6 @@ pub mod pallet {
 		#[pallet::constant]
 		type CodeStorageByteDeposit: Get<u32>;
 
-		/// Price of writing a byte in the storage.
-		#[pallet::constant]
-		type ContractStorageByteWritePrice: Get<u32>;
-
-		/// Price of extracting a byte from the storage.
-		#[pallet::constant]
-		type ContractStorageByteReadPrice: Get<u32>;
-
 		#[pallet::constant]
 		type WasmCostRules: Get<CostRules<Self>>;
 
@@ -321,24 +359,46 @@ pub mod pallet {
 		/// Source of time.
 		type UnixTime: UnixTime;
 
-		/// Weight implementation.
-		type WeightInfo: WeightInfo;
+		/// Weight implementation, along with the hand-estimated weights of the functions missing
+		/// from the benchmark output.
+		type WeightInfo: WeightInfo + EstimatedWeightInfo;
 
 		/// A hook into the VM execution semantic, allowing the runtime to hook into a contract
 		/// execution.
 		type PalletHook: PalletHook<Self>;
 
-		/// Origin to upload a WASM code
-		type UploadWasmOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Handler for the protobuf encoded messages (`CosmosMsg::Any` and `CosmosMsg::Stargate`)
+		/// emitted by contracts.
+		type AnyMsgHandler: AnyMsgHandler<Self>;
+
+		/// Whitelisted protobuf queries (`QueryRequest::Grpc` and `QueryRequest::Stargate`) made
+		/// by contracts.
+		type GrpcQueryRouter: GrpcQueryRouter<Self>;
+
+		/// Chain specific bindings for the `CosmosMsg::Custom` and `QueryRequest::Custom` emitted
+		/// by contracts.
+		type CustomHandler: CustomHandler<Self>;
+
+		/// Channel layer, dispatching the packets sent and the channels closed by contracts.
+		type IbcHandler: IbcHandler<Self>;
 
 		type ExecuteWasmOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
 		type NativeDenom: Get<&'static str>;
 
 		type NativeAssetId: Get<Self::AssetId>;
+
+		/// The balances of an account, zero ones included, as `(denom, amount)` pairs sorted by
+		/// denom. At most the given number of registered denoms are visited, `None` being
+		/// returned if more are registered.
+		type AllBalances: Convert<(AccountIdOf<Self>, u32), Option<Vec<(String, u128)>>>;
 	}
 
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// A mapping from an original code id to the original code, untouched by instrumentation.
@@ -362,6 +422,39 @@ pub mod pallet {
 	pub(crate) type CodeIdToInfo<T: Config> =
 		StorageMap<_, Twox64Concat, CosmwasmCodeId, CodeInfoOf<T>>;
 
+	#[pallet::type_value]
+	pub(crate) fn DefaultCodeUploadAccess<T: Config>() -> AccessConfigOf<T> {
+		AccessConfig::Everybody
+	}
+
+	/// Who is allowed to upload codes, a.k.a the `code_upload_access` parameter of wasmd.
+	#[pallet::storage]
+	pub(crate) type CodeUploadAccess<T: Config> =
+		StorageValue<_, AccessConfigOf<T>, ValueQuery, DefaultCodeUploadAccess<T>>;
+
+	/// Codes whose module is never evicted from the module cache and whose instrumented code is
+	/// kept by the node, a.k.a the pinned codes of wasmd.
+	#[pallet::storage]
+	pub(crate) type PinnedCodes<T: Config> = StorageMap<_, Twox64Concat, CosmwasmCodeId, ()>;
+
+	/// Instrumentation version the stored codes were last brought to by the background
+	/// re-instrumentation.
+	#[pallet::storage]
+	pub(crate) type CodesInstrumentationVersion<T: Config> = StorageValue<_, u16, ValueQuery>;
+
+	/// Instrumentation version targeted by the background re-instrumentation in progress, along
+	/// with the next code id to check.
+	#[pallet::storage]
+	pub(crate) type ReinstrumentationCursor<T: Config> =
+		StorageValue<_, (u16, CosmwasmCodeId), OptionQuery>;
+
+	/// Contract whose keys are being recorded by the background key indexing, along with the last
+	/// indexed entry of its storage. `None` until the first contract is visited.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub(crate) type KeyIndexingCursor<T: Config> =
+		StorageValue<_, (Option<AccountIdOf<T>>, Vec<u8>), OptionQuery>;
+
 	/// A mapping between a code hash and it's unique ID.
 	#[pallet::storage]
 	pub(crate) type CodeHashToId<T: Config> = StorageMap<_, Identity, [u8; 32], CosmwasmCodeId>;
@@ -373,19 +466,53 @@ pub mod pallet {
 	pub(crate) type CurrentNonce<T: Config> =
 		StorageValue<_, u64, ValueQuery, Nonce<ZeroInit, SafeIncrement>>;
 
+	/// Monotonic counter incremented on contract instantiation without a salt.
+	/// Used as the instance id of the classic contract address derivation.
+	#[allow(clippy::disallowed_types)]
+	#[pallet::storage]
+	pub(crate) type CurrentInstanceId<T: Config> =
+		StorageValue<_, u64, ValueQuery, Nonce<ZeroInit, SafeIncrement>>;
+
 	/// A mapping between a contract's account id and it's metadata.
 	#[pallet::storage]
 	pub(crate) type ContractToInfo<T: Config> =
 		StorageMap<_, Identity, AccountIdOf<T>, ContractInfoOf<T>>;
 
+	/// Index of the contracts running a code, a.k.a the contracts by code of wasmd.
+	#[pallet::storage]
+	pub(crate) type CodeIdToContracts<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CosmwasmCodeId, Blake2_128Concat, AccountIdOf<T>, ()>;
+
+	/// The code history entries of a contract, by sequence number.
+	#[pallet::storage]
+	pub(crate) type ContractToHistory<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		AccountIdOf<T>,
+		Twox64Concat,
+		u32,
+		ContractCodeHistoryEntryOf<T>,
+	>;
+
+	/// The sequence numbers of the code history entries kept for a contract.
+	#[pallet::storage]
+	pub(crate) type ContractToHistoryBounds<T: Config> =
+		StorageMap<_, Identity, AccountIdOf<T>, ContractCodeHistoryBounds, ValueQuery>;
+
+	/// A mapping between an IBC port (`wasm.<address>`) and the IBC capable contract bound to it.
+	#[pallet::storage]
+	pub(crate) type IbcPortToContract<T: Config> =
+		StorageMap<_, Blake2_128Concat, IbcPortId, AccountIdOf<T>>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub contracts: Vec<(T::AccountIdExtended, ContractCodeOf<T>)>,
+		pub code_upload_access: AccessConfigOf<T>,
 	}
 
 	impl<T: Config> Default for GenesisConfig<T> {
 		fn default() -> Self {
-			Self { contracts: Default::default() }
+			Self { contracts: Default::default(), code_upload_access: AccessConfig::Everybody }
 		}
 	}
 
@@ -393,8 +520,21 @@ pub mod pallet {
 	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
 		fn build(&self) {
 			for (who, code) in self.contracts.clone() {
-				<Pallet<T>>::do_upload(&who, code).expect("contracts in genesis are valid");
+				<Pallet<T>>::do_upload(&who, code, AccessConfig::Everybody)
+					.expect("contracts in genesis are valid");
 			}
+			CodeUploadAccess::<T>::put(&self.code_upload_access);
+			CodesInstrumentationVersion::<T>::put(INSTRUMENTATION_VERSION);
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let consumed = Self::do_reinstrument_codes(remaining_weight);
+			consumed.saturating_add(Self::do_index_contract_keys(
+				remaining_weight.saturating_sub(consumed),
+			))
 		}
 	}
 
@@ -415,9 +555,8 @@ pub mod pallet {
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::upload(code.len() as u32))]
 		pub fn upload(origin: OriginFor<T>, code: ContractCodeOf<T>) -> DispatchResult {
-			T::UploadWasmOrigin::ensure_origin(origin.clone())?;
 			let who = ensure_signed(origin)?;
-			Self::do_upload(&who, code)?;
+			Self::do_upload(&who, code, AccessConfig::Everybody)?;
 
 			Ok(())
 		}
@@ -461,7 +600,7 @@ pub mod pallet {
 				&mut shared,
 				who,
 				code_identifier,
-				salt,
+				Some(salt),
 				admin,
 				label,
 				funds,
@@ -567,6 +706,141 @@ pub mod pallet {
 			Self::deposit_event(Event::<T>::AdminUpdated { contract, new_admin });
 			Self::refund_gas(outcome, initial_gas, shared.gas.remaining())
 		}
+
+		/// Call the privileged `sudo` entrypoint of a contract.
+		///
+		/// * Emits an `Executed` event.
+		/// * Possibly emit `Emitted` events.
+		///
+		/// # Arguments
+		///
+		/// * `origin` the origin dispatching the extrinsic, must be root.
+		/// * `contract` the address of the contract that we want to call.
+		/// * `gas` the maximum gas to use, the remaining is refunded at the end of the transaction.
+		/// * `message` SudoMsg, that will be passed to the contract.
+		#[pallet::call_index(5)]
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::sudo().saturating_add(Weight::from_parts(*gas, 0)))]
+		pub fn sudo(
+			origin: OriginFor<T>,
+			contract: AccountIdOf<T>,
+			gas: u64,
+			message: ContractMessageOf<T>,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let mut shared = Self::do_create_vm_shared(gas, InitialStorageMutability::ReadWrite);
+			let initial_gas =
+				T::WeightInfo::sudo().saturating_add(Weight::from_parts(gas, 0)).ref_time();
+			let outcome = Self::do_sudo(&mut shared, contract, message);
+			Self::refund_gas(outcome, initial_gas, shared.gas.remaining())
+		}
+
+		/// Set who is allowed to upload codes, through the `upload` extrinsic as well as the
+		/// messages of the runtime.
+		///
+		/// * Emits a `CodeUploadAccessUpdated` event.
+		///
+		/// # Arguments
+		///
+		/// * `origin` the origin dispatching the extrinsic, must be root.
+		/// * `access` the new code upload access.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::set_code_upload_access())]
+		pub fn set_code_upload_access(
+			origin: OriginFor<T>,
+			access: AccessConfigOf<T>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			CodeUploadAccess::<T>::put(&access);
+			Self::deposit_event(Event::<T>::CodeUploadAccessUpdated { access });
+
+			Ok(())
+		}
+
+		/// Pin codes, keeping their module from being evicted from the module cache.
+		///
+		/// * Emits a `CodePinned` event for each code.
+		///
+		/// # Arguments
+		///
+		/// * `origin` the origin dispatching the extrinsic, must be root.
+		/// * `code_ids` the codes to pin.
+		#[pallet::call_index(7)]
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::pin_codes(code_ids.len() as u32))]
+		pub fn pin_codes(origin: OriginFor<T>, code_ids: Vec<CosmwasmCodeId>) -> DispatchResult {
+			ensure_root(origin)?;
+			for code_id in code_ids {
+				Self::do_pin_code(code_id)?;
+			}
+
+			Ok(())
+		}
+
+		/// Unpin codes, letting their module be evicted from the module cache.
+		///
+		/// * Emits a `CodeUnpinned` event for each code.
+		///
+		/// # Arguments
+		///
+		/// * `origin` the origin dispatching the extrinsic, must be root.
+		/// * `code_ids` the codes to unpin.
+		#[pallet::call_index(8)]
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::unpin_codes(code_ids.len() as u32))]
+		pub fn unpin_codes(origin: OriginFor<T>, code_ids: Vec<CosmwasmCodeId>) -> DispatchResult {
+			ensure_root(origin)?;
+			for code_id in code_ids {
+				Self::do_unpin_code(code_id)?;
+			}
+
+			Ok(())
+		}
+
+		/// Instrument the code again with the current instrumentation, even if it is up to date.
+		///
+		/// Outdated codes are otherwise re-instrumented in the background, or by the first call
+		/// loading them.
+		///
+		/// * Emits a `CodeReinstrumented` event.
+		///
+		/// # Arguments
+		///
+		/// * `origin` the origin dispatching the extrinsic, must be root.
+		/// * `code_id` the code to re-instrument.
+		#[pallet::call_index(9)]
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::reinstrument(T::MaxCodeSize::get()))]
+		pub fn force_reinstrument(
+			origin: OriginFor<T>,
+			code_id: CosmwasmCodeId,
+		) -> DispatchResultWithPostInfo {
+			ensure_root(origin)?;
+			let code_len =
+				PristineCode::<T>::decode_len(code_id).ok_or(Error::<T>::CodeNotFound)?;
+			Self::do_reinstrument_code(code_id)?;
+
+			Ok(Some(T::WeightInfo::reinstrument(code_len as u32)).into())
+		}
+
+		/// Remove a code no contract runs, releasing the deposit reserved for its upload.
+		///
+		/// * Emits a `CodeRemoved` event.
+		///
+		/// # Arguments
+		///
+		/// * `origin` the origin dispatching the extrinsic, must be root or the creator of the
+		///   code.
+		/// * `code_id` the code to remove.
+		#[pallet::call_index(10)]
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::remove_code())]
+		pub fn remove_code(origin: OriginFor<T>, code_id: CosmwasmCodeId) -> DispatchResult {
+			let who = ensure_signed_or_root(origin)?;
+			Self::do_remove_code(who.as_ref(), code_id)?;
+
+			Ok(())
+		}
 	}
 }
 
@@ -622,7 +896,7 @@ pub fn instantiate<T: Config>(
 		.try_into()
 		.map_err(|_| CosmwasmVMError::<T>::Rpc(String::from("'message' is too large")))?;
 	let mut shared = Pallet::<T>::do_create_vm_shared(gas, InitialStorageMutability::ReadWrite);
-	setup_instantiate_call(instantiator, code_id, &salt, admin, label)?.top_level_call(
+	setup_instantiate_call(instantiator, code_id, Some(&salt), admin, label)?.top_level_call(
 		&mut shared,
 		funds,
 		message,
@@ -675,7 +949,7 @@ impl<T: Config> Pallet<T> {
 		contract: AccountIdOf<T>,
 		funds: FundsOf<T>,
 		call: F,
-	) -> Result<(), CosmwasmVMError<T>>
+	) -> Result<Option<CosmwasmBinary>, CosmwasmVMError<T>>
 	where
 		F: for<'x> FnOnce(
 			OwnedWasmiVM<DefaultCosmwasmVM<'x, T>>,
@@ -704,8 +978,9 @@ impl<T: Config> Pallet<T> {
 				Self::deposit_event(Event::<T>::Executed {
 					contract,
 					entrypoint,
-					data: data.map(Into::into),
+					data: data.clone().map(Into::into),
 				});
+				data
 			},
 		)
 	}
@@ -762,6 +1037,7 @@ impl<T: Config> Pallet<T> {
 					CosmwasmVMError::ExecuteSerialize => Error::<T>::ExecuteSerialize,
 					CosmwasmVMError::NotImplemented => Error::<T>::NotAuthorized,
 					CosmwasmVMError::Xcm(_) => Error::<T>::Xcm,
+					CosmwasmVMError::Stargate(_) => Error::<T>::Stargate,
 				};
 				Err(DispatchErrorWithPostInfo { error: error.into(), post_info })
 			},
@@ -784,13 +1060,20 @@ impl<T: Config> Pallet<T> {
 		let mut info = Self::contract_info(contract)?;
 
 		if info.code_id != code_id {
+			CodeIdToContracts::<T>::remove(info.code_id, contract);
+			CodeIdToContracts::<T>::insert(code_id, contract, ());
 			// Increase the refcount of `new_code_id`.
-			CodeIdToInfo::<T>::try_mutate_exists(code_id, |entry| -> Result<(), Error<T>> {
-				let code_info = entry.as_mut().ok_or(Error::<T>::CodeNotFound)?;
-				code_info.refcount =
-					code_info.refcount.checked_add(1).ok_or(Error::<T>::RefcountOverflow)?;
-				Ok(())
-			})?;
+			let ibc_capable =
+				CodeIdToInfo::<T>::try_mutate_exists(code_id, |entry| -> Result<bool, Error<T>> {
+					let code_info = entry.as_mut().ok_or(Error::<T>::CodeNotFound)?;
+					code_info.refcount =
+						code_info.refcount.checked_add(1).ok_or(Error::<T>::RefcountOverflow)?;
+					Ok(code_info.ibc_capable)
+				})?;
+			// Migrating to an IBC capable code binds the contract port, if not bound yet.
+			if ibc_capable {
+				Self::do_bind_ibc_port(contract)?;
+			}
 
 			// Modify the existing `code_id`'s states and unreserve the bonded funds.
 			CodeIdToInfo::<T>::try_mutate_exists(info.code_id, |entry| -> Result<(), Error<T>> {
@@ -799,14 +1082,7 @@ impl<T: Config> Pallet<T> {
 				code_info.refcount =
 					code_info.refcount.checked_sub(1).ok_or(Error::<T>::RefcountOverflow)?;
 				if code_info.refcount == 0 {
-					// Unreserve the bonded funds for this code
-					let code = PristineCode::<T>::try_get(info.code_id)
-						.map_err(|_| Error::<T>::CodeNotFound)?;
-					let deposit = code.len().saturating_mul(T::CodeStorageByteDeposit::get() as _);
-					let _ = T::NativeAsset::unreserve(&code_info.creator, deposit.saturated_into());
-					PristineCode::<T>::remove(info.code_id);
-					InstrumentedCode::<T>::remove(info.code_id);
-					CodeHashToId::<T>::remove(code_info.pristine_code_hash);
+					Self::do_release_code(info.code_id, code_info)?;
 					// Code is unused after this point, so it can be removed
 					*entry = None;
 				}
@@ -826,6 +1102,37 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Append an entry to the code history of a contract, with the code it currently runs.
+	///
+	/// The oldest entry is dropped once the history holds [`Config::MaxCodeHistoryEntries`].
+	pub(crate) fn do_append_contract_history(
+		contract: &AccountIdOf<T>,
+		operation: ContractCodeHistoryOperation,
+		message: &[u8],
+	) -> Result<(), Error<T>> {
+		let info = Self::contract_info(contract)?;
+		let entry = ContractCodeHistoryEntry {
+			operation,
+			code_id: info.code_id,
+			updated: frame_system::Pallet::<T>::block_number().saturated_into(),
+			msg: message.to_vec().try_into().map_err(|_| Error::<T>::MessageTooBig)?,
+		};
+		let max = T::MaxCodeHistoryEntries::get();
+		if max == 0 {
+			return Ok(());
+		}
+		ContractToHistoryBounds::<T>::mutate(contract, |bounds| {
+			ContractToHistory::<T>::insert(contract, bounds.head.saturating_add(bounds.len), entry);
+			if bounds.len < max {
+				bounds.len += 1;
+			} else {
+				ContractToHistory::<T>::remove(contract, bounds.head);
+				bounds.head = bounds.head.saturating_add(1);
+			}
+		});
+		Ok(())
+	}
+
 	/// Ensure that a contract exists.
 	pub(crate) fn contract_exists(contract: &AccountIdOf<T>) -> Result<(), Error<T>> {
 		match T::PalletHook::info(contract) {
@@ -899,21 +1206,176 @@ impl<T: Config> Pallet<T> {
 	pub(crate) fn do_check_for_reinstrumentation(
 		code_id: CosmwasmCodeId,
 	) -> Result<Option<Vec<u8>>, Error<T>> {
-		CodeIdToInfo::<T>::try_mutate(code_id, |entry| {
+		let code_info = Self::code_info(code_id)?;
+		if code_info.instrumentation_version != INSTRUMENTATION_VERSION {
+			log::debug!(target: "runtime::contracts", "do_check_for_reinstrumentation: required");
+			Self::do_reinstrument_code(code_id).map(Some)
+		} else {
+			log::debug!(target: "runtime::contracts", "do_check_for_reinstrumentation: not required");
+			Ok(None)
+		}
+	}
+
+	/// Instrument the pristine code of `code_id` with the current instrumentation, returning the
+	/// instrumented code.
+	pub(crate) fn do_reinstrument_code(code_id: CosmwasmCodeId) -> Result<Vec<u8>, Error<T>> {
+		let instrumented_code = CodeIdToInfo::<T>::try_mutate(code_id, |entry| {
 			let code_info = entry.as_mut().ok_or(Error::<T>::CodeNotFound)?;
-			if code_info.instrumentation_version != INSTRUMENTATION_VERSION {
-				log::debug!(target: "runtime::contracts", "do_check_for_reinstrumentation: required");
-				let code = PristineCode::<T>::get(code_id).ok_or(Error::<T>::CodeNotFound)?;
-				let module = Self::do_load_module(&code)?;
-				let instrumented_code = Self::do_instrument_code(module)?;
-				InstrumentedCode::<T>::insert(code_id, instrumented_code.clone());
-				code_info.instrumentation_version = INSTRUMENTATION_VERSION;
-				Ok(Some(instrumented_code.into()))
-			} else {
-				log::debug!(target: "runtime::contracts", "do_check_for_reinstrumentation: not required");
-				Ok(None)
+			let code = PristineCode::<T>::get(code_id).ok_or(Error::<T>::CodeNotFound)?;
+			let module = Self::do_load_module(&code)?;
+			let instrumented_code = Self::do_instrument_code(module)?;
+			InstrumentedCode::<T>::insert(code_id, instrumented_code.clone());
+			code_info.instrumentation_version = INSTRUMENTATION_VERSION;
+			Ok::<_, Error<T>>(instrumented_code)
+		})?;
+		Self::deposit_event(Event::<T>::CodeReinstrumented { code_id });
+		Ok(instrumented_code.into())
+	}
+
+	/// Re-instrument the outdated codes within `limit`, resuming where the previous call stopped.
+	///
+	/// Codes are walked by increasing id, so that those uploaded during the walk are already
+	/// instrumented with the current version. Codes failing to be re-instrumented are skipped,
+	/// the calls loading them failing as before. Returns the consumed weight.
+	pub(crate) fn do_reinstrument_codes(limit: Weight) -> Weight {
+		let mut meter = WeightMeter::with_limit(limit);
+		if meter.try_consume(T::DbWeight::get().reads(1)).is_err() ||
+			CodesInstrumentationVersion::<T>::get() == INSTRUMENTATION_VERSION
+		{
+			return meter.consumed();
+		}
+		if meter.try_consume(T::DbWeight::get().reads_writes(2, 2)).is_err() {
+			return meter.consumed();
+		}
+
+		let last_code_id = CurrentCodeId::<T>::get();
+		let first_code_id = match ReinstrumentationCursor::<T>::get() {
+			Some((version, code_id)) if version == INSTRUMENTATION_VERSION => code_id,
+			_ => 1,
+		};
+		let mut code_id = first_code_id;
+		let mut reinstrumented = 0u32;
+		while code_id <= last_code_id {
+			if meter.try_consume(T::DbWeight::get().reads(1)).is_err() {
+				break;
 			}
-		})
+			let code_len = PristineCode::<T>::decode_len(code_id).unwrap_or_default();
+			if meter
+				.try_consume(T::WeightInfo::reinstrument(code_len.saturated_into()))
+				.is_err()
+			{
+				break;
+			}
+			match Self::do_check_for_reinstrumentation(code_id) {
+				Ok(Some(_)) => reinstrumented = reinstrumented.saturating_add(1),
+				Ok(None) | Err(Error::<T>::CodeNotFound) => {},
+				Err(e) => log::warn!(
+					target: "runtime::contracts",
+					"do_reinstrument_codes: failed to reinstrument code {}: {:?}",
+					code_id,
+					e
+				),
+			}
+			code_id = code_id.saturating_add(1);
+		}
+
+		if code_id != first_code_id {
+			Self::deposit_event(Event::<T>::ReinstrumentationProgressed {
+				next_code_id: code_id,
+				reinstrumented,
+			});
+		}
+		if code_id > last_code_id {
+			ReinstrumentationCursor::<T>::kill();
+			CodesInstrumentationVersion::<T>::put(INSTRUMENTATION_VERSION);
+			Self::deposit_event(Event::<T>::ReinstrumentationCompleted {
+				instrumentation_version: INSTRUMENTATION_VERSION,
+			});
+		} else {
+			ReinstrumentationCursor::<T>::put((INSTRUMENTATION_VERSION, code_id));
+		}
+
+		meter.consumed()
+	}
+
+	/// Record the keys of the existing contracts in their key indexes within `limit`, resuming
+	/// where the previous call stopped.
+	///
+	/// Contracts are walked by storage order. Keys written or removed during the walk maintain the
+	/// indexes themselves, hence recording a key twice is harmless. Returns the consumed weight.
+	pub(crate) fn do_index_contract_keys(limit: Weight) -> Weight {
+		let mut meter = WeightMeter::with_limit(limit);
+		if meter.try_consume(T::DbWeight::get().reads(1)).is_err() {
+			return meter.consumed();
+		}
+		let Some((mut contract, mut entry)) = KeyIndexingCursor::<T>::get() else {
+			return meter.consumed();
+		};
+		if meter.try_consume(T::DbWeight::get().reads_writes(1, 1)).is_err() {
+			return meter.consumed();
+		}
+
+		let mut info = contract.as_ref().and_then(ContractToInfo::<T>::get);
+		loop {
+			let Some(current) = info.take() else {
+				if meter.try_consume(T::DbWeight::get().reads(1)).is_err() {
+					break;
+				}
+				let next = match &contract {
+					Some(contract) => ContractToInfo::<T>::iter_from(
+						ContractToInfo::<T>::hashed_key_for(contract),
+					)
+					.next(),
+					None => ContractToInfo::<T>::iter().next(),
+				};
+				match next {
+					Some((next, next_info)) => {
+						contract = Some(next);
+						info = Some(next_info);
+						entry.clear();
+						continue;
+					},
+					None => {
+						KeyIndexingCursor::<T>::kill();
+						Self::deposit_event(Event::<T>::KeyIndexingCompleted);
+						return meter.consumed();
+					},
+				}
+			};
+
+			let child_info = Self::contract_child_trie(current.trie_id.as_ref());
+			loop {
+				if meter.try_consume(T::DbWeight::get().reads_writes(1, 2)).is_err() {
+					KeyIndexingCursor::<T>::put((contract, entry));
+					return meter.consumed();
+				}
+				match sp_io::default_child_storage::next_key(child_info.storage_key(), &entry) {
+					Some(next) => {
+						Self::do_index_key(
+							current.trie_id.as_ref(),
+							Blake2_128Concat::reverse(&next),
+						);
+						entry = next;
+					},
+					None => break,
+				}
+			}
+		}
+
+		KeyIndexingCursor::<T>::put((contract, entry));
+		meter.consumed()
+	}
+
+	/// Whether the key indexes of `contract` are complete, i.e. the background key indexing
+	/// already walked it.
+	pub(crate) fn is_contract_indexed(contract: &AccountIdOf<T>) -> bool {
+		match KeyIndexingCursor::<T>::get() {
+			None => true,
+			Some((None, _)) => false,
+			Some((Some(cursor), _)) =>
+				ContractToInfo::<T>::hashed_key_for(contract) <
+					ContractToInfo::<T>::hashed_key_for(cursor),
+		}
 	}
 
 	pub(crate) fn do_load_module(
@@ -940,7 +1402,9 @@ impl<T: Config> Pallet<T> {
 	pub fn do_upload(
 		who: &AccountIdOf<T>,
 		code: ContractCodeOf<T>,
+		instantiate_permission: AccessConfigOf<T>,
 	) -> Result<(H256, u64), Error<T>> {
+		ensure!(CodeUploadAccess::<T>::get().allowed(who), Error::<T>::NotAuthorized);
 		let code_hash = sp_io::hashing::sha2_256(&code);
 		ensure!(!CodeHashToId::<T>::contains_key(code_hash), Error::<T>::CodeAlreadyExists);
 		let deposit = code.len().saturating_mul(T::CodeStorageByteDeposit::get() as _);
@@ -961,18 +1425,83 @@ impl<T: Config> Pallet<T> {
 				instrumentation_version: INSTRUMENTATION_VERSION,
 				ibc_capable,
 				refcount: 0,
+				instantiate_permission,
 			},
 		);
 		Self::deposit_event(Event::<T>::Uploaded { code_hash, code_id });
 		Ok((H256::from(code_hash), code_id))
 	}
 
+	/// Remove an unused code, on behalf of its creator if `who` is set.
+	pub fn do_remove_code(
+		who: Option<&AccountIdOf<T>>,
+		code_id: CosmwasmCodeId,
+	) -> Result<(), Error<T>> {
+		let code_info = Self::code_info(code_id)?;
+		if let Some(who) = who {
+			ensure!(code_info.creator == *who, Error::<T>::NotAuthorized);
+		}
+		ensure!(code_info.refcount == 0, Error::<T>::CodeInUse);
+		Self::do_release_code(code_id, &code_info)?;
+		CodeIdToInfo::<T>::remove(code_id);
+		Ok(())
+	}
+
+	/// Delete the code of `code_id`, except for its info, and unreserve the deposit of its upload.
+	fn do_release_code(code_id: CosmwasmCodeId, code_info: &CodeInfoOf<T>) -> Result<(), Error<T>> {
+		// Unreserve the bonded funds for this code
+		let code_len = PristineCode::<T>::decode_len(code_id).ok_or(Error::<T>::CodeNotFound)?;
+		let deposit = code_len.saturating_mul(T::CodeStorageByteDeposit::get() as _);
+		let _ = T::NativeAsset::unreserve(&code_info.creator, deposit.saturated_into());
+		PristineCode::<T>::remove(code_id);
+		InstrumentedCode::<T>::remove(code_id);
+		CodeHashToId::<T>::remove(code_info.pristine_code_hash);
+		PinnedCodes::<T>::remove(code_id);
+		module_cache::set_pinned(&(code_info.pristine_code_hash, INSTRUMENTATION_VERSION), false);
+		hp_io::cosmwasm::unpin_code(code_info.pristine_code_hash, INSTRUMENTATION_VERSION);
+		Self::deposit_event(Event::<T>::CodeRemoved { code_id });
+		Ok(())
+	}
+
+	/// Pin a code, keeping its module from being evicted from the module cache and letting the
+	/// node keep its instrumented code once loaded.
+	pub fn do_pin_code(code_id: CosmwasmCodeId) -> Result<(), Error<T>> {
+		let code_info = Self::code_info(code_id)?;
+		PinnedCodes::<T>::insert(code_id, ());
+		module_cache::set_pinned(&(code_info.pristine_code_hash, INSTRUMENTATION_VERSION), true);
+		Self::deposit_event(Event::<T>::CodePinned { code_id });
+		Ok(())
+	}
+
+	/// Unpin a code, letting its module be evicted from the module cache and its instrumented code
+	/// be dropped by the node.
+	pub fn do_unpin_code(code_id: CosmwasmCodeId) -> Result<(), Error<T>> {
+		let code_info = Self::code_info(code_id)?;
+		PinnedCodes::<T>::remove(code_id);
+		module_cache::set_pinned(&(code_info.pristine_code_hash, INSTRUMENTATION_VERSION), false);
+		hp_io::cosmwasm::unpin_code(code_info.pristine_code_hash, INSTRUMENTATION_VERSION);
+		Self::deposit_event(Event::<T>::CodeUnpinned { code_id });
+		Ok(())
+	}
+
+	/// Replace the instantiate permission of an uploaded code.
+	pub fn do_update_instantiate_config(
+		code_id: CosmwasmCodeId,
+		instantiate_permission: AccessConfigOf<T>,
+	) -> Result<(), Error<T>> {
+		CodeIdToInfo::<T>::try_mutate(code_id, |entry| {
+			let code_info = entry.as_mut().ok_or(Error::<T>::CodeNotFound)?;
+			code_info.instantiate_permission = instantiate_permission;
+			Ok(())
+		})
+	}
+
 	#[allow(clippy::too_many_arguments)]
 	pub fn do_instantiate(
 		shared: &mut CosmwasmVMShared,
 		who: AccountIdOf<T>,
 		code_identifier: CodeIdentifier,
-		salt: ContractSaltOf<T>,
+		salt: Option<ContractSaltOf<T>>,
 		admin: Option<AccountIdOf<T>>,
 		label: ContractLabelOf<T>,
 		funds: FundsOf<T>,
@@ -983,7 +1512,7 @@ impl<T: Config> Pallet<T> {
 			CodeIdentifier::CodeHash(code_hash) =>
 				CodeHashToId::<T>::try_get(code_hash).map_err(|_| Error::<T>::CodeNotFound)?,
 		};
-		setup_instantiate_call(who, code_id, &salt, admin, label)?
+		setup_instantiate_call(who, code_id, salt.as_deref().map(Vec::as_slice), admin, label)?
 			.top_level_call(shared, funds, message)
 	}
 
@@ -1017,6 +1546,18 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
+	pub fn do_sudo(
+		shared: &mut CosmwasmVMShared,
+		contract: AccountIdOf<T>,
+		message: ContractMessageOf<T>,
+	) -> Result<(), CosmwasmVMError<T>> {
+		setup_sudo_call(contract)?.top_level_call(shared, Default::default(), message)
+	}
+
+	/// Set `new_admin` as the admin of `contract`.
+	///
+	/// Fails with [`Error::NotAuthorized`] if `who` is not the current admin, or if the contract
+	/// has none.
 	pub fn do_update_admin(
 		shared: &mut CosmwasmVMShared,
 		who: AccountIdOf<T>,
@@ -1035,7 +1576,12 @@ impl<T: Config> Pallet<T> {
 					CosmwasmAccount::new(contract),
 					new_admin.map(CosmwasmAccount::new),
 				)
-				.map_err(Into::into)
+				.map_err(|e| match CosmwasmVMError::<T>::from(e) {
+					CosmwasmVMError::VirtualMachine(WasmiVMError::SystemError(
+						SystemError::MustBeAdmin | SystemError::ImmutableCantMigrate,
+					)) => Error::<T>::NotAuthorized.into(),
+					e => e,
+				})
 			},
 		)
 	}
@@ -1095,7 +1641,10 @@ impl<T: Config> Pallet<T> {
 		// Else, the contract is not a pallet. We continue with the normal wasmi vm creation
 		// process:
 		let info = Self::contract_info(&contract)?;
-		let code = Self::get_code_from_cache(shared, info.code_id)?.clone();
+		let LoadedModule { module, gas } = Self::get_module_from_cache(shared, info.code_id)?;
+		if let GasOutcome::Halt = shared.gas.charge(gas) {
+			return Err(CosmwasmVMError::OutOfGas)
+		}
 
 		log::debug!(target: "runtime::contracts", "env  : {:#?}", env);
 		log::debug!(target: "runtime::contracts", "info : {:#?}", cosmwasm_message_info);
@@ -1113,43 +1662,88 @@ impl<T: Config> Pallet<T> {
 			},
 		};
 
-		let wasmi_vm = new_wasmi_vm(code.as_slice(), vm).map_err(|_| Error::<T>::VmCreation)?;
+		let wasmi_vm = new_wasmi_vm_from_module(&module, vm).map_err(|_| Error::<T>::VmCreation)?;
 		Ok(wasmi_vm)
 	}
 
-	/// Get the code for `code_id` from the `shared_vm`'s code cache. If the code isn't in the cache
-	/// yet, then we reinstrument the code if needed and insert it into the cache.
+	/// Get the module for `code_id` from the `shared_vm`'s code cache. If the module isn't in the
+	/// cache yet, then we reinstrument the code if needed and get its prepared module from the
+	/// module cache, preparing it on a miss.
 	///
-	/// Note that it may look like we could have an invalid cache if reinstrumentation just
-	/// happend, but the `shared.cache.code` is only shared for a single tx, so it is
-	/// impossible for non-reinstrumented code to be in the `shared.code.cache`
-	pub(crate) fn get_code_from_cache(
+	/// The module cache may outlive a transaction, but its modules are identified by code hash and
+	/// instrumentation version, so that a module is never served for an outdated instrumentation.
+	/// The instrumented code of a pinned code is kept by the node across blocks, sparing its read
+	/// from storage. The gas charged for the instantiation of the module only depends on the
+	/// length of its instrumented code and on whether its code is pinned, never on whether the
+	/// module or the code was actually cached.
+	pub(crate) fn get_module_from_cache(
 		shared_vm: &mut CosmwasmVMShared,
 		code_id: CosmwasmCodeId,
-	) -> Result<&mut Vec<u8>, CosmwasmVMError<T>> {
+	) -> Result<LoadedModule, CosmwasmVMError<T>> {
 		log::debug!(target: "runtime::contracts", "Getting code for: {:?} from shared cache", code_id);
 		match shared_vm.cache.code.entry(code_id) {
 			Entry::Vacant(v) => {
 				log::debug!(target: "runtime::contracts", "Code cache miss: {:?}", code_id);
 				// Reinstrument the code if needed
-				let code = Self::do_check_for_reinstrumentation(code_id)?.map_or_else(
+				let reinstrumented_code = Self::do_check_for_reinstrumentation(code_id)?;
+				let code_info = Self::code_info(code_id)?;
+				let pinned = PinnedCodes::<T>::contains_key(code_id);
+				let code_hash = code_info.pristine_code_hash;
+				let CachedModule { module, code_len } = module_cache::get_or_prepare(
+					(code_hash, INSTRUMENTATION_VERSION),
+					pinned,
+					T::ModuleCacheSize::get(),
 					|| {
-						// map_or_else instead of map_or to prevent the code from getting loaded
-						Ok(InstrumentedCode::<T>::get(code_id)
-							.ok_or(Error::<T>::CodeNotFound)?
-							.into_inner())
+						log::debug!(target: "runtime::contracts", "Module cache miss: {:?}", code_id);
+						let code = match reinstrumented_code {
+							Some(code) => code,
+							None => Self::instrumented_code(code_id, code_hash, pinned)?,
+						};
+						let module =
+							PreparedModule::new(&code).map_err(|_| Error::<T>::VmCreation)?;
+						Ok::<_, Error<T>>(CachedModule {
+							module: Arc::new(module),
+							code_len: code.len().saturated_into(),
+						})
 					},
-					Ok::<_, Error<T>>,
 				)?;
-				Ok(v.insert(code))
+				let gas = if pinned {
+					T::WeightInfo::load_pinned_module(code_len)
+				} else {
+					T::WeightInfo::load_module(code_len)
+				}
+				.ref_time();
+				Ok(v.insert(LoadedModule { module, gas }).clone())
 			},
 			Entry::Occupied(o) => {
 				log::debug!(target: "runtime::contracts", "Code cache hit: {:?}", code_id);
-				Ok(o.into_mut())
+				Ok(o.get().clone())
 			},
 		}
 	}
 
+	/// Get the instrumented code of `code_id`, from the node if its code is pinned and was kept by
+	/// the node, from storage otherwise. The node keeps the instrumented code of a pinned code once
+	/// read from storage.
+	fn instrumented_code(
+		code_id: CosmwasmCodeId,
+		code_hash: [u8; 32],
+		pinned: bool,
+	) -> Result<Vec<u8>, Error<T>> {
+		if pinned {
+			if let Some(code) = hp_io::cosmwasm::pinned_code(code_hash, INSTRUMENTATION_VERSION) {
+				return Ok(code)
+			}
+		}
+		let code = InstrumentedCode::<T>::get(code_id)
+			.ok_or(Error::<T>::CodeNotFound)?
+			.into_inner();
+		if pinned {
+			hp_io::cosmwasm::pin_code(code_hash, INSTRUMENTATION_VERSION, &code);
+		}
+		Ok(code)
+	}
+
 	/// Abstract function to operate on a contract child trie entry.
 	pub(crate) fn with_db_entry<R>(
 		trie_id: &ContractTrieIdOf<T>,
@@ -1160,13 +1754,28 @@ impl<T: Config> Pallet<T> {
 		f(child_trie, Blake2_128Concat::hash(key))
 	}
 
+	/// Compute the gas required to read `key_len` bytes of key and `value_len` bytes of value.
+	///
+	/// Along with the flat costs charged by the VM for every storage access, this follows the
+	/// `KVGasConfig` of the Cosmos SDK used by wasmd.
+	///
+	/// Equation: len(key) x `db_read_key_byte` + len(value) x `db_read_value_byte`
+	pub(crate) fn do_db_read_bytes_gas(key_len: usize, value_len: usize) -> u64 {
+		T::WeightInfo::db_read_key_byte()
+			.ref_time()
+			.saturating_mul(key_len as u64)
+			.saturating_add(
+				T::WeightInfo::db_read_value_byte().ref_time().saturating_mul(value_len as u64),
+			)
+	}
+
 	/// Compute the gas required to read the given entry.
 	///
-	/// Equation: len(entry(trie, key)) x [`T::ContractStorageByteReadPrice`]
+	/// Equation: [`Self::do_db_read_bytes_gas`] of len(key) and len(entry(trie, key))
 	pub(crate) fn do_db_read_gas(trie_id: &ContractTrieIdOf<T>, key: &[u8]) -> u64 {
 		Self::with_db_entry(trie_id, key, |child_trie, entry| {
-			let bytes_to_read = storage::child::len(&child_trie, &entry).unwrap_or(0);
-			u64::from(bytes_to_read).saturating_mul(T::ContractStorageByteReadPrice::get().into())
+			let value_len = storage::child::len(&child_trie, &entry).unwrap_or(0);
+			Self::do_db_read_bytes_gas(key.len(), value_len as usize)
 		})
 	}
 
@@ -1197,75 +1806,163 @@ impl<T: Config> Pallet<T> {
 		}))
 	}
 
-	/// Compute the gas required to overwrite the given entry.
+	/// Compute the gas required to write the given entry, whatever the entry it overwrites.
 	///
-	/// Equation: len(entry(trie, key)) - len(value)  x [`T::ContractStorageByteWritePrice`]
-	/// With minus saturating.
-	pub(crate) fn do_db_write_gas(trie_id: &ContractTrieIdOf<T>, key: &[u8], value: &[u8]) -> u64 {
-		Self::with_db_entry(trie_id, key, |child_trie, entry| {
-			let bytes_to_write = match storage::child::len(&child_trie, &entry) {
-				Some(current_len) => (value.len() as u32).saturating_sub(current_len),
-				None => value.len() as u32,
-			};
-			u64::from(bytes_to_write).saturating_mul(T::ContractStorageByteWritePrice::get().into())
-		})
+	/// Equation: len(key) x `db_write_key_byte` + len(value) x `db_write_value_byte`
+	pub(crate) fn do_db_write_gas(key: &[u8], value: &[u8]) -> u64 {
+		T::WeightInfo::db_write_key_byte()
+			.ref_time()
+			.saturating_mul(key.len() as u64)
+			.saturating_add(
+				T::WeightInfo::db_write_value_byte()
+					.ref_time()
+					.saturating_mul(value.len() as u64),
+			)
+	}
+
+	/// Compute the gas required to record `key` in, or drop it from, the key indexes of a
+	/// contract, i.e. to write the key and its reversed key.
+	///
+	/// Equation: (len(key) + 2 x len(key) + 1) x `db_write_key_byte`
+	pub(crate) fn do_db_index_gas(key: &[u8]) -> u64 {
+		T::WeightInfo::db_write_key_byte()
+			.ref_time()
+			.saturating_mul((key.len() as u64).saturating_mul(3).saturating_add(1))
 	}
 
 	/// Write an entry from the executing contract, charging the according gas prior to actually
-	/// writing the entry.
+	/// writing the entry and recording its key in the contract indexes.
 	pub(crate) fn do_db_write(
 		vm: &mut DefaultCosmwasmVM<T>,
 		key: &[u8],
 		value: &[u8],
 	) -> Result<(), CosmwasmVMError<T>> {
-		let price = Self::do_db_write_gas(&vm.contract_info.trie_id, key, value);
+		let price = Self::do_db_write_gas(key, value).saturating_add(Self::do_db_index_gas(key));
 		vm.charge_raw(price)?;
 		Self::with_db_entry(&vm.contract_info.trie_id, key, |child_trie, entry| {
 			storage::child::put_raw(&child_trie, &entry, value)
 		});
+		Self::do_index_key(vm.contract_info.trie_id.as_ref(), key);
 		Ok(())
 	}
 
-	/// Create an empty iterator.
-	pub(crate) fn do_db_scan(vm: &mut DefaultCosmwasmVM<T>) -> Result<u32, CosmwasmVMError<T>> {
-		let iterator_id = vm.iterators.len() as u32;
-		// let child_info = Self::contract_child_trie(vm.contract_info.trie_id.as_ref());
-		vm.iterators.insert(iterator_id, Vec::new());
+	/// Compute the gas required to move an iterator to its next entry.
+	///
+	/// Equation: [`frame_system::Config::DbWeight`] read
+	pub(crate) fn do_db_next_gas() -> u64 {
+		T::DbWeight::get().reads(1).ref_time()
+	}
+
+	/// Create an iterator over the executing contract storage, restricted to `[start, end)` and
+	/// following `order`. Nothing is read until the iterator is stepped.
+	///
+	/// Fails while the background key indexing has not walked the contract yet.
+	pub(crate) fn do_db_scan(
+		vm: &mut DefaultCosmwasmVM<T>,
+		start: Option<Vec<u8>>,
+		end: Option<Vec<u8>>,
+		order: Order,
+	) -> Result<u32, CosmwasmVMError<T>> {
+		ensure!(
+			Self::is_contract_indexed(vm.contract_address.as_ref()),
+			Error::<T>::ContractNotIndexed
+		);
+		let iterator_id =
+			u32::try_from(vm.iterators.len()).map_err(|_| Error::<T>::IteratorIdOverflow)?;
+		let trie_id = vm.contract_info.trie_id.as_ref();
+		let iterator = ContractIterator::new(
+			Self::contract_index_child_trie(trie_id),
+			Self::contract_reverse_index_child_trie(trie_id),
+			start.as_deref(),
+			end.as_deref(),
+			order,
+		);
+		vm.iterators.insert(iterator_id, iterator);
 		Ok(iterator_id)
 	}
 
-	/// Return the next (reversed-key, value) pair and save the state. If the next key
-	/// is `None`, the iterator is removed from the storage.
+	/// Return the next (key, value) pair of the iterator, charging the step along with the key and
+	/// value read. Entries removed after the iterator creation are skipped. Returns `None` once the
+	/// iterator is exhausted.
 	pub(crate) fn do_db_next(
 		vm: &mut DefaultCosmwasmVM<T>,
 		iterator_id: u32,
 	) -> Result<Option<(Vec<u8>, Vec<u8>)>, CosmwasmVMError<T>> {
-		// Get the next value from the vm's iterators, based on the iterator id.
-		// Error if the iterator with id [`iterator_id`]
-		let child_info = Self::contract_child_trie(vm.contract_info.trie_id.as_ref());
-		let key_entry = vm.iterators.get_mut(&iterator_id).ok_or(Error::<T>::IteratorNotFound)?;
-		match sp_io::default_child_storage::next_key(child_info.storage_key(), key_entry) {
-			Some(key) => {
-				let reversed_key = Blake2_128Concat::reverse(&key).to_vec();
-				*key_entry = key;
-				Ok(Some((
-					reversed_key.clone(),
-					Self::do_db_read(vm, reversed_key.as_slice())?
-						.ok_or(Error::<T>::IteratorValueNotFound)?,
-				)))
-			},
-			None => Ok(None),
+		loop {
+			vm.charge_raw(Self::do_db_next_gas())?;
+			let iterator =
+				vm.iterators.get_mut(&iterator_id).ok_or(Error::<T>::IteratorNotFound)?;
+			let Some(key) = iterator.next() else { return Ok(None) };
+			if let Some(value) = Self::do_db_read(vm, &key)? {
+				return Ok(Some((key, value)));
+			}
 		}
 	}
 
-	/// Remove an entry from the executing contract, no gas is charged for this operation.
-	pub(crate) fn do_db_remove(vm: &mut DefaultCosmwasmVM<T>, key: &[u8]) {
-		let trie_id = &vm.contract_info.trie_id;
+	/// Return the next key of the iterator without reading its value, charging the step along with
+	/// the key read. Entries removed after the iterator creation are skipped. Returns `None` once
+	/// the iterator is exhausted.
+	pub(crate) fn do_db_next_key(
+		vm: &mut DefaultCosmwasmVM<T>,
+		iterator_id: u32,
+	) -> Result<Option<Vec<u8>>, CosmwasmVMError<T>> {
+		loop {
+			vm.charge_raw(Self::do_db_next_gas())?;
+			let trie_id = &vm.contract_info.trie_id;
+			let iterator =
+				vm.iterators.get_mut(&iterator_id).ok_or(Error::<T>::IteratorNotFound)?;
+			let Some(key) = iterator.next() else { return Ok(None) };
+			let exists = Self::with_db_entry(trie_id, &key, |child_trie, entry| {
+				storage::child::exists(&child_trie, &entry)
+			});
+			if exists {
+				vm.charge_raw(Self::do_db_read_bytes_gas(key.len(), 0))?;
+				return Ok(Some(key));
+			}
+		}
+	}
+
+	/// Return the next value of the iterator, charging the key and value read. Entries removed
+	/// after the iterator creation are skipped. Returns `None` once the iterator is exhausted.
+	pub(crate) fn do_db_next_value(
+		vm: &mut DefaultCosmwasmVM<T>,
+		iterator_id: u32,
+	) -> Result<Option<Vec<u8>>, CosmwasmVMError<T>> {
+		Ok(Self::do_db_next(vm, iterator_id)?.map(|(_, value)| value))
+	}
+
+	/// Compute the gas required to remove the given entry.
+	///
+	/// Equation: len(key) x `db_write_key_byte` + len(entry(trie, key)) x `db_remove_value_byte`
+	pub(crate) fn do_db_remove_gas(trie_id: &ContractTrieIdOf<T>, key: &[u8]) -> u64 {
 		Self::with_db_entry(trie_id, key, |child_trie, entry| {
-			storage::child::kill(&child_trie, &entry)
+			let value_len = storage::child::len(&child_trie, &entry).unwrap_or(0);
+			Self::do_db_write_gas(key, &[]).saturating_add(
+				T::WeightInfo::db_remove_value_byte()
+					.ref_time()
+					.saturating_mul(value_len as u64),
+			)
 		})
 	}
 
+	/// Remove an entry from the executing contract, charging the key written and the value removed
+	/// prior to actually removing the entry and dropping its key from the contract indexes.
+	///
+	/// Equation: [`Self::do_db_remove_gas`] + [`Self::do_db_index_gas`]
+	pub(crate) fn do_db_remove(
+		vm: &mut DefaultCosmwasmVM<T>,
+		key: &[u8],
+	) -> Result<(), CosmwasmVMError<T>> {
+		let price = Self::do_db_remove_gas(&vm.contract_info.trie_id, key)
+			.saturating_add(Self::do_db_index_gas(key));
+		vm.charge_raw(price)?;
+		Self::with_db_entry(&vm.contract_info.trie_id, key, |child_trie, entry| {
+			storage::child::kill(&child_trie, &entry)
+		});
+		Self::do_unindex_key(vm.contract_info.trie_id.as_ref(), key);
+		Ok(())
+	}
+
 	pub(crate) fn do_running_contract_meta(
 		vm: &mut DefaultCosmwasmVM<T>,
 	) -> CosmwasmContractMeta<CosmwasmAccount<T>> {
@@ -1286,6 +1983,27 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Retrieve the non-zero balances of an account across every registered denom, sorted by
+	/// denom.
+	///
+	/// A balance lookup is charged for each registered denom, zero balances included. The walk
+	/// stops at the denoms the remaining gas is able to pay for, running out of gas beyond.
+	pub fn do_all_balance(
+		vm: &mut DefaultCosmwasmVM<T>,
+		account: &AccountIdOf<T>,
+	) -> Result<Vec<Coin>, CosmwasmVMError<T>> {
+		let lookup = T::WeightInfo::balance().ref_time().max(1);
+		let limit = (vm.shared.gas.remaining() / lookup).saturated_into::<u32>();
+		let balances = T::AllBalances::convert((account.clone(), limit))
+			.ok_or(CosmwasmVMError::<T>::OutOfGas)?;
+		vm.charge_raw(lookup.saturating_mul(balances.len() as u64))?;
+		Ok(balances
+			.into_iter()
+			.filter(|(_, amount)| *amount != 0)
+			.map(|(denom, amount)| Coin { denom, amount: amount.into() })
+			.collect())
+	}
+
 	pub(crate) fn do_supply(denom: String) -> Result<u128, Error<T>> {
 		if denom == T::NativeDenom::get() {
 			Ok(T::NativeAsset::total_issuance().into())
@@ -1316,27 +2034,51 @@ impl<T: Config> Pallet<T> {
 		}
 		Ok(())
 	}
+
+	/// Burn the `funds` from the `from` account, reducing the total issuance of each denom.
+	pub(crate) fn do_burn(from: &AccountIdOf<T>, funds: &[Coin]) -> Result<(), Error<T>> {
+		for Coin { denom, amount } in funds {
+			let amount = amount.u128().saturated_into();
+
+			if denom == T::NativeDenom::get() {
+				T::NativeAsset::withdraw(
+					from,
+					amount,
+					WithdrawReasons::all(),
+					ExistenceRequirement::AllowDeath,
+				)
+				.map_err(|_| Error::<T>::BurnFailed)?;
+			} else {
+				let asset = Self::cosmwasm_asset_to_native_asset(denom.clone())?;
+				T::Assets::burn_from(asset, from, amount, Precision::Exact, Fortitude::Polite)
+					.map_err(|_| Error::<T>::BurnFailed)?;
+			}
+		}
+		Ok(())
+	}
 	pub(crate) fn do_continue_instantiate(
 		vm: &mut DefaultCosmwasmVM<T>,
 		CosmwasmContractMeta { code_id, admin, label }: CosmwasmContractMeta<CosmwasmAccount<T>>,
 		funds: Vec<Coin>,
-		salt: &[u8],
+		salt: Option<&[u8]>,
 		message: &[u8],
 		event_handler: &mut dyn FnMut(cosmwasm_std::Event),
-	) -> Result<Option<cosmwasm_std::Binary>, CosmwasmVMError<T>> {
+	) -> Result<(AccountIdOf<T>, Option<cosmwasm_std::Binary>), CosmwasmVMError<T>> {
 		let label = label
 			.as_bytes()
 			.to_vec()
 			.try_into()
 			.map_err(|_| crate::Error::<T>::LabelTooBig)?;
-		setup_instantiate_call(
+		let call = setup_instantiate_call(
 			vm.contract_address.clone().into_inner(),
 			code_id,
 			salt,
 			admin.map(|admin| admin.into_inner()),
 			label,
-		)?
-		.sub_call(vm.shared, funds, message, event_handler)
+		)?;
+		let contract = call.output.clone();
+		call.sub_call(vm.shared, funds, message, event_handler)
+			.map(|data| (contract, data))
 	}
 
 	pub(crate) fn do_continue_execute(
@@ -1403,7 +2145,7 @@ impl<T: Config> Pallet<T> {
 				};
 				let code_info = CodeIdToInfo::<T>::get(contract_info.code_id)
 					.ok_or(Error::<T>::CodeNotFound)?;
-				let pinned = vm.shared.cache.code.contains_key(&contract_info.code_id);
+				let pinned = PinnedCodes::<T>::contains_key(contract_info.code_id);
 				(contract_info, code_info, pinned)
 			},
 		};
@@ -1423,13 +2165,24 @@ impl<T: Config> Pallet<T> {
 		))
 	}
 
-	pub(crate) fn do_query_code_info(code_id: u64) -> Result<CodeInfoResponse, CosmwasmVMError<T>> {
+	pub(crate) fn do_query_code_info(
+		code_id: u64,
+	) -> Result<CodeInfoQueryResponse, CosmwasmVMError<T>> {
 		let code_info = CodeIdToInfo::<T>::get(code_id).ok_or(Error::<T>::CodeNotFound)?;
-		Ok(CodeInfoResponse::new(
-			code_id,
-			Addr::unchecked(Pallet::<T>::account_to_cosmwasm_addr(code_info.creator)),
-			code_info.pristine_code_hash.into(),
-		))
+		let permission = code_info.instantiate_permission.permission().into();
+		let addresses = match code_info.instantiate_permission {
+			AccessConfig::AnyOfAddresses(addresses) =>
+				addresses.into_iter().map(Pallet::<T>::account_to_cosmwasm_addr).collect(),
+			_ => Vec::new(),
+		};
+		Ok(CodeInfoQueryResponse {
+			info: CodeInfoResponse::new(
+				code_id,
+				Addr::unchecked(Pallet::<T>::account_to_cosmwasm_addr(code_info.creator)),
+				code_info.pristine_code_hash.into(),
+			),
+			instantiate_permission: AccessConfigResponse { permission, addresses },
+		})
 	}
 
 	pub(crate) fn do_continue_query(
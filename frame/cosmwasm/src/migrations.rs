@@ -0,0 +1,266 @@
+/// Adds an instantiate permission to every stored code.
+pub mod v1 {
+	use crate::{
+		types::{AccessConfig, AccountIdOf, CodeInfo},
+		CodeIdToInfo, Config, Pallet,
+	};
+	#[cfg(feature = "try-runtime")]
+	use alloc::vec::Vec;
+	use core::marker::PhantomData;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{GetStorageVersion, OnRuntimeUpgrade},
+	};
+	#[cfg(feature = "try-runtime")]
+	use sp_runtime::TryRuntimeError;
+
+	/// Code metadata prior to [`CodeInfo::instantiate_permission`].
+	#[derive(Encode, Decode)]
+	struct OldCodeInfo<AccountId> {
+		creator: AccountId,
+		pristine_code_hash: [u8; 32],
+		instrumentation_version: u16,
+		refcount: u32,
+		ibc_capable: bool,
+	}
+
+	/// Migrates `CodeIdToInfo` entries, letting everybody instantiate the existing codes.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut translated = 0u64;
+			CodeIdToInfo::<T>::translate::<OldCodeInfo<AccountIdOf<T>>, _>(|_, old| {
+				translated = translated.saturating_add(1);
+				Some(CodeInfo {
+					creator: old.creator,
+					pristine_code_hash: old.pristine_code_hash,
+					instrumentation_version: old.instrumentation_version,
+					refcount: old.refcount,
+					ibc_capable: old.ibc_capable,
+					instantiate_permission: AccessConfig::Everybody,
+				})
+			});
+			StorageVersion::new(1).put::<Pallet<T>>();
+
+			T::DbWeight::get()
+				.reads_writes(translated.saturating_add(1), translated.saturating_add(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let codes = CodeIdToInfo::<T>::iter_keys().count() as u64;
+			Ok((Pallet::<T>::on_chain_storage_version(), codes).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let (version, codes) = <(StorageVersion, u64)>::decode(&mut &state[..])
+				.map_err(|_| "MigrateToV1: invalid pre-upgrade state")?;
+			if version != 0 {
+				return Ok(());
+			}
+			ensure!(Pallet::<T>::on_chain_storage_version() == 1, "MigrateToV1: version not set");
+
+			let mut migrated = 0u64;
+			for info in CodeIdToInfo::<T>::iter_values() {
+				ensure!(
+					info.instantiate_permission == AccessConfig::Everybody,
+					"MigrateToV1: restricted instantiate permission"
+				);
+				migrated = migrated.saturating_add(1);
+			}
+			ensure!(migrated == codes, "MigrateToV1: codes lost");
+			Ok(())
+		}
+	}
+}
+
+/// Indexes the existing contracts by code.
+pub mod v2 {
+	use crate::{CodeIdToContracts, Config, ContractToInfo, Pallet};
+	#[cfg(feature = "try-runtime")]
+	use alloc::vec::Vec;
+	use core::marker::PhantomData;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{GetStorageVersion, OnRuntimeUpgrade},
+	};
+	#[cfg(feature = "try-runtime")]
+	use sp_runtime::TryRuntimeError;
+
+	/// Populates `CodeIdToContracts` out of `ContractToInfo`.
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 1 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let mut indexed = 0u64;
+			for (contract, info) in ContractToInfo::<T>::iter() {
+				CodeIdToContracts::<T>::insert(info.code_id, contract, ());
+				indexed = indexed.saturating_add(1);
+			}
+			StorageVersion::new(2).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(indexed.saturating_add(1), indexed.saturating_add(1))
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok(Pallet::<T>::on_chain_storage_version().encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let version = StorageVersion::decode(&mut &state[..])
+				.map_err(|_| "MigrateToV2: invalid pre-upgrade state")?;
+			if version != 1 {
+				return Ok(());
+			}
+			ensure!(Pallet::<T>::on_chain_storage_version() == 2, "MigrateToV2: version not set");
+
+			for (contract, info) in ContractToInfo::<T>::iter() {
+				ensure!(
+					CodeIdToContracts::<T>::contains_key(info.code_id, contract),
+					"MigrateToV2: contract not indexed"
+				);
+			}
+			Ok(())
+		}
+	}
+}
+
+/// Starts the code history of the existing contracts.
+pub mod v3 {
+	use crate::{
+		types::{
+			ContractCodeHistoryBounds, ContractCodeHistoryEntry, ContractCodeHistoryOperation,
+		},
+		Config, ContractToHistory, ContractToHistoryBounds, ContractToInfo, Pallet,
+	};
+	#[cfg(feature = "try-runtime")]
+	use alloc::vec::Vec;
+	use core::marker::PhantomData;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{GetStorageVersion, OnRuntimeUpgrade},
+	};
+	use sp_runtime::traits::SaturatedConversion;
+	#[cfg(feature = "try-runtime")]
+	use sp_runtime::TryRuntimeError;
+
+	/// Records a `Genesis` entry with the current code of every contract, as the first entry of
+	/// its code history.
+	pub struct MigrateToV3<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 2 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			let updated = frame_system::Pallet::<T>::block_number().saturated_into();
+			let mut recorded = 0u64;
+			for (contract, info) in ContractToInfo::<T>::iter() {
+				let entry = ContractCodeHistoryEntry {
+					operation: ContractCodeHistoryOperation::Genesis,
+					code_id: info.code_id,
+					updated,
+					msg: Default::default(),
+				};
+				ContractToHistory::<T>::insert(&contract, 0, entry);
+				ContractToHistoryBounds::<T>::insert(
+					&contract,
+					ContractCodeHistoryBounds { head: 0, len: 1 },
+				);
+				recorded = recorded.saturating_add(1);
+			}
+			StorageVersion::new(3).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(
+				recorded.saturating_add(1),
+				recorded.saturating_mul(2).saturating_add(1),
+			)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok(Pallet::<T>::on_chain_storage_version().encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let version = StorageVersion::decode(&mut &state[..])
+				.map_err(|_| "MigrateToV3: invalid pre-upgrade state")?;
+			if version != 2 {
+				return Ok(());
+			}
+			ensure!(Pallet::<T>::on_chain_storage_version() == 3, "MigrateToV3: version not set");
+
+			for contract in ContractToInfo::<T>::iter_keys() {
+				let bounds = ContractToHistoryBounds::<T>::get(&contract);
+				ensure!(
+					bounds.len == 1 && ContractToHistory::<T>::contains_key(&contract, bounds.head),
+					"MigrateToV3: history not started"
+				);
+			}
+			Ok(())
+		}
+	}
+}
+
+/// Builds the key indexes of the existing contracts.
+pub mod v4 {
+	use crate::{Config, KeyIndexingCursor, Pallet};
+	use alloc::vec::Vec;
+	use core::marker::PhantomData;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{GetStorageVersion, OnRuntimeUpgrade},
+	};
+	#[cfg(feature = "try-runtime")]
+	use sp_runtime::TryRuntimeError;
+
+	/// Starts the background key indexing, which records the raw keys of every contract in the
+	/// contract indexes over the idle weight of the next blocks.
+	///
+	/// Contracts not walked yet cannot be iterated until then.
+	pub struct MigrateToV4<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 3 {
+				return T::DbWeight::get().reads(1);
+			}
+
+			KeyIndexingCursor::<T>::set(Some((None, Vec::new())));
+			StorageVersion::new(4).put::<Pallet<T>>();
+
+			T::DbWeight::get().reads_writes(1, 2)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			Ok(Pallet::<T>::on_chain_storage_version().encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let version = StorageVersion::decode(&mut &state[..])
+				.map_err(|_| "MigrateToV4: invalid pre-upgrade state")?;
+			if version != 3 {
+				return Ok(());
+			}
+			ensure!(Pallet::<T>::on_chain_storage_version() == 4, "MigrateToV4: version not set");
+			ensure!(KeyIndexingCursor::<T>::exists(), "MigrateToV4: key indexing not started");
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,105 @@
+//! Cache of the prepared wasm modules.
+//!
+//! Parsing and compiling the instrumented code dominates the cost of loading a contract, so that
+//! prepared modules are kept for as long as the memory of the runtime instance lives. With the
+//! wasm executor of the node, that is a single runtime call: the extrinsics of an imported block
+//! share the cache, while every extrinsic applied when authoring a block starts with an empty one.
+//! No module is kept across blocks, but the node keeps the instrumented code of the pinned codes
+//! through `hp_io::cosmwasm`, sparing its read from storage.
+//!
+//! Whether a module is served from the cache or not never changes the charged gas, which only
+//! depends on the on-chain state of its code.
+
+use alloc::{collections::BTreeMap, sync::Arc};
+use cosmwasm_vm_wasmi::PreparedModule;
+use spin::Mutex;
+
+/// Identifies a module by the hash of its pristine code and the version of its instrumentation.
+///
+/// Code ids are not used, since the same id may point to different codes on competing forks.
+pub type ModuleKey = ([u8; 32], u16);
+
+static MODULE_CACHE: Mutex<ModuleCache> = Mutex::new(ModuleCache::new());
+
+/// A prepared module, along with the length of the instrumented code it was prepared from.
+#[derive(Clone)]
+pub struct CachedModule {
+	pub module: Arc<PreparedModule>,
+	pub code_len: u32,
+}
+
+struct CacheEntry {
+	module: CachedModule,
+	/// Pinned modules are never evicted.
+	pinned: bool,
+	/// Value of the cache clock at the last access.
+	last_used: u64,
+}
+
+/// Pinned modules, along with a least recently used cache of the unpinned ones.
+struct ModuleCache {
+	entries: BTreeMap<ModuleKey, CacheEntry>,
+	clock: u64,
+}
+
+impl ModuleCache {
+	const fn new() -> Self {
+		Self { entries: BTreeMap::new(), clock: 0 }
+	}
+
+	fn get(&mut self, key: &ModuleKey, pinned: bool) -> Option<CachedModule> {
+		self.clock = self.clock.wrapping_add(1);
+		let entry = self.entries.get_mut(key)?;
+		entry.pinned = pinned;
+		entry.last_used = self.clock;
+		Some(entry.module.clone())
+	}
+
+	fn insert(&mut self, key: ModuleKey, module: CachedModule, pinned: bool, capacity: usize) {
+		self.clock = self.clock.wrapping_add(1);
+		self.entries.insert(key, CacheEntry { module, pinned, last_used: self.clock });
+		while self.entries.values().filter(|entry| !entry.pinned).count() > capacity {
+			let least_recently_used = self
+				.entries
+				.iter()
+				.filter(|(_, entry)| !entry.pinned)
+				.min_by_key(|(_, entry)| entry.last_used)
+				.map(|(key, _)| *key);
+			match least_recently_used {
+				Some(key) => self.entries.remove(&key),
+				None => break,
+			};
+		}
+	}
+}
+
+/// Get the module of `key` from the cache, or prepare it with `prepare` and cache it.
+///
+/// At most `capacity` unpinned modules are kept, the least recently used being evicted first.
+pub fn get_or_prepare<E>(
+	key: ModuleKey,
+	pinned: bool,
+	capacity: u32,
+	prepare: impl FnOnce() -> Result<CachedModule, E>,
+) -> Result<CachedModule, E> {
+	if let Some(module) = MODULE_CACHE.lock().get(&key, pinned) {
+		return Ok(module)
+	}
+	// Do not hold the lock while preparing the module.
+	let module = prepare()?;
+	MODULE_CACHE.lock().insert(key, module.clone(), pinned, capacity as usize);
+	Ok(module)
+}
+
+/// Keep the module of `key` from being evicted, or let it be evicted again, if cached.
+pub fn set_pinned(key: &ModuleKey, pinned: bool) {
+	if let Some(entry) = MODULE_CACHE.lock().entries.get_mut(key) {
+		entry.pinned = pinned;
+	}
+}
+
+/// Drop every cached module, so that benchmarks measure the preparation of modules.
+#[cfg(feature = "runtime-benchmarks")]
+pub fn clear() {
+	MODULE_CACHE.lock().entries.clear();
+}
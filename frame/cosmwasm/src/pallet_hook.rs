@@ -87,6 +87,8 @@ where
 				refcount: u32::MAX,
 				// A pallet can choose wether to be IBC capable
 				ibc_capable,
+				// Pallet contracts are never instantiated
+				instantiate_permission: AccessConfig::Nobody,
 			},
 			contract: ContractInfo {
 				// Pallets don't need a code ID, but we do not want to clash with CosmWasm
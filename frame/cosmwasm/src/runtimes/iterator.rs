@@ -0,0 +1,239 @@
+use alloc::vec::Vec;
+use cosmwasm_std::Order;
+use frame_support::storage::child::{self, ChildInfo};
+
+/// Encode a contract key for the reversed key index, so that the encoded keys sort in the reverse
+/// order of the keys.
+///
+/// Each byte `b` of the key is written as `[0x00, !b]` and the key is terminated by `0x01`, so that
+/// a key sorts after the keys it is a prefix of. The encoded key is `2 x len(key) + 1` long.
+pub fn reverse_key(key: &[u8]) -> Vec<u8> {
+	let mut reversed = Vec::with_capacity(key.len() * 2 + 1);
+	for byte in key {
+		reversed.extend_from_slice(&[0x00, !byte]);
+	}
+	reversed.push(0x01);
+	reversed
+}
+
+/// Decode a key of the reversed key index, see [`reverse_key`].
+pub fn unreverse_key(reversed: &[u8]) -> Vec<u8> {
+	reversed.chunks_exact(2).map(|pair| !pair[1]).collect()
+}
+
+/// Contract storage iterator.
+///
+/// Contract entries are stored in the child trie under [`Blake2_128Concat`] hashed keys, so the
+/// trie order is unrelated to the order of the keys. The iterator walks a key index of the
+/// contract instead: the ordered key index, holding every raw key of the contract storage, for
+/// ascending iterators, and the reversed key index, holding every key encoded by [`reverse_key`],
+/// for descending ones.
+///
+/// Both directions are lazy: each step moves to the next key of the index and the range bound
+/// ending the walk is checked as the walk goes.
+///
+/// [`Blake2_128Concat`]: frame_support::Blake2_128Concat
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContractIterator {
+	/// Key index walked, according to the order.
+	index: ChildInfo,
+	/// Order of the walk.
+	order: Order,
+	/// Index key to resume the walk from and whether it must be yielded, `None` once exhausted.
+	cursor: Option<(Vec<u8>, bool)>,
+	/// Inclusive start of the range.
+	start: Option<Vec<u8>>,
+	/// Exclusive end of the range.
+	end: Option<Vec<u8>>,
+}
+
+impl ContractIterator {
+	/// Create an iterator over the keys in `[start, end)`, following the given `order`. `index`
+	/// is the ordered key index of the contract and `reverse_index` its reversed key index.
+	///
+	/// Matches the `Storage::range` semantic of `cosmwasm-std`: `start` is inclusive, `end` is
+	/// exclusive and both are optional. A range where `start >= end` is empty.
+	pub fn new(
+		index: ChildInfo,
+		reverse_index: ChildInfo,
+		start: Option<&[u8]>,
+		end: Option<&[u8]>,
+		order: Order,
+	) -> Self {
+		let (index, cursor) = match order {
+			Order::Ascending => (index, (start.unwrap_or_default().to_vec(), true)),
+			// Keys below `end` are the ones whose reversed key is above the reversed `end`.
+			Order::Descending => match end {
+				Some(end) => (reverse_index, (reverse_key(end), false)),
+				None => (reverse_index, (Vec::new(), true)),
+			},
+		};
+		Self {
+			index,
+			order,
+			cursor: Some(cursor),
+			start: start.map(<[u8]>::to_vec),
+			end: end.map(<[u8]>::to_vec),
+		}
+	}
+}
+
+impl Iterator for ContractIterator {
+	type Item = Vec<u8>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let (from, inclusive) = self.cursor.take()?;
+		let index_key = if inclusive && child::exists(&self.index, &from) {
+			from
+		} else {
+			sp_io::default_child_storage::next_key(self.index.storage_key(), &from)?
+		};
+		let (key, in_range) = match self.order {
+			Order::Ascending => {
+				let in_range = self.end.as_ref().map_or(true, |end| index_key < *end);
+				(index_key.clone(), in_range)
+			},
+			Order::Descending => {
+				let key = unreverse_key(&index_key);
+				let in_range = self.start.as_ref().map_or(true, |start| key >= *start);
+				(key, in_range)
+			},
+		};
+		if !in_range {
+			return None;
+		}
+		self.cursor = Some((index_key, false));
+		Some(key)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use alloc::vec;
+
+	fn put(key: &[u8]) {
+		child::put_raw(&ChildInfo::new_default(b"index"), key, &[]);
+		child::put_raw(&ChildInfo::new_default(b"rindex"), &reverse_key(key), &[]);
+	}
+
+	fn kill(key: &[u8]) {
+		child::kill(&ChildInfo::new_default(b"index"), key);
+		child::kill(&ChildInfo::new_default(b"rindex"), &reverse_key(key));
+	}
+
+	fn iterator(start: Option<&[u8]>, end: Option<&[u8]>, order: Order) -> ContractIterator {
+		ContractIterator::new(
+			ChildInfo::new_default(b"index"),
+			ChildInfo::new_default(b"rindex"),
+			start,
+			end,
+			order,
+		)
+	}
+
+	fn range(start: Option<&[u8]>, end: Option<&[u8]>, order: Order) -> Vec<Vec<u8>> {
+		sp_io::TestExternalities::default().execute_with(|| {
+			for key in [b"foo".as_slice(), b"ant", b"ze"] {
+				put(key);
+			}
+			iterator(start, end, order).collect()
+		})
+	}
+
+	#[test]
+	fn reversed_keys_sort_in_reverse() {
+		let keys = [b"".as_slice(), b"\0", b"\0\0", b"a", b"a\0", b"ab", b"a\xff", b"b", b"\xff"];
+		for pair in keys.windows(2) {
+			assert!(reverse_key(pair[0]) > reverse_key(pair[1]));
+		}
+		for key in keys {
+			assert_eq!(unreverse_key(&reverse_key(key)), key);
+		}
+	}
+
+	#[test]
+	fn unbounded() {
+		assert_eq!(
+			range(None, None, Order::Ascending),
+			vec![b"ant".to_vec(), b"foo".to_vec(), b"ze".to_vec()]
+		);
+		assert_eq!(
+			range(None, None, Order::Descending),
+			vec![b"ze".to_vec(), b"foo".to_vec(), b"ant".to_vec()]
+		);
+	}
+
+	#[test]
+	fn bounded() {
+		assert_eq!(range(Some(b"f"), Some(b"n"), Order::Ascending), vec![b"foo".to_vec()]);
+		assert_eq!(
+			range(Some(b"air"), Some(b"loop"), Order::Descending),
+			vec![b"foo".to_vec(), b"ant".to_vec()]
+		);
+		assert_eq!(range(Some(b"foo"), Some(b"ze"), Order::Ascending), vec![b"foo".to_vec()]);
+		assert_eq!(range(Some(b"foo"), Some(b"ze"), Order::Descending), vec![b"foo".to_vec()]);
+	}
+
+	#[test]
+	fn bounded_empty() {
+		assert!(range(Some(b"foo"), Some(b"foo"), Order::Ascending).is_empty());
+		assert!(range(Some(b"foo"), Some(b"foo"), Order::Descending).is_empty());
+		assert!(range(Some(b"z"), Some(b"a"), Order::Ascending).is_empty());
+		assert!(range(Some(b"z"), Some(b"a"), Order::Descending).is_empty());
+	}
+
+	#[test]
+	fn half_bounded() {
+		assert_eq!(
+			range(Some(b"f"), None, Order::Ascending),
+			vec![b"foo".to_vec(), b"ze".to_vec()]
+		);
+		assert_eq!(
+			range(Some(b"f"), None, Order::Descending),
+			vec![b"ze".to_vec(), b"foo".to_vec()]
+		);
+		assert_eq!(range(None, Some(b"f"), Order::Ascending), vec![b"ant".to_vec()]);
+		assert_eq!(
+			range(None, Some(b"no"), Order::Descending),
+			vec![b"foo".to_vec(), b"ant".to_vec()]
+		);
+	}
+
+	#[test]
+	fn prefixes() {
+		sp_io::TestExternalities::default().execute_with(|| {
+			for key in [b"a".as_slice(), b"ab", b"a\0", b"b"] {
+				put(key);
+			}
+			assert_eq!(
+				iterator(Some(b"a"), Some(b"b"), Order::Descending).collect::<Vec<_>>(),
+				vec![b"ab".to_vec(), b"a\0".to_vec(), b"a".to_vec()]
+			);
+		});
+	}
+
+	#[test]
+	fn lazy() {
+		let walks: [(_, &[u8], &[u8]); 2] =
+			[(Order::Ascending, b"ant", b"ze"), (Order::Descending, b"ze", b"ant")];
+		for (order, first, last) in walks {
+			sp_io::TestExternalities::default().execute_with(|| {
+				for key in [b"foo".as_slice(), b"ant", b"ze"] {
+					put(key);
+				}
+				kill(last);
+
+				let mut iterator = iterator(None, None, order);
+				assert_eq!(iterator.next(), Some(first.to_vec()));
+
+				// Entries written and removed during the walk are observed.
+				kill(b"foo");
+				put(last);
+				assert_eq!(iterator.next(), Some(last.to_vec()));
+				assert_eq!(iterator.next(), None);
+				assert_eq!(iterator.next(), None);
+			});
+		}
+	}
+}
@@ -1,2 +1,3 @@
 pub mod abstraction;
+pub mod iterator;
 pub mod vm;
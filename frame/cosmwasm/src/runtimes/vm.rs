@@ -1,8 +1,21 @@
-use super::abstraction::{CanonicalCosmwasmAccount, CosmwasmAccount, Gas};
-use crate::{runtimes::abstraction::GasOutcome, types::*, weights::WeightInfo, Config, Pallet};
-use alloc::{borrow::ToOwned, collections::btree_map::BTreeMap, string::String, vec::Vec};
+use super::{
+	abstraction::{CanonicalCosmwasmAccount, CosmwasmAccount, Gas},
+	iterator::ContractIterator,
+};
+use crate::{
+	any_msg_handler::AnyMsgHandler, custom_handler::CustomHandler, grpc_query::GrpcQueryRouter,
+	runtimes::abstraction::GasOutcome, types::*, weights::WeightInfo, Config, Event, Pallet,
+};
+use alloc::{
+	borrow::ToOwned,
+	collections::btree_map::BTreeMap,
+	string::{String, ToString},
+	sync::Arc,
+	vec,
+	vec::Vec,
+};
 use core::marker::{Send, Sync};
-use cosmwasm_std::{CodeInfoResponse, Coin, ContractInfoResponse, Empty, Env, MessageInfo};
+use cosmwasm_std::{Coin, ContractInfoResponse, Env, MessageInfo};
 use cosmwasm_vm::{
 	executor::ExecutorError,
 	has::Has,
@@ -12,7 +25,7 @@ use cosmwasm_vm::{
 	vm::{VMBase, VmGas},
 };
 use cosmwasm_vm_wasmi::{
-	OwnedWasmiVM, WasmiContext, WasmiInput, WasmiModule, WasmiOutput, WasmiVMError,
+	OwnedWasmiVM, PreparedModule, WasmiContext, WasmiInput, WasmiModule, WasmiOutput, WasmiVMError,
 };
 use frame_support::traits::tokens::Preservation;
 use sp_runtime::DispatchError;
@@ -76,6 +89,7 @@ pub enum CosmwasmVMError<T: Config> {
 	Rpc(String),
 	Ibc(String),
 	Xcm(String),
+	Stargate(String),
 	AssetConversion,
 	Precompile,
 }
@@ -142,11 +156,20 @@ pub enum InitialStorageMutability {
 	ReadWrite,
 }
 
+/// Module loaded by a VM.
+#[derive(Clone)]
+pub struct LoadedModule {
+	/// The prepared module, ready to be instantiated.
+	pub module: Arc<PreparedModule>,
+	/// Gas charged whenever the module is instantiated.
+	pub gas: u64,
+}
+
 /// VM shared cache
 #[derive(Clone, Default)]
 pub struct CosmwasmVMCache {
-	/// Code cache, a mapping from an identifier to it's code.
-	pub code: BTreeMap<CosmwasmCodeId, Vec<u8>>,
+	/// Code cache, a mapping from an identifier to it's loaded module.
+	pub code: BTreeMap<CosmwasmCodeId, LoadedModule>,
 }
 
 /// VM shared state
@@ -208,8 +231,8 @@ pub struct CosmwasmVM<'a, T: Config> {
 	pub contract_info: ContractInfoOf<T>,
 	/// State shared across all contracts within a single transaction.
 	pub shared: &'a mut CosmwasmVMShared,
-	/// Iterator id's to corresponding keys. Keys are used to get the next key.
-	pub iterators: BTreeMap<u32, Vec<u8>>,
+	/// Iterator id's to corresponding contract storage iterators.
+	pub iterators: BTreeMap<u32, ContractIterator>,
 	/// Actual contract runtime
 	pub contract_runtime: ContractBackend,
 }
@@ -255,9 +278,10 @@ impl<'a, T: Config> CosmwasmVM<'a, T> {
 impl<'a, T: Config + Send + Sync> VMBase for CosmwasmVM<'a, T> {
 	type Input<'x> = WasmiInput<OwnedWasmiVM<Self>>;
 	type Output<'x> = WasmiOutput<OwnedWasmiVM<Self>>;
-	type QueryCustom = Empty;
-	type MessageCustom = Empty;
+	type QueryCustom = CustomQueryOf<T>;
+	type MessageCustom = CustomMessageOf<T>;
 	type ContractMeta = CosmwasmContractMeta<CosmwasmAccount<T>>;
+	type CodeInfo = CodeInfoQueryResponse;
 	type Address = CosmwasmAccount<T>;
 	type CanonicalAddress = CanonicalCosmwasmAccount<T>;
 	type StorageKey = Vec<u8>;
@@ -271,12 +295,12 @@ impl<'a, T: Config + Send + Sync> VMBase for CosmwasmVM<'a, T> {
 
 	fn db_scan(
 		&mut self,
-		_start: Option<Self::StorageKey>,
-		_end: Option<Self::StorageKey>,
-		_order: cosmwasm_std::Order,
+		start: Option<Self::StorageKey>,
+		end: Option<Self::StorageKey>,
+		order: cosmwasm_std::Order,
 	) -> Result<u32, Self::Error> {
 		log::debug!(target: "runtime::contracts", "db_scan");
-		Pallet::<T>::do_db_scan(self)
+		Pallet::<T>::do_db_scan(self, start, end, order)
 	}
 
 	fn db_next(
@@ -290,6 +314,19 @@ impl<'a, T: Config + Send + Sync> VMBase for CosmwasmVM<'a, T> {
 		}
 	}
 
+	fn db_next_key(&mut self, iterator_id: u32) -> Result<Option<Self::StorageKey>, Self::Error> {
+		log::debug!(target: "runtime::contracts", "db_next_key");
+		Pallet::<T>::do_db_next_key(self, iterator_id)
+	}
+
+	fn db_next_value(
+		&mut self,
+		iterator_id: u32,
+	) -> Result<Option<Self::StorageValue>, Self::Error> {
+		log::debug!(target: "runtime::contracts", "db_next_value");
+		Pallet::<T>::do_db_next_value(self, iterator_id)
+	}
+
 	fn set_contract_meta(
 		&mut self,
 		address: Self::Address,
@@ -347,7 +384,15 @@ impl<'a, T: Config + Send + Sync> VMBase for CosmwasmVM<'a, T> {
 		event_handler: &mut dyn FnMut(cosmwasm_std::Event),
 	) -> Result<(Self::Address, Option<cosmwasm_std::Binary>), Self::Error> {
 		log::debug!(target: "runtime::contracts", "continue_instantiate");
-		self.continue_instantiate2(contract_meta, funds, b"salt", message, event_handler)
+		Pallet::<T>::do_continue_instantiate(
+			self,
+			contract_meta,
+			funds,
+			None,
+			message,
+			event_handler,
+		)
+		.map(|(contract, data)| (CosmwasmAccount::new(contract), data))
 	}
 
 	fn continue_instantiate2(
@@ -363,11 +408,11 @@ impl<'a, T: Config + Send + Sync> VMBase for CosmwasmVM<'a, T> {
 			self,
 			contract_meta,
 			funds,
-			salt,
+			Some(salt),
 			message,
 			event_handler,
 		)
-		.map(|r| (self.contract_address.clone(), r))
+		.map(|(contract, data)| (CosmwasmAccount::new(contract), data))
 	}
 
 	fn continue_migrate(
@@ -382,20 +427,47 @@ impl<'a, T: Config + Send + Sync> VMBase for CosmwasmVM<'a, T> {
 
 	fn query_custom(
 		&mut self,
-		_: Self::QueryCustom,
+		query: Self::QueryCustom,
+	) -> Result<cosmwasm_std::SystemResult<cosmwasm_vm::executor::CosmwasmQueryResult>, Self::Error>
+	{
+		log::debug!(target: "runtime::contracts", "query_custom: {:?}", query);
+		T::CustomHandler::query(self, query)
+	}
+
+	fn query_grpc(
+		&mut self,
+		path: String,
+		data: cosmwasm_std::Binary,
 	) -> Result<cosmwasm_std::SystemResult<cosmwasm_vm::executor::CosmwasmQueryResult>, Self::Error>
 	{
-		log::debug!(target: "runtime::contracts", "query_custom");
-		Err(CosmwasmVMError::Unsupported)
+		log::debug!(target: "runtime::contracts", "query_grpc: {}", path);
+		Ok(match T::GrpcQueryRouter::route(self, &path, &data)? {
+			Some(response) =>
+				cosmwasm_std::SystemResult::Ok(response.map(cosmwasm_std::Binary::new).into()),
+			None =>
+				cosmwasm_std::SystemResult::Err(cosmwasm_std::SystemError::UnsupportedRequest {
+					kind: path,
+				}),
+		})
 	}
 
 	fn message_custom(
 		&mut self,
-		_: Self::MessageCustom,
-		_: &mut dyn FnMut(cosmwasm_std::Event),
+		message: Self::MessageCustom,
+		event_handler: &mut dyn FnMut(cosmwasm_std::Event),
+	) -> Result<Option<cosmwasm_std::Binary>, Self::Error> {
+		log::debug!(target: "runtime::contracts", "message_custom: {:?}", message);
+		T::CustomHandler::message(self, message, event_handler)
+	}
+
+	fn message_any(
+		&mut self,
+		type_url: String,
+		value: cosmwasm_std::Binary,
+		event_handler: &mut dyn FnMut(cosmwasm_std::Event),
 	) -> Result<Option<cosmwasm_std::Binary>, Self::Error> {
-		log::debug!(target: "runtime::contracts", "message_custom");
-		Err(CosmwasmVMError::Unsupported)
+		log::debug!(target: "runtime::contracts", "message_any: {}", type_url);
+		T::AnyMsgHandler::handle(self, &type_url, &value, event_handler)
 	}
 
 	fn query_raw(
@@ -416,7 +488,25 @@ impl<'a, T: Config + Send + Sync> VMBase for CosmwasmVM<'a, T> {
 
 	fn burn(&mut self, funds: &[Coin]) -> Result<(), Self::Error> {
 		log::debug!(target: "runtime::contracts", "burn: {:#?}", funds);
-		Err(CosmwasmVMError::NotImplemented)
+		let burner = self.contract_address.as_ref();
+		Pallet::<T>::do_burn(burner, funds)?;
+		Pallet::<T>::deposit_event(Event::<T>::Emitted {
+			contract: burner.clone(),
+			ty: b"burn".to_vec(),
+			attributes: vec![
+				(b"burner".to_vec(), String::from(self.contract_address.clone()).into_bytes()),
+				(
+					b"amount".to_vec(),
+					funds
+						.iter()
+						.map(ToString::to_string)
+						.collect::<Vec<_>>()
+						.join(",")
+						.into_bytes(),
+				),
+			],
+		});
+		Ok(())
 	}
 
 	fn balance(&mut self, account: &Self::Address, denom: String) -> Result<Coin, Self::Error> {
@@ -427,7 +517,7 @@ impl<'a, T: Config + Send + Sync> VMBase for CosmwasmVM<'a, T> {
 
 	fn all_balance(&mut self, account: &Self::Address) -> Result<Vec<Coin>, Self::Error> {
 		log::debug!(target: "runtime::contracts", "all balance: {}", String::from(account.clone()));
-		Err(CosmwasmVMError::Unsupported)
+		Pallet::<T>::do_all_balance(self, account.as_ref())
 	}
 
 	fn supply(&mut self, denom: String) -> Result<Coin, Self::Error> {
@@ -443,10 +533,7 @@ impl<'a, T: Config + Send + Sync> VMBase for CosmwasmVM<'a, T> {
 		Pallet::<T>::do_query_contract_info(self, address.into_inner())
 	}
 
-	fn query_code_info(
-		&mut self,
-		code_id: CosmwasmCodeId,
-	) -> Result<CodeInfoResponse, Self::Error> {
+	fn query_code_info(&mut self, code_id: CosmwasmCodeId) -> Result<Self::CodeInfo, Self::Error> {
 		log::debug!(target: "runtime::contracts", "query_code_info");
 		Pallet::<T>::do_query_code_info(code_id)
 	}
@@ -483,7 +570,7 @@ impl<'a, T: Config + Send + Sync> VMBase for CosmwasmVM<'a, T> {
 		if self.shared.storage_is_readonly() {
 			Err(CosmwasmVMError::ReadOnlyViolation)
 		} else {
-			Pallet::<T>::do_db_remove(self, &key);
+			Pallet::<T>::do_db_remove(self, &key)?;
 			Ok(())
 		}
 	}
@@ -530,9 +617,22 @@ impl<'a, T: Config + Send + Sync> VMBase for CosmwasmVM<'a, T> {
 			VmGas::DbRemove => T::WeightInfo::db_remove().ref_time(),
 			VmGas::DbScan => T::WeightInfo::db_scan().ref_time(),
 			VmGas::DbNext => T::WeightInfo::db_next().ref_time(),
+			VmGas::DbNextKey => T::WeightInfo::db_next_key().ref_time(),
+			VmGas::DbNextValue => T::WeightInfo::db_next_value().ref_time(),
 			VmGas::Balance => T::WeightInfo::balance().ref_time(),
+			VmGas::AllBalance => T::WeightInfo::balance().ref_time(),
 			VmGas::Secp256k1Verify => T::WeightInfo::secp256k1_verify().ref_time(),
 			VmGas::Secp256k1RecoverPubkey => T::WeightInfo::secp256k1_recover_pubkey().ref_time(),
+			VmGas::Secp256r1Verify => T::WeightInfo::secp256r1_verify().ref_time(),
+			VmGas::Secp256r1RecoverPubkey => T::WeightInfo::secp256r1_recover_pubkey().ref_time(),
+			VmGas::Bls12381AggregateG1 { nb_of_points } =>
+				T::WeightInfo::bls12_381_aggregate_g1(nb_of_points).ref_time(),
+			VmGas::Bls12381AggregateG2 { nb_of_points } =>
+				T::WeightInfo::bls12_381_aggregate_g2(nb_of_points).ref_time(),
+			VmGas::Bls12381PairingEquality { nb_of_pairs } =>
+				T::WeightInfo::bls12_381_pairing_equality(nb_of_pairs).ref_time(),
+			VmGas::Bls12381HashToG1 => T::WeightInfo::bls12_381_hash_to_g1().ref_time(),
+			VmGas::Bls12381HashToG2 => T::WeightInfo::bls12_381_hash_to_g2().ref_time(),
 			VmGas::Ed25519Verify => T::WeightInfo::ed25519_verify().ref_time(),
 			VmGas::Ed25519BatchVerify => T::WeightInfo::ed25519_batch_verify().ref_time(),
 			VmGas::AddrValidate => T::WeightInfo::addr_validate().ref_time(),
@@ -549,6 +649,7 @@ impl<'a, T: Config + Send + Sync> VMBase for CosmwasmVM<'a, T> {
 			VmGas::ContinueQuery => T::WeightInfo::continue_query().ref_time(),
 			VmGas::ContinueReply => T::WeightInfo::continue_reply().ref_time(),
 			VmGas::QueryRaw => T::WeightInfo::query_raw().ref_time(),
+			VmGas::QueryGrpc => T::WeightInfo::query_grpc().ref_time(),
 			VmGas::QueryContractInfo => T::WeightInfo::query_contract_info().ref_time(),
 			VmGas::QueryCodeInfo => T::WeightInfo::query_code_info().ref_time(),
 			_ => 1_u64,
@@ -582,6 +683,10 @@ impl<'a, T: Config + Send + Sync> VMBase for CosmwasmVM<'a, T> {
 		}
 	}
 
+	fn gas_remaining(&mut self) -> Result<u64, Self::Error> {
+		Ok(self.shared.gas.remaining())
+	}
+
 	fn secp256k1_verify(
 		&mut self,
 		message_hash: &[u8],
@@ -602,6 +707,67 @@ impl<'a, T: Config + Send + Sync> VMBase for CosmwasmVM<'a, T> {
 		Ok(Pallet::<T>::do_secp256k1_recover_pubkey(message_hash, signature, recovery_param))
 	}
 
+	fn secp256r1_verify(
+		&mut self,
+		message_hash: &[u8],
+		signature: &[u8],
+		public_key: &[u8],
+	) -> Result<bool, Self::Error> {
+		log::debug!(target: "runtime::contracts", "secp256r1_verify");
+		Ok(Pallet::<T>::do_secp256r1_verify(message_hash, signature, public_key))
+	}
+
+	fn secp256r1_recover_pubkey(
+		&mut self,
+		message_hash: &[u8],
+		signature: &[u8],
+		recovery_param: u8,
+	) -> Result<Result<Vec<u8>, ()>, Self::Error> {
+		log::debug!(target: "runtime::contracts", "secp256r1_recover_pubkey");
+		Ok(Pallet::<T>::do_secp256r1_recover_pubkey(message_hash, signature, recovery_param))
+	}
+
+	fn bls12_381_aggregate_g1(&mut self, g1s: &[u8]) -> Result<Result<Vec<u8>, u32>, Self::Error> {
+		log::debug!(target: "runtime::contracts", "bls12_381_aggregate_g1");
+		Ok(Pallet::<T>::do_bls12_381_aggregate_g1(g1s))
+	}
+
+	fn bls12_381_aggregate_g2(&mut self, g2s: &[u8]) -> Result<Result<Vec<u8>, u32>, Self::Error> {
+		log::debug!(target: "runtime::contracts", "bls12_381_aggregate_g2");
+		Ok(Pallet::<T>::do_bls12_381_aggregate_g2(g2s))
+	}
+
+	fn bls12_381_pairing_equality(
+		&mut self,
+		ps: &[u8],
+		qs: &[u8],
+		r: &[u8],
+		s: &[u8],
+	) -> Result<Result<bool, u32>, Self::Error> {
+		log::debug!(target: "runtime::contracts", "bls12_381_pairing_equality");
+		Ok(Pallet::<T>::do_bls12_381_pairing_equality(ps, qs, r, s))
+	}
+
+	fn bls12_381_hash_to_g1(
+		&mut self,
+		hash_function: u32,
+		msg: &[u8],
+		dst: &[u8],
+	) -> Result<Result<Vec<u8>, u32>, Self::Error> {
+		log::debug!(target: "runtime::contracts", "bls12_381_hash_to_g1");
+		Ok(Pallet::<T>::do_bls12_381_hash_to_g1(hash_function, msg, dst))
+	}
+
+	fn bls12_381_hash_to_g2(
+		&mut self,
+		hash_function: u32,
+		msg: &[u8],
+		dst: &[u8],
+	) -> Result<Result<Vec<u8>, u32>, Self::Error> {
+		log::debug!(target: "runtime::contracts", "bls12_381_hash_to_g2");
+		Ok(Pallet::<T>::do_bls12_381_hash_to_g2(hash_function, msg, dst))
+	}
+
 	fn ed25519_verify(
 		&mut self,
 		message: &[u8],
@@ -1,8 +1,11 @@
-use crate::{runtimes::vm::CosmwasmVM, Config};
+use crate::{custom_handler::CustomHandler, runtimes::vm::CosmwasmVM, Config};
+use alloc::{string::String, vec::Vec};
 use codec::{Decode, Encode, MaxEncodedLen};
+use cosmwasm_std::CodeInfoResponse;
 use cosmwasm_vm::system::CosmwasmCodeId;
-use frame_support::{BoundedBTreeMap, BoundedVec};
+use frame_support::{traits::ConstU32, BoundedBTreeMap, BoundedVec};
 use scale_info::TypeInfo;
+use serde::{Deserialize, Serialize};
 
 pub type DefaultCosmwasmVM<'a, T> = CosmwasmVM<'a, T>;
 pub type KeepAlive = bool;
@@ -25,6 +28,14 @@ pub type AssetIdOf<T> = <T as Config>::AssetId;
 pub type BalanceOf<T> = <T as Config>::Balance;
 pub type ContractInfoOf<T> = ContractInfo<AccountIdOf<T>, ContractLabelOf<T>, ContractTrieIdOf<T>>;
 pub type CodeInfoOf<T> = CodeInfo<AccountIdOf<T>>;
+pub type ContractCodeHistoryEntryOf<T> = ContractCodeHistoryEntry<ContractMessageOf<T>>;
+/// ICS-24 host identifiers are at most 128 characters long.
+pub type IbcPortId = BoundedVec<u8, ConstU32<128>>;
+/// Upper bound on the number of addresses an [`AccessConfig::AnyOfAddresses`] can hold.
+pub type MaxAccessConfigAddresses = ConstU32<32>;
+pub type AccessConfigOf<T> = AccessConfig<AccountIdOf<T>>;
+pub type CustomQueryOf<T> = <<T as Config>::CustomHandler as CustomHandler<T>>::Query;
+pub type CustomMessageOf<T> = <<T as Config>::CustomHandler as CustomHandler<T>>::Message;
 
 #[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
 pub enum EntryPoint {
@@ -38,6 +49,7 @@ pub enum EntryPoint {
 	IbcPacketTimeout,
 	IbcPacketReceive,
 	IbcPacketAck,
+	Sudo,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, TypeInfo, Debug)]
@@ -68,6 +80,58 @@ pub struct CodeInfo<AccountId> {
 	/// Wether the contract export IBC functions and is consequently able to be called back by IBC
 	/// operations.
 	pub ibc_capable: bool,
+	/// Who is allowed to instantiate contracts from this code.
+	pub instantiate_permission: AccessConfig<AccountId>,
+}
+
+/// Access control applied to a stored code.
+#[derive(
+	Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, Debug, Serialize, Deserialize,
+)]
+pub enum AccessConfig<AccountId> {
+	/// Nobody is allowed.
+	Nobody,
+	/// Everybody is allowed.
+	Everybody,
+	/// Only the listed addresses are allowed.
+	AnyOfAddresses(BoundedVec<AccountId, MaxAccessConfigAddresses>),
+}
+
+impl<AccountId> AccessConfig<AccountId> {
+	/// Name of the access type, as displayed by wasmd.
+	pub fn permission(&self) -> &'static str {
+		match self {
+			AccessConfig::Nobody => "Nobody",
+			AccessConfig::Everybody => "Everybody",
+			AccessConfig::AnyOfAddresses(_) => "AnyOfAddresses",
+		}
+	}
+}
+
+impl<AccountId: PartialEq> AccessConfig<AccountId> {
+	/// Whether `actor` is granted access by this configuration.
+	pub fn allowed(&self, actor: &AccountId) -> bool {
+		match self {
+			AccessConfig::Nobody => false,
+			AccessConfig::Everybody => true,
+			AccessConfig::AnyOfAddresses(addresses) => addresses.contains(actor),
+		}
+	}
+}
+
+/// Answer to `WasmQuery::CodeInfo`, extended with the instantiate permission of the code.
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+pub struct CodeInfoQueryResponse {
+	#[serde(flatten)]
+	pub info: CodeInfoResponse,
+	pub instantiate_permission: AccessConfigResponse,
+}
+
+/// JSON representation of an [`AccessConfig`].
+#[derive(Clone, PartialEq, Eq, Serialize, Debug)]
+pub struct AccessConfigResponse {
+	pub permission: String,
+	pub addresses: Vec<String>,
 }
 
 /// Contract metadata.
@@ -85,3 +149,36 @@ pub struct ContractInfo<AccountId, Label, TrieId> {
 	/// Contract label defined by the instantiator.
 	pub label: Label,
 }
+
+/// Operation which changed the code of a contract.
+#[derive(Copy, Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, Debug)]
+pub enum ContractCodeHistoryOperation {
+	/// The contract was instantiated.
+	Init,
+	/// The contract was migrated to another code.
+	Migrate,
+	/// The contract existed before its history was recorded.
+	Genesis,
+}
+
+/// Entry of the code history of a contract.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, Debug)]
+pub struct ContractCodeHistoryEntry<Message> {
+	/// Operation which set the code.
+	pub operation: ContractCodeHistoryOperation,
+	/// The code the contract is baked by from this entry on.
+	pub code_id: CosmwasmCodeId,
+	/// Block height at which the operation happened.
+	pub updated: u64,
+	/// Message passed to the `instantiate` or `migrate` entrypoint.
+	pub msg: Message,
+}
+
+/// Entries of the code history of a contract kept in storage, by sequence number.
+#[derive(Copy, Clone, Default, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, Debug)]
+pub struct ContractCodeHistoryBounds {
+	/// Sequence number of the oldest entry kept.
+	pub head: u32,
+	/// Number of entries kept.
+	pub len: u32,
+}
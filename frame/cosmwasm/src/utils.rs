@@ -1,18 +1,30 @@
 use alloc::{string::String, vec::Vec};
 use cosmwasm_std::Coin;
-use cosmwasm_vm::system::CosmwasmContractMeta;
+use cosmwasm_vm::system::{CosmwasmCodeId, CosmwasmContractMeta};
+use frame_support::{ensure, storage::child, Blake2_128Concat, StorageHasher};
 use sp_core::storage::ChildInfo;
 use sp_runtime::traits::{Convert, Hash};
 
 use crate::{
 	runtimes::{
 		abstraction::{CanonicalCosmwasmAccount, CosmwasmAccount, VMPallet},
+		iterator::reverse_key,
 		vm::CosmwasmVMError,
 	},
-	types::{AccountIdOf, AssetIdOf, BalanceOf, ContractInfoOf, ContractTrieIdOf},
-	Config, ContractToInfo, Error, Pallet,
+	types::{
+		AccessConfigOf, AccountIdOf, AssetIdOf, BalanceOf, CodeInfoOf, ContractCodeHistoryEntryOf,
+		ContractCodeOf, ContractInfoOf, ContractTrieIdOf,
+	},
+	CodeIdToContracts, CodeIdToInfo, CodeUploadAccess, Config, ContractToHistory,
+	ContractToHistoryBounds, ContractToInfo, CurrentCodeId, Error, Pallet, PinnedCodes,
+	PristineCode,
 };
 
+/// Suffix appended to a contract trie id to derive the child trie of its ordered key index.
+const CONTRACT_INDEX_SUFFIX: &[u8] = b":index";
+/// Suffix appended to a contract trie id to derive the child trie of its reversed key index.
+const CONTRACT_REVERSE_INDEX_SUFFIX: &[u8] = b":rindex";
+
 impl<T: Config> Pallet<T> {
 	pub(crate) fn derive_contract_address(
 		creator: &AccountIdOf<T>,
@@ -41,6 +53,22 @@ impl<T: Config> Pallet<T> {
 		Pallet::<T>::canonical_addr_to_account(address).map_err(|_| Error::<T>::InvalidAccount)
 	}
 
+	/// Classic contract address generation, compatible with wasmd's `BuildContractAddressClassic`.
+	pub(crate) fn derive_contract_address_classic(
+		code_id: CosmwasmCodeId,
+		instance_id: u64,
+	) -> Result<AccountIdOf<T>, Error<T>> {
+		let module_hash = sp_io::hashing::sha2_256(b"module");
+
+		let mut key = Vec::<u8>::from(module_hash);
+		key.extend_from_slice(b"wasm\0");
+		key.extend_from_slice(&code_id.to_be_bytes());
+		key.extend_from_slice(&instance_id.to_be_bytes());
+
+		let address = sp_io::hashing::sha2_256(&key).into();
+		Pallet::<T>::canonical_addr_to_account(address).map_err(|_| Error::<T>::InvalidAccount)
+	}
+
 	/// Deterministic contract trie id generation.
 	pub(crate) fn derive_contract_trie_id(
 		contract: &AccountIdOf<T>,
@@ -57,11 +85,110 @@ impl<T: Config> Pallet<T> {
 		ContractToInfo::<T>::insert(contract, info)
 	}
 
+	/// Handy wrapper to return code info.
+	pub fn code_info(code_id: CosmwasmCodeId) -> Result<CodeInfoOf<T>, Error<T>> {
+		CodeIdToInfo::<T>::get(code_id).ok_or(Error::<T>::CodeNotFound)
+	}
+
 	/// Handy wrapper to return contract info.
-	pub(crate) fn contract_info(contract: &AccountIdOf<T>) -> Result<ContractInfoOf<T>, Error<T>> {
+	pub fn contract_info(contract: &AccountIdOf<T>) -> Result<ContractInfoOf<T>, Error<T>> {
 		ContractToInfo::<T>::get(contract).ok_or(Error::<T>::ContractNotFound)
 	}
 
+	/// Iterate over the code history of a contract by sequence number, oldest entry first,
+	/// starting after `start_after`.
+	pub fn contract_history(
+		contract: &AccountIdOf<T>,
+		start_after: Option<u32>,
+	) -> Result<impl Iterator<Item = (u32, ContractCodeHistoryEntryOf<T>)>, Error<T>> {
+		Self::contract_info(contract)?;
+		let bounds = ContractToHistoryBounds::<T>::get(contract);
+		let start = start_after.map_or(bounds.head, |seq| seq.saturating_add(1)).max(bounds.head);
+		let end = bounds.head.saturating_add(bounds.len);
+		let contract = contract.clone();
+		Ok((start..end)
+			.filter_map(move |seq| ContractToHistory::<T>::get(&contract, seq).map(|e| (seq, e))))
+	}
+
+	/// Handy wrapper to return the original code, untouched by instrumentation.
+	pub fn pristine_code(code_id: CosmwasmCodeId) -> Result<ContractCodeOf<T>, Error<T>> {
+		PristineCode::<T>::get(code_id).ok_or(Error::<T>::CodeNotFound)
+	}
+
+	/// Handy wrapper to return who is allowed to upload codes.
+	pub fn code_upload_access() -> AccessConfigOf<T> {
+		CodeUploadAccess::<T>::get()
+	}
+
+	/// Iterate over the stored codes by ascending code id, starting after `start_after`.
+	pub fn codes(
+		start_after: Option<CosmwasmCodeId>,
+	) -> impl Iterator<Item = (CosmwasmCodeId, CodeInfoOf<T>)> {
+		let start = start_after.map_or(1, |code_id| code_id.saturating_add(1));
+		(start..=CurrentCodeId::<T>::get())
+			.filter_map(|code_id| CodeIdToInfo::<T>::get(code_id).map(|info| (code_id, info)))
+	}
+
+	/// Whether the module of a code is never evicted from the module cache.
+	pub fn is_pinned(code_id: CosmwasmCodeId) -> bool {
+		PinnedCodes::<T>::contains_key(code_id)
+	}
+
+	/// Iterate over the pinned codes, in storage order, starting after `start_after`.
+	pub fn pinned_codes(
+		start_after: Option<CosmwasmCodeId>,
+	) -> impl Iterator<Item = CosmwasmCodeId> {
+		match start_after {
+			Some(code_id) =>
+				PinnedCodes::<T>::iter_keys_from(PinnedCodes::<T>::hashed_key_for(code_id)),
+			None => PinnedCodes::<T>::iter_keys(),
+		}
+	}
+
+	/// Iterate over the contracts running `code_id`, starting after `start_after`.
+	pub fn contracts_by_code(
+		code_id: CosmwasmCodeId,
+		start_after: Option<AccountIdOf<T>>,
+	) -> impl Iterator<Item = AccountIdOf<T>> {
+		match start_after {
+			Some(contract) => CodeIdToContracts::<T>::iter_prefix_from(
+				code_id,
+				CodeIdToContracts::<T>::hashed_key_for(code_id, contract),
+			),
+			None => CodeIdToContracts::<T>::iter_prefix(code_id),
+		}
+		.map(|(contract, ())| contract)
+	}
+
+	/// Iterate over the (key, value) pairs of a contract storage, in key order, starting after the
+	/// `start_after` key. Fails while the background key indexing has not walked the contract yet.
+	pub fn contract_state(
+		contract: &AccountIdOf<T>,
+		start_after: Option<Vec<u8>>,
+	) -> Result<impl Iterator<Item = (Vec<u8>, Vec<u8>)>, Error<T>> {
+		ensure!(Self::is_contract_indexed(contract), Error::<T>::ContractNotIndexed);
+		let info = Self::contract_info(contract)?;
+		let child_info = Self::contract_child_trie(info.trie_id.as_ref());
+		let index = Self::contract_index_child_trie(info.trie_id.as_ref());
+		let mut key = start_after.unwrap_or_default();
+		Ok(core::iter::from_fn(move || {
+			key = sp_io::default_child_storage::next_key(index.storage_key(), &key)?;
+			let value = child::get_raw(&child_info, &Blake2_128Concat::hash(&key))?;
+			Some((key.clone(), value))
+		}))
+	}
+
+	/// Read an entry of a contract storage.
+	pub fn contract_raw_state(
+		contract: &AccountIdOf<T>,
+		key: &[u8],
+	) -> Result<Option<Vec<u8>>, Error<T>> {
+		let info = Self::contract_info(contract)?;
+		Ok(Self::with_db_entry(&info.trie_id, key, |child_trie, entry| {
+			child::get_raw(&child_trie, &entry)
+		}))
+	}
+
 	pub(crate) fn canonical_addr_to_account(
 		canonical: Vec<u8>,
 	) -> Result<AccountIdOf<T>, <T as VMPallet>::VmError> {
@@ -99,6 +226,35 @@ impl<T: Config> Pallet<T> {
 		ChildInfo::new_default(trie_id)
 	}
 
+	/// Build the [`ChildInfo`] of the ordered key index of a contract out of its trie id.
+	///
+	/// The index maps every raw key of the contract storage to an empty value, so that the
+	/// contract keys can be walked in order. It lives in its own child trie, as its keys are
+	/// controlled by the contract and could otherwise clash with the hashed entries.
+	pub(crate) fn contract_index_child_trie(trie_id: &[u8]) -> ChildInfo {
+		ChildInfo::new_default(&[trie_id, CONTRACT_INDEX_SUFFIX].concat())
+	}
+
+	/// Build the [`ChildInfo`] of the reversed key index of a contract out of its trie id.
+	///
+	/// The index maps every raw key of the contract storage, encoded by [`reverse_key`], to an
+	/// empty value, so that the contract keys can be walked in descending order.
+	pub(crate) fn contract_reverse_index_child_trie(trie_id: &[u8]) -> ChildInfo {
+		ChildInfo::new_default(&[trie_id, CONTRACT_REVERSE_INDEX_SUFFIX].concat())
+	}
+
+	/// Record `key` in the key indexes of the contract owning `trie_id`.
+	pub(crate) fn do_index_key(trie_id: &[u8], key: &[u8]) {
+		child::put_raw(&Self::contract_index_child_trie(trie_id), key, &[]);
+		child::put_raw(&Self::contract_reverse_index_child_trie(trie_id), &reverse_key(key), &[]);
+	}
+
+	/// Drop `key` from the key indexes of the contract owning `trie_id`.
+	pub(crate) fn do_unindex_key(trie_id: &[u8], key: &[u8]) {
+		child::kill(&Self::contract_index_child_trie(trie_id), key);
+		child::kill(&Self::contract_reverse_index_child_trie(trie_id), &reverse_key(key));
+	}
+
 	pub(crate) fn do_contract_meta(
 		address: AccountIdOf<T>,
 	) -> Result<CosmwasmContractMeta<CosmwasmAccount<T>>, CosmwasmVMError<T>> {
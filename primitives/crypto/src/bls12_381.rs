@@ -0,0 +1,82 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! BLS12-381 operations following the CosmWasm semantic.
+//!
+//! Errors are reported with the CosmWasm crypto error codes, which are handed back to contracts
+//! as is.
+
+#[cfg(feature = "std")]
+use cosmwasm_crypto::HashFunction;
+
+/// Aggregate a list of compressed G1 points into a single compressed G1 point.
+#[cfg(feature = "std")]
+pub fn bls12_381_aggregate_g1(g1s: &[u8]) -> Result<Vec<u8>, u32> {
+	cosmwasm_crypto::bls12_381_aggregate_g1(g1s)
+		.map(Vec::from)
+		.map_err(|e| e.code())
+}
+
+/// Aggregate a list of compressed G2 points into a single compressed G2 point.
+#[cfg(feature = "std")]
+pub fn bls12_381_aggregate_g2(g2s: &[u8]) -> Result<Vec<u8>, u32> {
+	cosmwasm_crypto::bls12_381_aggregate_g2(g2s)
+		.map(Vec::from)
+		.map_err(|e| e.code())
+}
+
+/// Check whether `e(ps[0], qs[0]) * ... * e(ps[n], qs[n]) == e(r, s)`.
+#[cfg(feature = "std")]
+pub fn bls12_381_pairing_equality(ps: &[u8], qs: &[u8], r: &[u8], s: &[u8]) -> Result<bool, u32> {
+	cosmwasm_crypto::bls12_381_pairing_equality(ps, qs, r, s).map_err(|e| e.code())
+}
+
+/// Hash a message to a compressed G1 point using the given domain separation tag.
+#[cfg(feature = "std")]
+pub fn bls12_381_hash_to_g1(hash_function: u32, msg: &[u8], dst: &[u8]) -> Result<Vec<u8>, u32> {
+	let hash_function = HashFunction::from_u32(hash_function).map_err(|e| e.code())?;
+	Ok(cosmwasm_crypto::bls12_381_hash_to_g1(hash_function, msg, dst).to_vec())
+}
+
+/// Hash a message to a compressed G2 point using the given domain separation tag.
+#[cfg(feature = "std")]
+pub fn bls12_381_hash_to_g2(hash_function: u32, msg: &[u8], dst: &[u8]) -> Result<Vec<u8>, u32> {
+	let hash_function = HashFunction::from_u32(hash_function).map_err(|e| e.code())?;
+	Ok(cosmwasm_crypto::bls12_381_hash_to_g2(hash_function, msg, dst).to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{bls12_381_aggregate_g1, bls12_381_hash_to_g1};
+
+	#[test]
+	fn bls12_381_aggregate_g1_test() {
+		let generator = hex::decode("97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb").unwrap();
+
+		assert_eq!(bls12_381_aggregate_g1(&generator), Ok(generator.clone()));
+		assert!(bls12_381_aggregate_g1(&generator[..47]).is_err());
+	}
+
+	#[test]
+	fn bls12_381_hash_to_g1_test() {
+		let point = bls12_381_hash_to_g1(0, b"message", b"dst").unwrap();
+
+		assert_eq!(point.len(), 48);
+		assert_eq!(bls12_381_hash_to_g1(0, b"message", b"dst"), Ok(point));
+		assert_eq!(bls12_381_hash_to_g1(1, b"message", b"dst"), Err(9));
+	}
+}
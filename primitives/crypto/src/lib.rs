@@ -17,9 +17,18 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod bls12_381;
 mod ecdsa;
 mod secp256k1;
+mod secp256r1;
 
+#[cfg(feature = "std")]
+pub use crate::bls12_381::{
+	bls12_381_aggregate_g1, bls12_381_aggregate_g2, bls12_381_hash_to_g1, bls12_381_hash_to_g2,
+	bls12_381_pairing_equality,
+};
 #[cfg(feature = "std")]
 pub use crate::secp256k1::secp256k1_ecdsa_verify;
+#[cfg(feature = "std")]
+pub use crate::secp256r1::{secp256r1_ecdsa_recover, secp256r1_ecdsa_verify};
 pub use ecdsa::EcdsaExt;
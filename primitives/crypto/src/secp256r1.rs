@@ -0,0 +1,71 @@
+// This file is part of Horizon.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "std")]
+use p256::ecdsa::{signature::hazmat::PrehashVerifier, RecoveryId, Signature, VerifyingKey};
+
+/// Verify a secp256r1 (NIST P-256) ECDSA signature over a prehashed message.
+///
+/// High-S signatures are normalized before verification.
+#[cfg(feature = "std")]
+pub fn secp256r1_ecdsa_verify(sig: &[u8], msg: &[u8], pub_key: &[u8]) -> bool {
+	let sig = match Signature::from_slice(sig) {
+		Ok(v) => v.normalize_s().unwrap_or(v),
+		Err(_) => return false,
+	};
+	let pub_key = match VerifyingKey::from_sec1_bytes(pub_key) {
+		Ok(v) => v,
+		Err(_) => return false,
+	};
+
+	pub_key.verify_prehash(msg, &sig).is_ok()
+}
+
+/// Recover the uncompressed secp256r1 (NIST P-256) public key from a prehashed message and its
+/// signature.
+#[cfg(feature = "std")]
+pub fn secp256r1_ecdsa_recover(sig: &[u8], msg: &[u8], recovery_id: u8) -> Option<Vec<u8>> {
+	let recovery_id = RecoveryId::from_byte(recovery_id)?;
+	let sig = Signature::from_slice(sig).ok()?;
+
+	VerifyingKey::recover_from_prehash(msg, &sig, recovery_id)
+		.map(|pub_key| pub_key.to_encoded_point(false).as_bytes().to_vec())
+		.ok()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{secp256r1_ecdsa_recover, secp256r1_ecdsa_verify};
+	use p256::ecdsa::SigningKey;
+
+	#[test]
+	fn secp256r1_ecdsa_verify_and_recover_test() {
+		let signing_key = SigningKey::from_slice(&[0x42; 32]).unwrap();
+		let pub_key = signing_key.verifying_key().to_encoded_point(false);
+		let msg = hex::decode("714d4bdfdbd0bd630ebdf93b1f6eba7d3c752e92bbab6c9d3d9c93e1777348bb")
+			.unwrap();
+		let (sig, recovery_id) = signing_key.sign_prehash_recoverable(&msg).unwrap();
+
+		assert!(secp256r1_ecdsa_verify(&sig.to_bytes(), &msg, pub_key.as_bytes()));
+		assert!(!secp256r1_ecdsa_verify(&sig.to_bytes(), &[0u8; 32], pub_key.as_bytes()));
+		assert_eq!(
+			secp256r1_ecdsa_recover(&sig.to_bytes(), &msg, recovery_id.to_byte()),
+			Some(pub_key.as_bytes().to_vec())
+		);
+		assert_eq!(secp256r1_ecdsa_recover(&sig.to_bytes(), &msg, 4), None);
+	}
+}
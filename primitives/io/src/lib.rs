@@ -20,6 +20,9 @@
 #![warn(missing_docs)]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use alloc::vec::Vec;
 use sp_runtime_interface::runtime_interface;
 
 /// Interface for working with crypto-related, handling Cosmos transactions and messages.
@@ -29,4 +32,66 @@ pub trait Cosmos {
 	fn secp256k1_ecdsa_verify(sig: &[u8], msg: &[u8], pub_key: &[u8]) -> bool {
 		hp_crypto::secp256k1_ecdsa_verify(sig, msg, pub_key)
 	}
+
+	/// Verify with secp256r1.
+	fn secp256r1_ecdsa_verify(sig: &[u8], msg: &[u8], pub_key: &[u8]) -> bool {
+		hp_crypto::secp256r1_ecdsa_verify(sig, msg, pub_key)
+	}
+
+	/// Recover the uncompressed public key with secp256r1.
+	fn secp256r1_ecdsa_recover(sig: &[u8], msg: &[u8], recovery_id: u8) -> Option<Vec<u8>> {
+		hp_crypto::secp256r1_ecdsa_recover(sig, msg, recovery_id)
+	}
+
+	/// Aggregate BLS12-381 G1 points.
+	fn bls12_381_aggregate_g1(g1s: &[u8]) -> Result<Vec<u8>, u32> {
+		hp_crypto::bls12_381_aggregate_g1(g1s)
+	}
+
+	/// Aggregate BLS12-381 G2 points.
+	fn bls12_381_aggregate_g2(g2s: &[u8]) -> Result<Vec<u8>, u32> {
+		hp_crypto::bls12_381_aggregate_g2(g2s)
+	}
+
+	/// Check a BLS12-381 pairing equality.
+	fn bls12_381_pairing_equality(ps: &[u8], qs: &[u8], r: &[u8], s: &[u8]) -> Result<bool, u32> {
+		hp_crypto::bls12_381_pairing_equality(ps, qs, r, s)
+	}
+
+	/// Hash to a BLS12-381 G1 point.
+	fn bls12_381_hash_to_g1(hash_function: u32, msg: &[u8], dst: &[u8]) -> Result<Vec<u8>, u32> {
+		hp_crypto::bls12_381_hash_to_g1(hash_function, msg, dst)
+	}
+
+	/// Hash to a BLS12-381 G2 point.
+	fn bls12_381_hash_to_g2(hash_function: u32, msg: &[u8], dst: &[u8]) -> Result<Vec<u8>, u32> {
+		hp_crypto::bls12_381_hash_to_g2(hash_function, msg, dst)
+	}
+}
+
+/// Instrumented codes of the pinned cosmwasm codes, kept by the node across runtime calls.
+#[cfg(feature = "std")]
+static PINNED_CODES: std::sync::Mutex<std::collections::BTreeMap<([u8; 32], u16), Vec<u8>>> =
+	std::sync::Mutex::new(std::collections::BTreeMap::new());
+
+/// Interface for keeping the instrumented codes of the pinned cosmwasm codes in the node.
+///
+/// Codes are identified by the hash of their pristine code and the version of their
+/// instrumentation, so that an entry never goes stale and is safe to share between forks.
+#[runtime_interface]
+pub trait Cosmwasm {
+	/// Get the instrumented code kept for a pinned code, if any.
+	fn pinned_code(code_hash: [u8; 32], version: u16) -> Option<Vec<u8>> {
+		PINNED_CODES.lock().unwrap().get(&(code_hash, version)).cloned()
+	}
+
+	/// Keep the instrumented code of a pinned code.
+	fn pin_code(code_hash: [u8; 32], version: u16, code: &[u8]) {
+		PINNED_CODES.lock().unwrap().insert((code_hash, version), code.to_vec());
+	}
+
+	/// Drop the instrumented code kept for a code which is no longer pinned.
+	fn unpin_code(code_hash: [u8; 32], version: u16) {
+		PINNED_CODES.lock().unwrap().remove(&(code_hash, version));
+	}
 }
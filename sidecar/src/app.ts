@@ -125,11 +125,7 @@ export class App {
 		}
 		const txService = new TxService(this.db, this.chainApi);
 		const abciService = new AbciService(this.chainApi, accountService, txService);
-		const balanceService = new BalanceService(
-			this.config,
-			this.chainApi,
-			accountService
-		);
+		const balanceService = new BalanceService(this.chainApi);
 		const distributionService = new DistributionService();
 		const nodeInfoService = new NodeInfoService(this.config);
 		const stakingService = new StakingService();
@@ -20,6 +20,16 @@ const rpc = {
       ],
       type: "SimulateResponse",
     },
+    allBalances: {
+      description: "Query the balances of an account.",
+      params: [
+        {
+          name: "request",
+          type: "Bytes",
+        },
+      ],
+      type: "Bytes",
+    },
   },
   cosmwasm: {
     query: {
@@ -44,6 +54,132 @@ const rpc = {
       ],
       type: "Bytes",
     },
+    contractInfo: {
+      description: "Query the metadata of a Cosmwasm contract.",
+      params: [
+        {
+          name: "request",
+          type: "Bytes",
+        },
+        {
+          name: "at",
+          type: "Option<BlockHash>",
+        },
+      ],
+      type: "Bytes",
+    },
+    contractHistory: {
+      description: "Query the code history of a Cosmwasm contract.",
+      params: [
+        {
+          name: "request",
+          type: "Bytes",
+        },
+        {
+          name: "at",
+          type: "Option<BlockHash>",
+        },
+      ],
+      type: "Bytes",
+    },
+    contractsByCode: {
+      description: "Query the Cosmwasm contracts running a code.",
+      params: [
+        {
+          name: "request",
+          type: "Bytes",
+        },
+        {
+          name: "at",
+          type: "Option<BlockHash>",
+        },
+      ],
+      type: "Bytes",
+    },
+    allContractState: {
+      description: "Query every entry of a Cosmwasm contract storage.",
+      params: [
+        {
+          name: "request",
+          type: "Bytes",
+        },
+        {
+          name: "at",
+          type: "Option<BlockHash>",
+        },
+      ],
+      type: "Bytes",
+    },
+    rawContractState: {
+      description: "Query an entry of a Cosmwasm contract storage.",
+      params: [
+        {
+          name: "request",
+          type: "Bytes",
+        },
+        {
+          name: "at",
+          type: "Option<BlockHash>",
+        },
+      ],
+      type: "Bytes",
+    },
+    code: {
+      description: "Query a Cosmwasm code.",
+      params: [
+        {
+          name: "request",
+          type: "Bytes",
+        },
+        {
+          name: "at",
+          type: "Option<BlockHash>",
+        },
+      ],
+      type: "Bytes",
+    },
+    codes: {
+      description: "Query the Cosmwasm codes.",
+      params: [
+        {
+          name: "request",
+          type: "Bytes",
+        },
+        {
+          name: "at",
+          type: "Option<BlockHash>",
+        },
+      ],
+      type: "Bytes",
+    },
+    pinnedCodes: {
+      description: "Query the pinned Cosmwasm codes.",
+      params: [
+        {
+          name: "request",
+          type: "Bytes",
+        },
+        {
+          name: "at",
+          type: "Option<BlockHash>",
+        },
+      ],
+      type: "Bytes",
+    },
+    params: {
+      description: "Query the Cosmwasm parameters.",
+      params: [
+        {
+          name: "request",
+          type: "Bytes",
+        },
+        {
+          name: "at",
+          type: "Option<BlockHash>",
+        },
+      ],
+      type: "Bytes",
+    },
   }
 };
 
@@ -13,6 +13,25 @@ import { SimulateRequest, SimulateResponse } from "cosmjs-types/cosmos/tx/v1beta
 import { TxService } from "./tx.js";
 import { QuerySmartContractStateRequest, QuerySmartContractStateResponse } from 'cosmjs-types/cosmwasm/wasm/v1/query.js'
 
+// `cosmwasm.wasm.v1.Query` methods served by the `cosmwasm` RPC, with protobuf encoded request and response.
+const WASM_QUERY_METHODS: Record<string, string> = {
+  ContractInfo: 'contractInfo',
+  ContractHistory: 'contractHistory',
+  ContractsByCode: 'contractsByCode',
+  AllContractState: 'allContractState',
+  RawContractState: 'rawContractState',
+  Code: 'code',
+  Codes: 'codes',
+  PinnedCodes: 'pinnedCodes',
+  Params: 'params',
+};
+
+// Names of the codespaces of `CosmosError`.
+const CODESPACES: Record<number, string> = {
+  0: 'sdk',
+  1: 'wasm',
+};
+
 export class AbciService implements ApiService {
   chainApi: ApiPromise;
   accountService: IAccountService;
@@ -110,6 +129,58 @@ export class AbciService implements ApiService {
         height: Long.fromString(height.toString()),
         codespace: "",
       };
+    } else if (path === '/cosmos.bank.v1beta1.Query/AllBalances') {
+      const height = await this.chainApi.query.system.number();
+      const response = await this.chainApi.rpc['cosmos']['allBalances'](`0x${data}`);
+
+      return {
+        code: 0,
+        log: "",
+        info: "",
+        index: Long.ZERO,
+        key: undefined,
+        value: response.toU8a(true),
+        proofOps: undefined,
+        height: Long.fromString(height.toString()),
+        codespace: "",
+      };
+    } else if (path.startsWith('/cosmwasm.wasm.v1.Query/') && WASM_QUERY_METHODS[path.slice('/cosmwasm.wasm.v1.Query/'.length)]) {
+      const method = WASM_QUERY_METHODS[path.slice('/cosmwasm.wasm.v1.Query/'.length)];
+      const height = await this.chainApi.query.system.number();
+      const blockHash = await this.chainApi.rpc.chain.getBlockHash(height.toString());
+
+      let response;
+      try {
+        response = await this.chainApi.rpc['cosmwasm'][method](`0x${data}`, blockHash.toString());
+      } catch (e: any) {
+        // Query errors carry the SCALE encoded `CosmosError { codespace, code }` of the runtime.
+        if (typeof e?.data !== 'string') throw e;
+        const [codespace, code] = Uint8Array.from(Buffer.from(e.data.startsWith('0x') ? e.data.slice(2) : e.data, 'hex'));
+
+        return {
+          code,
+          log: e.message,
+          info: "",
+          index: Long.ZERO,
+          key: undefined,
+          value: new Uint8Array(),
+          proofOps: undefined,
+          height: Long.fromString(height.toString()),
+          codespace: CODESPACES[codespace] ?? 'sdk',
+        };
+      }
+
+      return {
+        code: 0,
+        log: "",
+        info: "",
+        index: Long.ZERO,
+        key: undefined,
+        value: response.toU8a(true),
+        proofOps: undefined,
+        height: Long.fromString(height.toString()),
+        codespace: "",
+      };
     } else {
       throw new Error("unexpected path");
     }
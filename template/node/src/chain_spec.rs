@@ -28,8 +28,11 @@ use sp_runtime::traits::{IdentifyAccount, Verify};
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
 
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
-pub type ChainSpec =
-	sc_service::GenericChainSpec<RuntimeGenesisConfig, NoExtension, hp_io::cosmos::HostFunctions>;
+pub type ChainSpec = sc_service::GenericChainSpec<
+	RuntimeGenesisConfig,
+	NoExtension,
+	(hp_io::cosmos::HostFunctions, hp_io::cosmwasm::HostFunctions),
+>;
 
 /// Generate a crypto pair from seed.
 pub fn get_from_seed<TPublic: Public>(seed: &str) -> <TPublic::Pair as Pair>::Public {
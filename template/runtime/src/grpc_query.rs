@@ -0,0 +1,60 @@
+// This file is part of Horizion.
+
+// Copyright (C) 2023 Haderech Pte. Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use alloc::{
+	string::{String, ToString},
+	vec::Vec,
+};
+use core::marker::PhantomData;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+use pallet_cosmos_x_auth::grpc_query::{account, QUERY_ACCOUNT_PATH};
+use pallet_cosmos_x_bank::grpc_query::{
+	all_balances_request, all_balances_response, denom_metadata, QUERY_ALL_BALANCES_PATH,
+	QUERY_DENOM_METADATA_PATH,
+};
+use pallet_cosmwasm::runtimes::vm::{CosmwasmVM, CosmwasmVMError};
+
+pub struct GrpcQueryRouter<T>(PhantomData<T>);
+impl<T> pallet_cosmwasm::grpc_query::GrpcQueryRouter<T> for GrpcQueryRouter<T>
+where
+	T: pallet_cosmos::Config
+		+ pallet_cosmwasm::Config<AccountIdExtended = <T as frame_system::Config>::AccountId>,
+{
+	fn route<'a>(
+		vm: &mut CosmwasmVM<'a, T>,
+		path: &str,
+		data: &[u8],
+	) -> Result<Option<Result<Vec<u8>, String>>, CosmwasmVMError<T>> {
+		Ok(match path {
+			QUERY_ACCOUNT_PATH => Some(account::<T>(data)),
+			// Charged per registered denom, as `BankQuery::AllBalances`.
+			QUERY_ALL_BALANCES_PATH => match all_balances_request::<T>(data) {
+				Ok(who) => {
+					let balances = pallet_cosmwasm::Pallet::<T>::do_all_balance(vm, &who)?
+						.into_iter()
+						.map(|coin| Coin { denom: coin.denom, amount: coin.amount.to_string() })
+						.collect();
+					Some(Ok(all_balances_response(balances)))
+				},
+				Err(e) => Some(Err(e)),
+			},
+			QUERY_DENOM_METADATA_PATH => Some(denom_metadata::<T>(data)),
+			_ => None,
+		})
+	}
+}
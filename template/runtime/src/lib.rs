@@ -32,6 +32,7 @@ mod accounts;
 mod ante;
 mod assets;
 mod compat;
+mod grpc_query;
 mod msgs;
 
 use alloc::{boxed::Box, format, string::String, vec::Vec};
@@ -45,7 +46,7 @@ use frame_support::{
 	parameter_types,
 	traits::{
 		tokens::{fungible, Fortitude, Preservation},
-		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, ConstU8, OnTimestampSet,
+		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, ConstU8, Get, OnTimestampSet,
 	},
 	weights::{
 		constants::{RocksDbWeight as RuntimeDbWeight, WEIGHT_REF_TIME_PER_MILLIS},
@@ -76,7 +77,8 @@ use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, ecdsa::Public, OpaqueMetadata, H160};
 use sp_runtime::{
-	codec, create_runtime_str, generic, impl_opaque_keys,
+	codec::{self, Encode},
+	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
 		AccountIdLookup, BlakeTwo256, Block as BlockT, Convert, DispatchInfoOf, Dispatchable,
 		IdentifyAccount, NumberFor, One, PostDispatchInfoOf, Verify,
@@ -394,16 +396,29 @@ parameter_types! {
 	pub const MaxContractTrieIdSize: u32 = Hash::len_bytes() as u32;
 	pub const MaxInstantiateSaltSize: u32 = 128;
 	pub const MaxFundsAssets: u32 = 32;
+	pub const MaxCodeHistoryEntries: u32 = 16;
+	pub const ModuleCacheSize: u32 = 32;
 	pub const CodeTableSizeLimit: u32 = 4096;
 	pub const CodeGlobalVariableLimit: u32 = 256;
 	pub const CodeParameterLimit: u32 = 128;
 	pub const CodeBranchTableSizeLimit: u32 = 256;
 	pub const CodeStorageByteDeposit: u32 = 1_000_000;
-	pub const ContractStorageByteReadPrice: u32 = 1;
-	pub const ContractStorageByteWritePrice: u32 = 1;
 	pub WasmCostRules: CostRules<Runtime> = Default::default();
 }
 
+/// The Cosmos address of the sudo key, allowed to submit the governance messages of `x/wasm` such
+/// as `MsgSudoContract`.
+///
+/// It follows `Sudo::set_key`, and is empty, thus matching no message, once the key is removed.
+pub struct WasmAuthority;
+impl Get<String> for WasmAuthority {
+	fn get() -> String {
+		Sudo::key()
+			.map(<accounts::AccountToAddr<Runtime> as Convert<AccountId, String>>::convert)
+			.unwrap_or_default()
+	}
+}
+
 impl pallet_cosmwasm::Config for Runtime {
 	const MAX_FRAMES: u8 = 64;
 	type RuntimeEvent = RuntimeEvent;
@@ -423,6 +438,8 @@ impl pallet_cosmwasm::Config for Runtime {
 	type MaxContractTrieIdSize = MaxContractTrieIdSize;
 	type MaxInstantiateSaltSize = MaxInstantiateSaltSize;
 	type MaxFundsAssets = MaxFundsAssets;
+	type MaxCodeHistoryEntries = MaxCodeHistoryEntries;
+	type ModuleCacheSize = ModuleCacheSize;
 
 	type CodeTableSizeLimit = CodeTableSizeLimit;
 	type CodeGlobalVariableLimit = CodeGlobalVariableLimit;
@@ -431,8 +448,6 @@ impl pallet_cosmwasm::Config for Runtime {
 	type CodeParameterLimit = CodeParameterLimit;
 	type CodeBranchTableSizeLimit = CodeBranchTableSizeLimit;
 	type CodeStorageByteDeposit = CodeStorageByteDeposit;
-	type ContractStorageByteReadPrice = ContractStorageByteReadPrice;
-	type ContractStorageByteWritePrice = ContractStorageByteWritePrice;
 
 	type WasmCostRules = WasmCostRules;
 	type UnixTime = Timestamp;
@@ -441,13 +456,21 @@ impl pallet_cosmwasm::Config for Runtime {
 	// TODO: Add precompile to use execute or query pallet
 	type PalletHook = ();
 
-	type UploadWasmOrigin = frame_system::EnsureSigned<Self::AccountId>;
+	type AnyMsgHandler = pallet_cosmos_x_wasm::any_msg_handler::AnyMsgHandler<Runtime>;
+
+	type GrpcQueryRouter = grpc_query::GrpcQueryRouter<Runtime>;
+
+	type CustomHandler = pallet_cosmos_x_wasm::custom_handler::CustomHandler<Runtime>;
+
+	type IbcHandler = ();
 
 	type ExecuteWasmOrigin = frame_system::EnsureSigned<Self::AccountId>;
 
 	type NativeDenom = NativeDenom;
 
 	type NativeAssetId = NativeAssetId;
+
+	type AllBalances = pallet_cosmos::AllBalances<Runtime>;
 }
 
 impl pallet_sudo::Config for Runtime {
@@ -499,6 +522,13 @@ pub type UncheckedExtrinsic =
 	fp_self_contained::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+/// Pending storage migrations, run on runtime upgrade.
+pub type Migrations = (
+	pallet_cosmwasm::migrations::v1::MigrateToV1<Runtime>,
+	pallet_cosmwasm::migrations::v2::MigrateToV2<Runtime>,
+	pallet_cosmwasm::migrations::v3::MigrateToV3<Runtime>,
+	pallet_cosmwasm::migrations::v4::MigrateToV4<Runtime>,
+);
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -506,6 +536,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 impl fp_self_contained::SelfContainedCall for RuntimeCall {
@@ -649,6 +680,7 @@ impl Runtime {
 }
 
 impl_runtime_apis! {
+	#[api_version(2)]
 	impl cosmos_runtime_api::CosmosRuntimeApi<Block> for Runtime {
 		fn convert_tx(tx_bytes: Vec<u8>) -> <Block as BlockT>::Extrinsic {
 			UncheckedExtrinsic::new_unsigned(
@@ -673,8 +705,14 @@ impl_runtime_apis! {
 					}
 				}).ok_or(SimulateError::InternalError("Cosmos events does not exist".into()))
 		}
+
+		fn all_balances(request: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+			pallet_cosmos_x_bank::grpc_query::all_balances::<Runtime>(&request)
+				.map_err(String::into_bytes)
+		}
 	}
 
+	#[api_version(2)]
 	impl cosmwasm_runtime_api::CosmwasmRuntimeApi<Block, Vec<u8>> for Runtime {
 		fn query(
 			contract: String,
@@ -691,6 +729,51 @@ impl_runtime_apis! {
 				Err(err) => Err(alloc::format!("{:?}", err).into_bytes())
 			}
 		}
+
+		fn contract_info(request: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+			pallet_cosmos_x_wasm::query::contract_info::<Runtime>(&request)
+				.map_err(|err| err.encode())
+		}
+
+		fn contract_history(request: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+			pallet_cosmos_x_wasm::query::contract_history::<Runtime>(&request)
+				.map_err(|err| err.encode())
+		}
+
+		fn contracts_by_code(request: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+			pallet_cosmos_x_wasm::query::contracts_by_code::<Runtime>(&request)
+				.map_err(|err| err.encode())
+		}
+
+		fn all_contract_state(request: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+			pallet_cosmos_x_wasm::query::all_contract_state::<Runtime>(&request)
+				.map_err(|err| err.encode())
+		}
+
+		fn raw_contract_state(request: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+			pallet_cosmos_x_wasm::query::raw_contract_state::<Runtime>(&request)
+				.map_err(|err| err.encode())
+		}
+
+		fn code(request: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+			pallet_cosmos_x_wasm::query::code::<Runtime>(&request)
+				.map_err(|err| err.encode())
+		}
+
+		fn codes(request: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+			pallet_cosmos_x_wasm::query::codes::<Runtime>(&request)
+				.map_err(|err| err.encode())
+		}
+
+		fn pinned_codes(request: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+			pallet_cosmos_x_wasm::query::pinned_codes::<Runtime>(&request)
+				.map_err(|err| err.encode())
+		}
+
+		fn params(request: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+			pallet_cosmos_x_wasm::query::params::<Runtime>(&request)
+				.map_err(|err| err.encode())
+		}
 	}
 
 	impl sp_api::Core<Block> for Runtime {
@@ -21,8 +21,9 @@ use core::marker::PhantomData;
 use cosmos_sdk_proto::{
 	cosmos::bank::v1beta1::MsgSend,
 	cosmwasm::wasm::v1::{
-		MsgExecuteContract, MsgInstantiateContract2, MsgMigrateContract, MsgStoreCode,
-		MsgUpdateAdmin,
+		MsgClearAdmin, MsgExecuteContract, MsgInstantiateContract, MsgInstantiateContract2,
+		MsgMigrateContract, MsgPinCodes, MsgStoreAndInstantiateContract, MsgStoreCode,
+		MsgSudoContract, MsgUnpinCodes, MsgUpdateAdmin, MsgUpdateInstantiateConfig,
 	},
 	Any,
 };
@@ -30,15 +31,20 @@ use hp_crypto::EcdsaExt;
 use pallet_cosmos_types::{any_match, context, msgservice::MsgHandler};
 use pallet_cosmos_x_bank::msgs::MsgSendHandler;
 use pallet_cosmos_x_wasm::msgs::{
-	MsgExecuteContractHandler, MsgInstantiateContract2Handler, MsgMigrateContractHandler,
-	MsgStoreCodeHandler, MsgUpdateAdminHandler,
+	MsgClearAdminHandler, MsgExecuteContractHandler, MsgInstantiateContract2Handler,
+	MsgInstantiateContractHandler, MsgMigrateContractHandler, MsgPinCodesHandler,
+	MsgRemoveCodeHandler, MsgStoreAndInstantiateContractHandler, MsgStoreCodeHandler,
+	MsgSudoContractHandler, MsgUnpinCodesHandler, MsgUpdateAdminHandler,
+	MsgUpdateInstantiateConfigHandler,
 };
+use pallet_cosmos_x_wasm_types::proto::MsgRemoveCode;
+use sp_core::{crypto::UncheckedFrom, H256};
 
 pub struct MsgServiceRouter<T>(PhantomData<T>);
 impl<T, Context> pallet_cosmos_types::msgservice::MsgServiceRouter<Context> for MsgServiceRouter<T>
 where
 	T: frame_system::Config + pallet_cosmos::Config + pallet_cosmwasm::Config,
-	T::AccountId: EcdsaExt,
+	T::AccountId: EcdsaExt + UncheckedFrom<H256>,
 	Context: context::traits::Context,
 {
 	fn route(msg: &Any) -> Option<Box<dyn MsgHandler<Context>>> {
@@ -46,10 +52,18 @@ where
 			msg, {
 				MsgSend => Some(Box::<MsgSendHandler<T>>::default()),
 				MsgStoreCode => Some(Box::<MsgStoreCodeHandler<T>>::default()),
+				MsgInstantiateContract => Some(Box::<MsgInstantiateContractHandler<T>>::default()),
 				MsgInstantiateContract2 => Some(Box::<MsgInstantiateContract2Handler<T>>::default()),
 				MsgExecuteContract => Some(Box::<MsgExecuteContractHandler<T>>::default()),
 				MsgMigrateContract => Some(Box::<MsgMigrateContractHandler<T>>::default()),
 				MsgUpdateAdmin => Some(Box::<MsgUpdateAdminHandler<T>>::default()),
+				MsgClearAdmin => Some(Box::<MsgClearAdminHandler<T>>::default()),
+				MsgStoreAndInstantiateContract => Some(Box::<MsgStoreAndInstantiateContractHandler<T, crate::WasmAuthority>>::default()),
+				MsgUpdateInstantiateConfig => Some(Box::<MsgUpdateInstantiateConfigHandler<T, crate::WasmAuthority>>::default()),
+				MsgSudoContract => Some(Box::<MsgSudoContractHandler<T, crate::WasmAuthority>>::default()),
+				MsgPinCodes => Some(Box::<MsgPinCodesHandler<T, crate::WasmAuthority>>::default()),
+				MsgUnpinCodes => Some(Box::<MsgUnpinCodesHandler<T, crate::WasmAuthority>>::default()),
+				MsgRemoveCode => Some(Box::<MsgRemoveCodeHandler<T, crate::WasmAuthority>>::default()),
 			},
 			None
 		)